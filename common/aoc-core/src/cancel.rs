@@ -0,0 +1,100 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Result;
+
+// region:    --- Token
+
+/// Asks a running solver to stop. Cancellation is cooperative: a solver that can run for a
+/// long time calls `check` in its loops and gives up with `Cancelled` once the token is set.
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// endregion: --- Token
+
+// region:    --- Current Token
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with `token` installed for the calling thread, so `check` inside it sees it.
+pub fn with_token<T>(token: Token, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.replace(Some(token));
+    let result = f();
+    CURRENT.set(previous);
+
+    result
+}
+
+// false on threads without a token, e.g. when a day is run through its own binary
+pub fn is_cancelled() -> bool {
+    CURRENT.with_borrow(|token| token.as_ref().is_some_and(Token::is_cancelled))
+}
+
+pub fn check() -> Result<()> {
+    if is_cancelled() {
+        return Err(Cancelled.into());
+    }
+
+    Ok(())
+}
+
+// endregion: --- Current Token
+
+/// The error of a solver that stopped because it was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_check() {
+        assert!(check().is_ok());
+
+        let token = Token::new();
+        with_token(token.clone(), || {
+            assert!(check().is_ok());
+            token.cancel();
+
+            let err = check().unwrap_err();
+            assert!(err.is::<Cancelled>());
+        });
+
+        // the token only applies inside `with_token`
+        assert!(check().is_ok());
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+// how many smaller inputs are tried at most while shrinking a counterexample
+const SHRINK_BUDGET: usize = 10_000;
+
+/// An input on which a fast solver and its reference disagree, shrunk as far as it still does.
+#[derive(Debug)]
+pub struct Counterexample<I, O> {
+    pub input: I,
+    // a panic is kept with its message
+    pub expected: Result<O, String>,
+    pub actual: Result<O, String>,
+}
+
+impl<I: fmt::Debug, O: fmt::Debug> fmt::Display for Counterexample<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "the solvers disagree on {:?}", self.input)?;
+        writeln!(f, "reference: {:?}", self.expected)?;
+        write!(f, "fast:      {:?}", self.actual)
+    }
+}
+
+/// Runs `reference`, slow but plainly right, and `fast` on every input, and shrinks the first
+/// one they disagree on with `smaller`, which gives the candidates one step smaller.
+///
+/// A panic of either side counts as a disagreement.
+pub fn check<I, O: PartialEq>(
+    inputs: impl IntoIterator<Item = I>,
+    reference: impl Fn(&I) -> O,
+    fast: impl Fn(&I) -> O,
+    smaller: impl Fn(&I) -> Vec<I>,
+) -> Result<(), Counterexample<I, O>> {
+    let compare = |input: I| {
+        let expected = catch(|| reference(&input));
+        let actual = catch(|| fast(&input));
+        match (&expected, &actual) {
+            (Ok(expected), Ok(actual)) if expected == actual => None,
+            _ => Some(Counterexample {
+                input,
+                expected,
+                actual,
+            }),
+        }
+    };
+
+    let Some(mut counterexample) = inputs.into_iter().find_map(compare) else {
+        return Ok(());
+    };
+
+    // the first smaller input still disagreeing replaces it, until none does
+    let mut budget = SHRINK_BUDGET;
+    'shrink: while budget > 0 {
+        for candidate in smaller(&counterexample.input) {
+            if budget == 0 {
+                break 'shrink;
+            }
+            budget -= 1;
+            if let Some(smaller) = compare(candidate) {
+                counterexample = smaller;
+                continue 'shrink;
+            }
+        }
+        break;
+    }
+
+    Err(counterexample)
+}
+
+fn catch<O>(solve: impl FnOnce() -> O) -> Result<O, String> {
+    panic::catch_unwind(AssertUnwindSafe(solve)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+/// The lists with one item left out, for `smaller`.
+pub fn without_one<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    (0..items.len())
+        .map(|skipped| {
+            let mut fewer = items.to_vec();
+            fewer.remove(skipped);
+            fewer
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_check() {
+        let sum = |numbers: &Vec<u32>| numbers.iter().sum::<u32>();
+        // off by one as soon as there is a 3
+        let wrong_sum = |numbers: &Vec<u32>| sum(numbers) + u32::from(numbers.contains(&3));
+        let inputs = vec![vec![1, 2], vec![5, 3, 4, 3, 1], vec![3]];
+
+        assert!(check(inputs.clone(), sum, sum, |numbers| without_one(numbers)).is_ok());
+
+        let counterexample =
+            check(inputs, sum, wrong_sum, |numbers| without_one(numbers)).unwrap_err();
+        assert_eq!(vec![3], counterexample.input);
+        assert_eq!(Ok(3), counterexample.expected);
+        assert_eq!(Ok(4), counterexample.actual);
+    }
+
+    #[test]
+    fn test_check_panics() {
+        // the panic hook is shared by the tests running alongside, so the panic is left to print
+        let first = |numbers: &Vec<u32>| numbers[0];
+        let counterexample = check(
+            vec![vec![], vec![1]],
+            |numbers| numbers.first().copied().unwrap_or_default(),
+            first,
+            |numbers| without_one(numbers),
+        )
+        .unwrap_err();
+
+        assert!(counterexample.input.is_empty());
+        assert_eq!(Ok(0), counterexample.expected);
+        let message = counterexample.actual.unwrap_err();
+        assert!(message.contains("index out of bounds"), "{message}");
+    }
+
+    #[test]
+    fn test_without_one() {
+        assert_eq!(
+            vec![vec![2, 3], vec![1, 3], vec![1, 2]],
+            without_one(&[1, 2, 3])
+        );
+    }
+}
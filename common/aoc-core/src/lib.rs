@@ -0,0 +1,10 @@
+// region:    --- Modules
+pub use params::Params;
+
+pub mod cancel;
+pub mod differential;
+pub mod log;
+pub mod params;
+pub mod rng;
+pub mod vault;
+// endregion: --- Modules
@@ -0,0 +1,232 @@
+use std::{
+    fmt,
+    io::Write,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        RwLock,
+    },
+};
+
+// region:    --- Level
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    // `-v` flags counted on top of warnings, which are always shown by the runner
+    pub fn from_verbosity(count: u8) -> Self {
+        match count {
+            0 => Level::Warn,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+
+        f.pad(name)
+    }
+}
+
+// endregion: --- Level
+
+// region:    --- Filter
+
+/// Which messages get through: a level for everything, and overrides for some targets.
+///
+/// A target is the module path of the call site, e.g. `day_20` or `day_20::simulation`.
+/// Overrides match a crate or module and everything below it, `-` is read as `_` so
+/// `day-20` can be given as it is named on disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    level: Option<Level>,
+    targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn new(level: Option<Level>) -> Self {
+        Self {
+            level,
+            targets: vec![],
+        }
+    }
+
+    pub fn target(mut self, target: &str, level: Level) -> Self {
+        self.targets.push((target.replace('-', "_"), level));
+        self
+    }
+
+    pub fn level_for(&self, target: &str) -> Option<Level> {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| {
+                target == prefix
+                    || target
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.level, |(_, level)| Some(*level))
+    }
+
+    // the most verbose level any target can reach
+    pub fn max_level(&self) -> Option<Level> {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .chain(self.level)
+            .max()
+    }
+}
+
+// endregion: --- Filter
+
+// region:    --- Global State
+
+// 0 means off, otherwise a `Level`. Checked before anything else so a disabled call site
+// costs one atomic load and never formats its arguments.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+static FILTER: RwLock<Filter> = RwLock::new(Filter {
+    level: None,
+    targets: vec![],
+});
+
+/// Installs the filter for the whole process. Logging is off until this is called.
+pub fn set_filter(filter: Filter) {
+    let max_level = filter.max_level().map_or(0, |level| level as u8);
+
+    *FILTER.write().expect("log filter lock") = filter;
+    MAX_LEVEL.store(max_level, Ordering::Relaxed);
+}
+
+#[inline]
+pub fn enabled(level: Level, target: &str) -> bool {
+    if level as u8 > MAX_LEVEL.load(Ordering::Relaxed) {
+        return false;
+    }
+
+    FILTER
+        .read()
+        .expect("log filter lock")
+        .level_for(target)
+        .is_some_and(|max| level <= max)
+}
+
+// call sites go through the macros, which check `enabled` first
+pub fn write(level: Level, target: &str, args: fmt::Arguments) {
+    let mut stderr = std::io::stderr().lock();
+    let _ = writeln!(stderr, "[{level:<5} {target}] {args}");
+}
+
+// endregion: --- Global State
+
+// region:    --- Macros
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level, module_path!()) {
+            $crate::log::write($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+/// True if the calling module logs at `level`, to guard work done only for a message.
+#[macro_export]
+macro_rules! log_enabled {
+    ($level:expr) => {
+        $crate::log::enabled($level, module_path!())
+    };
+}
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+// endregion: --- Macros
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[rstest]
+    #[case(None, "day_21")]
+    #[case(Some(Level::Trace), "day_20")]
+    #[case(Some(Level::Trace), "day_20::simulation::module")]
+    #[case(None, "day_200")]
+    #[case(Some(Level::Debug), "day_24")]
+    #[case(Some(Level::Info), "day_24::parser")]
+    fn test_level_for(#[case] expected: Option<Level>, #[case] target: &str) {
+        let filter = Filter::new(None)
+            .target("day-20", Level::Trace)
+            .target("day_24", Level::Debug)
+            .target("day_24::parser", Level::Info);
+
+        assert_eq!(expected, filter.level_for(target));
+    }
+
+    #[test]
+    fn test_max_level() {
+        assert_eq!(None, Filter::default().max_level());
+        assert_eq!(
+            Some(Level::Trace),
+            Filter::new(Some(Level::Warn))
+                .target("day_20", Level::Trace)
+                .max_level()
+        );
+    }
+
+    #[test]
+    fn test_disabled_arguments_are_not_evaluated() {
+        // the global filter starts off and no test in this crate installs one
+        let mut evaluated = false;
+        crate::trace!("{}", {
+            evaluated = true;
+            "never"
+        });
+
+        assert!(!evaluated);
+    }
+}
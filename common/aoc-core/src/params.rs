@@ -0,0 +1,137 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Result};
+
+/// Named puzzle constants, like the test area of 2023 day 24, that a solution reads instead of
+/// hard-coding. Values are kept as text and parsed on use, so the runner and the manifest can
+/// override them without knowing their types.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &str, value: impl ToString) {
+        self.values.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn get<T>(&self, name: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .values
+            .get(name)
+            .ok_or_else(|| anyhow!("missing parameter `{name}`"))?;
+
+        value
+            .parse()
+            .map_err(|err| anyhow!("parameter `{name}` = `{value}`: {err}"))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    // `overrides` may only replace parameters declared here, to catch typos in names
+    pub fn overridden_by(&self, overrides: &Params) -> Result<Self> {
+        let mut params = self.clone();
+        for (name, value) in overrides.iter() {
+            if !self.contains(name) {
+                let known = self.values.keys().cloned().collect::<Vec<_>>().join(", ");
+                return Err(anyhow!(
+                    "unknown parameter `{name}`, expected one of: {known}"
+                ));
+            }
+            params.set(name, value);
+        }
+
+        Ok(params)
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+// `name=value`, as given on the command line
+pub fn parse_assignment(input: &str) -> Result<(String, String)> {
+    let (name, value) = input
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected `name=value`, got `{input}`"))?;
+
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_get() {
+        let params = Params::new().with("steps", 64).with("window_start", 7.5);
+
+        assert_eq!(64, params.get::<usize>("steps").unwrap());
+        assert_eq!(7.5, params.get::<f64>("window_start").unwrap());
+        assert!(params.get::<usize>("window_start").is_err());
+        assert!(params.get::<usize>("offset").is_err());
+    }
+
+    #[test]
+    fn test_overridden_by() {
+        let defaults = Params::new().with("window_start", 7).with("window_end", 27);
+
+        let params = defaults
+            .overridden_by(&Params::new().with("window_end", 20))
+            .unwrap();
+        assert_eq!(27, defaults.get::<i64>("window_end").unwrap());
+        assert_eq!(20, params.get::<i64>("window_end").unwrap());
+
+        assert!(defaults
+            .overridden_by(&Params::new().with("window", 20))
+            .is_err());
+    }
+
+    #[rstest]
+    #[case(Some(("scale", "10")), "scale=10")]
+    #[case(Some(("steps", "6")), " steps = 6")]
+    #[case(Some(("offset", "")), "offset=")]
+    #[case(None, "scale")]
+    fn test_parse_assignment(#[case] expected: Option<(&str, &str)>, #[case] input: &str) {
+        let result = parse_assignment(input).ok();
+
+        assert_eq!(
+            expected.map(|(name, value)| (name.to_string(), value.to_string())),
+            result
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::Params;
+
+/// The parameter a day that guesses takes its seed from, set by the day, the manifest or
+/// `aoc run --seed`.
+pub const SEED: &str = "seed";
+
+/// The seed when none is given, fixed so that two runs take the same steps.
+pub const DEFAULT_SEED: u64 = 2023;
+
+/// The random numbers of a seed, for the solutions and the input generators alike, so a seed
+/// is all it takes to repeat a run.
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+pub fn from_params(params: &Params) -> Result<StdRng> {
+    Ok(seeded(params.get(SEED)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_from_params() {
+        let params = Params::new().with(SEED, DEFAULT_SEED);
+        let draw = |mut rng: StdRng| (0..4).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+
+        assert_eq!(
+            draw(seeded(DEFAULT_SEED)),
+            draw(from_params(&params).unwrap())
+        );
+        assert_ne!(draw(seeded(1)), draw(seeded(2)));
+        assert!(from_params(&Params::new()).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    env, fs,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, OsRng},
+    AeadCore, ChaCha20Poly1305, Nonce,
+};
+
+// region:    --- Key
+
+/// Where the key is looked for, overriding `~/.config/aoc/input.key`.
+pub const KEY_VAR: &str = "AOC_KEY_FILE";
+
+// written first, so a file of another format is told apart from a wrong key
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+const NONCE_LEN: usize = 12;
+
+/// The symmetric key the puzzle inputs are encrypted with, kept as hex in a local file.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng).into())
+    }
+
+    // `$AOC_KEY_FILE`, or next to the runner's config
+    pub fn path() -> Option<PathBuf> {
+        env::var_os(KEY_VAR).map(PathBuf::from).or_else(|| {
+            env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+                .map(|dir| dir.join("aoc/input.key"))
+        })
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path().ok_or_else(|| anyhow!("no key file, set {KEY_VAR}"))?;
+        Self::read(&path)
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).with_context(|| format!("in {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Err(anyhow!(
+                "the inputs are encrypted and there is no key at {}, copy it there or set {KEY_VAR}",
+                path.display()
+            )),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let content = content.trim();
+        if content.len() != 64 {
+            return Err(anyhow!("a key is 64 hex digits, not {}", content.len()));
+        }
+
+        let mut key = [0; 32];
+        for (byte, digits) in key.iter_mut().zip(content.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits)?;
+            *byte = u8::from_str_radix(digits, 16)
+                .with_context(|| format!("{digits:?} is not a hex byte"))?;
+        }
+
+        Ok(Self(key))
+    }
+
+    // only readable by its owner, and never over an existing key
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let hex = self
+            .0
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        options
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{hex}"))
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(&nonce, plaintext)
+            .expect("a puzzle input is far below the size limit");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        let sealed = sealed
+            .strip_prefix(MAGIC)
+            .filter(|sealed| sealed.len() >= NONCE_LEN)
+            .ok_or_else(|| anyhow!("not an encrypted input"))?;
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("the key does not decrypt it, or it was changed"))
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.0.into())
+    }
+}
+
+// keeps the key out of logs and panics
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Key(..)")
+    }
+}
+
+// endregion: --- Key
+
+// region:    --- Inputs
+
+/// Where the encrypted copy of `path` is kept, `input.txt.enc` for `input.txt`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".enc");
+    path.with_file_name(name)
+}
+
+/// The input at `path`, decrypted with the local key when only its encrypted copy is there.
+///
+/// A plain file wins, so a freshly fetched input is used before it is encrypted. `None` when
+/// there is neither.
+pub fn read(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(input) => return Ok(Some(input)),
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+    }
+
+    let encrypted = encrypted_path(path);
+    let sealed = match fs::read(&encrypted) {
+        Ok(sealed) => sealed,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", encrypted.display())),
+    };
+    let input = Key::load()
+        .and_then(|key| key.decrypt(&sealed))
+        .with_context(|| format!("decrypting {}", encrypted.display()))?;
+
+    Ok(Some(String::from_utf8(input).with_context(|| {
+        format!("{} is not text", encrypted.display())
+    })?))
+}
+
+/// The `input.txt` of the day crate in `dir`, see [`read`].
+pub fn read_input(dir: impl AsRef<Path>) -> Result<String> {
+    let path = dir.as_ref().join("input.txt");
+    read(&path)?.ok_or_else(|| anyhow!("no input at {}", path.display()))
+}
+
+/// The input of the day crate it is used in, instead of `include_str!("../../input.txt")`,
+/// which would not build while the input is only there encrypted.
+#[macro_export]
+macro_rules! input {
+    () => {
+        $crate::vault::read_input(env!("CARGO_MANIFEST_DIR"))
+            .unwrap_or_else(|err| panic!("Failed to read the input: {err:#}"))
+    };
+}
+
+// endregion: --- Inputs
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_encrypt() {
+        let key = Key::generate();
+        let sealed = key.encrypt(b"seeds: 79 14 55 13");
+
+        assert_ne!(key.encrypt(b"seeds: 79 14 55 13"), sealed);
+        assert_eq!(
+            b"seeds: 79 14 55 13".to_vec(),
+            key.decrypt(&sealed).unwrap()
+        );
+        assert!(Key::generate().decrypt(&sealed).is_err());
+
+        let mut changed = sealed.clone();
+        *changed.last_mut().unwrap() ^= 1;
+        assert!(key.decrypt(&changed).is_err());
+        assert!(key.decrypt(b"seeds: 79 14 55 13").is_err());
+    }
+
+    #[test]
+    fn test_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aoc/input.key");
+        let key = Key::generate();
+
+        key.write(&path).unwrap();
+        assert_eq!(key, Key::read(&path).unwrap());
+        assert!(key.write(&path).is_err());
+        assert!(Key::read(&dir.path().join("other.key"))
+            .unwrap_err()
+            .to_string()
+            .contains("no key"));
+        assert!(Key::parse("abc").is_err());
+        assert!(Key::parse(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_path() {
+        assert_eq!(
+            Path::new("day-5/input.txt.enc"),
+            encrypted_path(Path::new("day-5/input.txt"))
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::{vault, Params};
+
+use crate::{
+    cli::{Format, RunArgs},
+    day::{Day, Year},
+    manifest::Manifest,
+    record::{self, Record, RecordStatus},
+    run::{self, Parsed, DEFAULT_TIMEOUT},
+    table::{Align, Table},
+};
+
+// one day on every input of a directory, with the parameters and timeout of the real input
+pub fn batch(year: &Year, day: u8, dir: &Path, args: &RunArgs) -> Result<()> {
+    let day = *year
+        .day(day)
+        .ok_or_else(|| anyhow!("day {day} of {} is not registered", year.year))?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let manifest = Manifest::load(&year.manifest_path())?;
+    let overrides = args.params.iter().cloned().collect::<Params>();
+    let params = run::params(&day, &manifest, &overrides)?;
+    let timeout = args
+        .timeout
+        .map(Duration::from_secs)
+        .or(manifest.timeout(day.day))
+        .unwrap_or(DEFAULT_TIMEOUT);
+
+    let paths = input_paths(dir)?;
+    if paths.is_empty() {
+        return Err(anyhow!("no .txt inputs in {}", dir.display()));
+    }
+
+    let mut records = vec![];
+    for path in &paths {
+        let input = vault::read(path)?
+            .map(Arc::<str>::from)
+            .ok_or_else(|| anyhow!("no input at {}", path.display()))?;
+        let parsed = Parsed::new(&day, &input, &params, timeout);
+        for &part in &parts {
+            let record = solve(year, &day, part, path, &parsed, &params, timeout);
+            if args.format == Format::Jsonl {
+                record::write_line(&mut io::stdout(), &record)?;
+            }
+            records.push(record);
+        }
+    }
+
+    match args.format {
+        Format::Text => println!("{}", report(year.year, day.day, &parts, &records)),
+        Format::Json => record::write_all(&mut io::stdout(), &records)?,
+        Format::Jsonl => {}
+    }
+
+    Ok(())
+}
+
+// the `.txt` files, or their encrypted copies, in the order of their names
+fn input_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("reading {}", dir.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let name = name.strip_suffix(".enc").unwrap_or(name);
+            name.ends_with(".txt").then(|| path.with_file_name(name))
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+
+    Ok(paths)
+}
+
+fn solve(
+    year: &Year,
+    day: &Day,
+    part: u8,
+    path: &Path,
+    parsed: &Parsed,
+    params: &Params,
+    timeout: Duration,
+) -> Record {
+    let (outcome, elapsed) = parsed.solve(day, part, params, timeout);
+
+    Record {
+        input: Some(file_name(path)),
+        ..Record::solved((year.year, day.day, part), &outcome, elapsed, timeout)
+            .with_parse(Some(parsed.elapsed))
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |name| name.to_string_lossy().into(),
+    )
+}
+
+// region:    --- Report
+
+// a row per input, with why the flagged parts failed below the table
+fn report(year: u16, day: u8, parts: &[u8], records: &[Record]) -> String {
+    let mut columns = vec![("input", Align::Left)];
+    let titles = parts
+        .iter()
+        .map(|part| format!("part {part}"))
+        .collect::<Vec<_>>();
+    for title in &titles {
+        columns.push((title.as_str(), Align::Left));
+        columns.push(("time", Align::Right));
+    }
+    let mut table = Table::new(&columns);
+
+    let mut flagged = vec![];
+    for row in records.chunks(parts.len()) {
+        let mut cells = vec![row[0].input.clone().unwrap_or_default()];
+        for record in row {
+            cells.push(match (&record.answer, record.status) {
+                (Some(answer), _) => answer.clone(),
+                (None, RecordStatus::NotImplemented) => "not implemented".to_string(),
+                (None, status) => {
+                    flagged.push(record);
+                    format!("{status:?}").to_lowercase()
+                }
+            });
+            cells.push(record.solve_seconds.map_or(String::new(), |seconds| {
+                format!("{:.2?}", Duration::from_secs_f64(seconds))
+            }));
+        }
+        table.push(cells);
+    }
+
+    let inputs = records.len() / parts.len();
+    let mut report = format!("{year} day {day}\n{}", table.terminal());
+    for record in &flagged {
+        writeln!(
+            report,
+            "{} part {}: {}",
+            record.input.as_deref().unwrap_or_default(),
+            record.part,
+            record.error.as_deref().unwrap_or_default()
+        )
+        .expect("writing to a string");
+    }
+    write!(report, "{inputs} inputs, {} flagged", flagged.len()).expect("writing to a string");
+
+    report
+}
+
+// endregion: --- Report
+
+#[cfg(test)]
+mod tests {
+    use crate::day::model;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_batch() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, input) in [("bob.txt", "12345"), ("alice.txt", "123"), ("notes.md", "")] {
+            fs::write(dir.path().join(name), input).unwrap();
+        }
+        let paths = input_paths(dir.path()).unwrap();
+        assert_eq!(
+            vec!["alice.txt", "bob.txt"],
+            paths.iter().map(|path| file_name(path)).collect::<Vec<_>>()
+        );
+
+        let year = Year {
+            year: 2023,
+            root: PathBuf::new(),
+            days: vec![],
+        };
+        // only the short inputs hold the assumption of part 2
+        let day = Day::parsed(
+            8,
+            |input, _| Ok(Arc::new(input.len())),
+            |len, _| Ok(model::<usize>(len)?.to_string()),
+            |len, _| {
+                assert!(*model::<usize>(len)? < 5, "too long");
+                Ok("short".to_string())
+            },
+        );
+        let timeout = Duration::from_secs(10);
+        let records = paths
+            .iter()
+            .flat_map(|path| {
+                let input = Arc::from(fs::read_to_string(path).unwrap());
+                let parsed = Parsed::new(&day, &input, &Params::new(), timeout);
+                [1, 2].map(|part| solve(&year, &day, part, path, &parsed, &Params::new(), timeout))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(Some("alice.txt"), records[0].input.as_deref());
+        assert_eq!(Some("5"), records[2].answer.as_deref());
+        assert_eq!(RecordStatus::Panic, records[3].status);
+
+        let report = report(2023, 8, &[1, 2], &records);
+        assert!(report.contains("\nalice.txt  3 "));
+        assert!(report.contains("\nbob.txt    5 "));
+        assert!(report.ends_with("bob.txt part 2: too long\n2 inputs, 1 flagged"));
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    fs,
+    io::{self, ErrorKind, Write},
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::Params;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::{BenchArgs, Format},
+    day::{select_years, Day, Year},
+    manifest::Manifest,
+    record::{self, Record, RecordStatus},
+    run::{self, Outcome, Parsed, DEFAULT_TIMEOUT},
+};
+
+// region:    --- History
+
+/// A saved result, a line of `benchmarks.jsonl`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Saved {
+    // seconds since the Unix epoch
+    pub at: u64,
+    #[serde(flatten)]
+    pub record: Record,
+}
+
+// the parts that did not answer have no time worth keeping
+pub fn save(path: &Path, at: u64, records: &[Record]) -> Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    for record in records
+        .iter()
+        .filter(|record| record.status == RecordStatus::Ok)
+    {
+        let saved = Saved {
+            at,
+            record: record.clone(),
+        };
+        writeln!(file, "{}", serde_json::to_string(&saved)?)?;
+    }
+
+    Ok(())
+}
+
+// nothing saved yet when there is no file
+pub fn history(path: &Path) -> Result<Vec<Saved>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("line {} of {}", idx + 1, path.display()))
+        })
+        .collect()
+}
+
+// endregion: --- History
+
+pub fn bench(years: &[Year], args: &BenchArgs) -> Result<()> {
+    let at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_secs());
+
+    let mut records = vec![];
+    for year in select_years(years, args.year)? {
+        let year_records = bench_year(year, args, &mut io::stdout())?;
+        if args.save {
+            save(&year.benchmarks_path(), at, &year_records)?;
+        }
+        records.extend(year_records);
+    }
+
+    if args.format == Format::Json {
+        record::write_all(&mut io::stdout(), &records)?;
+    }
+
+    Ok(())
+}
+
+fn bench_year(year: &Year, args: &BenchArgs, out: &mut impl Write) -> Result<Vec<Record>> {
+    let days = match args.day {
+        Some(day) => vec![*year
+            .day(day)
+            .ok_or_else(|| anyhow!("day {day} of {} is not registered", year.year))?],
+        None => year.days.clone(),
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let manifest = Manifest::load(&year.manifest_path())?;
+
+    let mut records = vec![];
+    for day in days {
+        let input = year.read_input(day.day)?.map(Arc::<str>::from);
+        let params = run::params(&day, &manifest, &Params::new())?;
+        let timeout = args
+            .timeout
+            .map(Duration::from_secs)
+            .or(manifest.timeout(day.day))
+            .unwrap_or(DEFAULT_TIMEOUT);
+
+        for &part in &parts {
+            let record = match &input {
+                Some(input) => bench_part(year, &day, part, input, &params, args.runs, timeout)
+                    .with_expected(manifest.answer(day.day, part)),
+                None => Record::new(year.year, day.day, part, RecordStatus::NoInput),
+            };
+
+            match args.format {
+                Format::Text => writeln!(out, "{}", text(&record))?,
+                Format::Jsonl => record::write_line(out, &record)?,
+                Format::Json => {}
+            }
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+// the medians of `runs` runs, parsing apart from solving, or how the first run that did not
+// answer went
+fn bench_part(
+    year: &Year,
+    day: &Day,
+    part: u8,
+    input: &Arc<str>,
+    params: &Params,
+    runs: usize,
+    timeout: Duration,
+) -> Record {
+    let mut parse_times = vec![];
+    let mut times = vec![];
+    let mut answer = String::new();
+    for _ in 0..runs {
+        let parsed = Parsed::new(day, input, params, timeout);
+        let (outcome, elapsed) = parsed.solve(day, part, params, timeout);
+
+        match outcome {
+            Outcome::Solved(solved) => answer = solved,
+            outcome => {
+                return Record::solved((year.year, day.day, part), &outcome, elapsed, timeout)
+                    .with_parse(Some(parsed.elapsed))
+            }
+        }
+        parse_times.push(parsed.elapsed);
+        times.push(elapsed);
+    }
+    parse_times.sort();
+    times.sort();
+
+    Record {
+        runs: Some(runs),
+        ..Record::solved(
+            (year.year, day.day, part),
+            &Outcome::Solved(answer),
+            times[times.len() / 2],
+            timeout,
+        )
+        .with_parse(parse_times.get(parse_times.len() / 2).copied())
+    }
+}
+
+fn text(record: &Record) -> String {
+    let prefix = format!("{} day {:>2} part {}", record.year, record.day, record.part);
+    match (record.status, record.solve_seconds, &record.error) {
+        (RecordStatus::Ok, Some(median), _) => {
+            let text = format!(
+                "{prefix}: {:>12.2?} median of {} runs",
+                Duration::from_secs_f64(median),
+                record.runs.unwrap_or(1)
+            );
+            match record.parse_seconds {
+                Some(parse) => format!("{text}, parsing {:.2?}", Duration::from_secs_f64(parse)),
+                None => text,
+            }
+        }
+        (RecordStatus::NoInput, ..) => format!("{prefix}: no input"),
+        (RecordStatus::NotImplemented, ..) => format!("{prefix}: not implemented"),
+        (_, _, Some(error)) => format!("{prefix}: {error}"),
+        (status, ..) => format!("{prefix}: {status:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_bench_part() {
+        let year = Year {
+            year: 2023,
+            root: PathBuf::new(),
+            days: vec![],
+        };
+        let day = Day::parsed(
+            1,
+            |input, _| Ok(Arc::new(input.len())),
+            |len, _| Ok(crate::day::model::<usize>(len)?.to_string()),
+            |_, _| Err(anyhow!("no path")),
+        );
+        let input = Arc::from("input");
+        let timeout = Duration::from_secs(10);
+
+        let record = bench_part(&year, &day, 1, &input, &Params::new(), 3, timeout);
+        assert_eq!(RecordStatus::Ok, record.status);
+        assert_eq!(Some("5"), record.answer.as_deref());
+        assert_eq!(Some(3), record.runs);
+        assert!(record.parse_seconds.is_some());
+        assert!(text(&record).contains("median of 3 runs, parsing "));
+
+        let record = bench_part(&year, &day, 2, &input, &Params::new(), 3, timeout);
+        assert_eq!(RecordStatus::Error, record.status);
+        assert_eq!(None, record.runs);
+        assert_eq!("2023 day  1 part 2: no path", text(&record));
+    }
+
+    #[test]
+    fn test_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("benchmarks.jsonl");
+        let solved = Record {
+            answer: Some("35".into()),
+            solve_seconds: Some(0.5),
+            runs: Some(10),
+            ..Record::new(2023, 5, 1, RecordStatus::Ok)
+        };
+        let failed = Record::new(2023, 5, 2, RecordStatus::Error);
+
+        assert!(history(&path).unwrap().is_empty());
+        save(&path, 100, &[solved.clone(), failed]).unwrap();
+        save(&path, 200, std::slice::from_ref(&solved)).unwrap();
+
+        let saved = history(&path).unwrap();
+        assert_eq!(
+            vec![100, 200],
+            saved.iter().map(|saved| saved.at).collect::<Vec<_>>()
+        );
+        assert_eq!(solved, saved[1].record);
+    }
+}
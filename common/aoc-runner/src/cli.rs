@@ -0,0 +1,442 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use aoc_core::{
+    log::{self, Filter, Level},
+    params::parse_assignment,
+};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+
+use crate::{
+    bench, day::Year, examples, fetch, fuzz, generate, inputs, leaderboard, repl, report, run,
+    scaffold, status, submit,
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code solutions")]
+struct Cli {
+    /// Show more solver output, -v for info, -vv for debug, -vvv for trace
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Show everything logged by a day or module, e.g. `--trace day-20`
+    #[arg(long, value_name = "TARGET", global = true)]
+    trace: Vec<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run solutions against the real inputs
+    Run(RunArgs),
+    /// Download a day's input into its directory, through a local cache
+    Fetch(FetchArgs),
+    /// Solve a part and send the answer, unless it is known to be wrong
+    Submit(SubmitArgs),
+    /// Save the examples of a puzzle page as `test-N.txt` and their answers in the manifest
+    Examples(ExamplesArgs),
+    /// Create a day from the template and register it
+    New(NewArgs),
+    /// Show which parts of each day are missing, unimplemented, failing or verified
+    Status(StatusArgs),
+    /// Time each part over several runs on the real input
+    Bench(BenchArgs),
+    /// Build a static HTML site of the stars, answers, benchmarks and images of each day
+    Report(ReportArgs),
+    /// Report on a private leaderboard from its exported JSON
+    Leaderboard(LeaderboardArgs),
+    /// Feed mutated examples to both parts until one panics or hangs
+    Fuzz(FuzzArgs),
+    /// Make up an input for a day with a generator, e.g. to share or to stress a solution
+    Generate(GenerateArgs),
+    /// Encrypt the puzzle inputs with a local key, or decrypt them again
+    Inputs(InputsArgs),
+    /// Parse a day's input once and ask its model questions, e.g. `map 79` on 2023 day 5
+    Repl(ReplArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Only run this year
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// Only run this day
+    #[arg(short, long)]
+    pub day: Option<u8>,
+
+    /// Only run this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Override a parameter of the day, on top of the manifest
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment, requires = "day")]
+    pub params: Vec<(String, String)>,
+
+    /// Give up on a part after this many seconds, instead of the manifest's or 60 seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Seed the days that use randomness with this, instead of the manifest's or their own
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Run the years concurrently, each year's results are still printed together
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Run the day on its `test-N.txt` instead, with the example's parameters and answers from
+    /// the manifest
+    #[arg(long, value_name = "N", requires = "day", conflicts_with = "inputs", value_parser = clap::value_parser!(u8).range(1..))]
+    pub example: Option<u8>,
+
+    /// Run the day on every `.txt` file of this directory instead, e.g. the inputs of a team
+    #[arg(
+        long,
+        value_name = "DIR",
+        requires = "day",
+        conflicts_with = "parallel"
+    )]
+    pub inputs: Option<PathBuf>,
+
+    /// Print the results as text, a JSON array, or JSON lines as each part finishes
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+#[derive(Debug, Args)]
+pub struct FetchArgs {
+    /// The year of the puzzle, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// The day of the puzzle
+    #[arg(short, long)]
+    pub day: u8,
+}
+
+#[derive(Debug, Args)]
+pub struct SubmitArgs {
+    /// The year of the puzzle, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// The day of the puzzle
+    #[arg(short, long)]
+    pub day: u8,
+
+    /// The part to submit
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: u8,
+
+    /// Send this answer instead of running the solution
+    #[arg(long)]
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ExamplesArgs {
+    /// The year of the puzzle, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// The day of the puzzle
+    #[arg(short, long)]
+    pub day: u8,
+
+    /// Read this saved page instead of the cached or downloaded one
+    #[arg(long, value_name = "FILE")]
+    pub page: Option<PathBuf>,
+
+    /// Download the page again, e.g. once part 2 is unlocked
+    #[arg(long, conflicts_with = "page")]
+    pub refresh: bool,
+
+    /// Replace existing files and manifest entries without asking
+    #[arg(long)]
+    pub yes: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct NewArgs {
+    /// The year of the day, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// The day to create
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+}
+
+#[derive(Debug, Args)]
+pub struct StatusArgs {
+    /// Only show this year
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// Give up on a part after this many seconds, instead of the manifest's or 60 seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Also write the tables to this Markdown file
+    #[arg(long, value_name = "FILE")]
+    pub markdown: Option<PathBuf>,
+
+    /// Print the results as text, a JSON array, or JSON lines as each part finishes
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Only this year
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// Only this day
+    #[arg(short, long)]
+    pub day: Option<u8>,
+
+    /// Only this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Also add the results to the year's `benchmarks.jsonl`, the history `aoc report` charts
+    #[arg(long)]
+    pub save: bool,
+
+    /// How many times each part runs, the median is reported
+    #[arg(long, default_value_t = 10, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub runs: usize,
+
+    /// Give up on a run after this many seconds, instead of the manifest's or 60 seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Print the results as text, a JSON array, or JSON lines as each part finishes
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Only this year
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// The directory to write the site to
+    #[arg(long, value_name = "DIR", default_value = "site")]
+    pub out: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct LeaderboardArgs {
+    /// The JSON export of the leaderboard
+    pub file: PathBuf,
+
+    /// Only this day in the per-day reports
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: Option<u8>,
+
+    /// The reports to show, all of them by default
+    #[arg(short, long, value_enum)]
+    pub report: Vec<Report>,
+
+    /// Print Markdown tables instead
+    #[arg(long)]
+    pub markdown: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Report {
+    /// Who finished each day first
+    Order,
+    /// Time from part 1 to part 2
+    Gaps,
+    /// The local score, recomputed from the stars
+    Scores,
+    /// Days finished on the day they opened, in a row
+    Streaks,
+}
+
+/// How `run`, `status` and `bench` print their results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+    Jsonl,
+}
+
+#[derive(Debug, Args)]
+pub struct FuzzArgs {
+    /// The year of the days, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// Only fuzz this day
+    #[arg(short, long)]
+    pub day: Option<u8>,
+
+    /// How many inputs to try per day
+    #[arg(long, default_value_t = 10_000)]
+    pub runs: usize,
+
+    /// Start from this seed to try the same inputs again
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Count a part running for this many seconds as hanging
+    #[arg(long, value_name = "SECONDS", default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: u64,
+
+    /// Check the inputs saved in the days' `fuzz` directories instead
+    #[arg(long, conflicts_with_all = ["day", "runs", "seed"])]
+    pub replay: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// The year of the day, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+    /// The day to make an input for
+    #[arg(short, long)]
+    pub day: u8,
+    /// Make the same input as an earlier run
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Write the input to this file instead of printing it
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReplArgs {
+    /// The year of the day, needed when more than one year is registered
+    #[arg(short, long)]
+    pub year: Option<u16>,
+
+    /// The day to load
+    #[arg(short, long)]
+    pub day: u8,
+
+    /// Load the day's `test-N.txt` instead, with the example's parameters
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u8).range(1..))]
+    pub example: Option<u8>,
+
+    /// Override a parameter of the day, on top of the manifest
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_assignment)]
+    pub params: Vec<(String, String)>,
+
+    /// Give up on a command after this many seconds, instead of the manifest's or 60 seconds
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+pub struct InputsArgs {
+    /// Only the inputs of this year
+    #[arg(short, long, global = true)]
+    pub year: Option<u16>,
+
+    #[command(subcommand)]
+    pub action: InputsAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum InputsAction {
+    /// Write `input.txt.enc` next to each `input.txt`, making a key first if there is none
+    Encrypt,
+    /// Write each `input.txt` back from its `input.txt.enc`
+    Decrypt,
+    /// Encrypt every input again under a new key, which replaces the old one
+    Rekey {
+        /// Take this key file instead of a new one, e.g. a key shared with the team
+        #[arg(long, value_name = "FILE")]
+        new_key: Option<PathBuf>,
+    },
+}
+
+pub fn main(years: Vec<Year>) -> ExitCode {
+    let cli = Cli::parse();
+
+    log::set_filter(cli.filter());
+
+    let result = match &cli.command {
+        Command::Run(args) => run::run(&years, args),
+        Command::Fetch(args) => fetch::fetch(&years, args),
+        Command::Submit(args) => submit::submit(&years, args),
+        Command::Examples(args) => examples::examples(&years, args),
+        Command::New(args) => scaffold::new(&years, args),
+        Command::Status(args) => status::status(&years, args),
+        Command::Bench(args) => bench::bench(&years, args),
+        Command::Report(args) => report::report(&years, args),
+        Command::Leaderboard(args) => leaderboard::leaderboard(args),
+        Command::Fuzz(args) => fuzz::fuzz(&years, args),
+        Command::Generate(args) => generate::generate(&years, args),
+        Command::Inputs(args) => inputs::inputs(&years, args),
+        Command::Repl(args) => repl::repl(&years, args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+impl Cli {
+    fn filter(&self) -> Filter {
+        self.trace.iter().fold(
+            Filter::new(Some(Level::from_verbosity(self.verbose))),
+            |filter, target| filter.target(target, Level::Trace),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_filter() {
+        let cli = Cli::try_parse_from(["aoc", "run", "-v", "--trace", "day-20"]).expect("parsed");
+        let filter = cli.filter();
+
+        assert_eq!(Some(Level::Info), filter.level_for("day_21"));
+        assert_eq!(Some(Level::Trace), filter.level_for("day_20::simulation"));
+    }
+
+    #[test]
+    fn test_params_need_a_day() {
+        assert!(Cli::try_parse_from(["aoc", "run", "--param", "scale=10"]).is_err());
+
+        let cli =
+            Cli::try_parse_from(["aoc", "run", "-d", "11", "--param", "scale=10"]).expect("parsed");
+        let Command::Run(args) = cli.command else {
+            panic!("expected the run command")
+        };
+        assert_eq!(vec![("scale".to_string(), "10".to_string())], args.params);
+    }
+
+    #[test]
+    fn test_year_and_parallel() {
+        let cli = Cli::try_parse_from(["aoc", "run", "-y", "2015", "--parallel"]).expect("parsed");
+        let Command::Run(args) = cli.command else {
+            panic!("expected the run command")
+        };
+
+        assert_eq!(Some(2015), args.year);
+        assert!(args.parallel);
+    }
+}
@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"));
+
+/// Talks to the Advent of Code site, or anything answering like it at `base_url`.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|err| request_error(err, &url, year, day))?;
+
+        Ok(response.into_string()?)
+    }
+
+    // the puzzle text, with part 2 once part 1 is solved by the session's user
+    pub fn puzzle(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}", self.base_url);
+
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()
+            .map_err(|err| request_error(err, &url, year, day))?;
+
+        Ok(response.into_string()?)
+    }
+
+    // the page answering the guess, which tells if it was right
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| request_error(err, &url, year, day))?;
+
+        Ok(response.into_string()?)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn request_error(err: ureq::Error, url: &str, year: u16, day: u8) -> anyhow::Error {
+    match err {
+        ureq::Error::Status(400, _) => anyhow!("session token was not accepted"),
+        ureq::Error::Status(404, _) => anyhow!("{year} day {day} is not unlocked yet"),
+        err => anyhow!("requesting {url}: {err}"),
+    }
+}
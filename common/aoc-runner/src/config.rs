@@ -0,0 +1,127 @@
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to the Advent of Code site, read from `~/.config/aoc/config.toml`
+/// (or `$AOC_CONFIG`), each of them can be overridden by an environment variable:
+///
+/// ```toml
+/// session = "53616c7465645f5f..." # AOC_SESSION, the `session` cookie of a logged in browser
+/// base_url = "http://localhost:8080" # AOC_BASE_URL
+/// cache_dir = "/tmp/aoc" # AOC_CACHE_DIR, defaults to ~/.cache/aoc
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    base_url: Option<String>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let file = match config_path() {
+            Some(path) => Self::read(&path)?,
+            None => Self::default(),
+        };
+
+        Ok(file.overridden_by(|name| env::var(name).ok()))
+    }
+
+    // a missing file is the same as an empty one
+    pub fn read(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).with_context(|| format!("in {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    // `var` looks up an environment variable
+    pub fn overridden_by(self, var: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            session: var("AOC_SESSION").or(self.session),
+            base_url: var("AOC_BASE_URL").or(self.base_url),
+            cache_dir: var("AOC_CACHE_DIR").map(PathBuf::from).or(self.cache_dir),
+        }
+    }
+
+    pub fn session(&self) -> Result<&str> {
+        self.session
+            .as_deref()
+            .map(str::trim)
+            .filter(|session| !session.is_empty())
+            .ok_or_else(|| anyhow!("no session token, set AOC_SESSION or `session` in the config"))
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+
+    pub fn cache_dir(&self) -> Result<PathBuf> {
+        self.cache_dir
+            .clone()
+            .or_else(|| home_dir("XDG_CACHE_HOME", ".cache").map(|dir| dir.join("aoc")))
+            .ok_or_else(|| anyhow!("no cache directory, set AOC_CACHE_DIR or `cache_dir`"))
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    env::var_os("AOC_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| home_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join("aoc/config.toml")))
+}
+
+// `$xdg_var`, or `fallback` under the home directory
+fn home_dir(xdg_var: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(xdg_var)
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(fallback)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_overridden_by() {
+        let config = Config::parse(
+            r#"
+            session = "from-file"
+            base_url = "http://localhost:8080/"
+            "#,
+        )
+        .expect("valid config");
+
+        let config =
+            config.overridden_by(|name| (name == "AOC_SESSION").then(|| "from-env".into()));
+        assert_eq!("from-env", config.session().unwrap());
+        assert_eq!("http://localhost:8080", config.base_url());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::default();
+
+        assert!(config.session().is_err());
+        assert_eq!(DEFAULT_BASE_URL, config.base_url());
+        assert!(Config::parse("token = \"abc\"").is_err());
+    }
+}
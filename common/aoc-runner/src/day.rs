@@ -0,0 +1,162 @@
+use std::{any::Any, path::PathBuf, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use aoc_core::{vault, Params};
+
+/// A day's input once parsed, the owned model both of its parts run on.
+pub type Model = Arc<dyn Any + Send + Sync>;
+
+/// Parses the puzzle input into the day's model.
+pub type Parser = fn(&str, &Params) -> Result<Model>;
+
+/// A solution for one part, taking the day's model and parameters and giving the answer to
+/// submit.
+pub type ModelSolver = fn(&Model, &Params) -> Result<String>;
+
+/// A random input for a day from a seed, the same for the same seed.
+pub type Generator = fn(u64) -> String;
+
+/// A command of `aoc repl`, answering the rest of its line from the day's model.
+pub type Command = fn(&Model, &str) -> Result<String>;
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    // the input is parsed once, both parts run on the model
+    pub parser: Parser,
+    pub part1: ModelSolver,
+    pub part2: ModelSolver,
+    // defaults for the real input and for the examples
+    pub params: fn() -> Params,
+    pub example_params: fn() -> Params,
+    // for the days that can make up inputs
+    pub generator: Option<Generator>,
+    // what `aoc repl` can ask the model, by their usage, e.g. `map SEED [LENGTH]`
+    pub commands: &'static [(&'static str, Command)],
+}
+
+impl Day {
+    pub fn parsed(day: u8, parser: Parser, part1: ModelSolver, part2: ModelSolver) -> Self {
+        Self {
+            day,
+            parser,
+            part1,
+            part2,
+            params: Params::new,
+            example_params: Params::new,
+            generator: None,
+            commands: &[],
+        }
+    }
+
+    pub fn with_params(self, params: fn() -> Params, example_params: fn() -> Params) -> Self {
+        Self {
+            params,
+            example_params,
+            ..self
+        }
+    }
+
+    pub fn with_generator(self, generator: Generator) -> Self {
+        Self {
+            generator: Some(generator),
+            ..self
+        }
+    }
+
+    pub fn with_commands(self, commands: &'static [(&'static str, Command)]) -> Self {
+        Self { commands, ..self }
+    }
+
+    // the command named by the first word of its usage
+    pub fn command(&self, name: &str) -> Option<Command> {
+        self.commands
+            .iter()
+            .find(|(usage, _)| usage.split_whitespace().next() == Some(name))
+            .map(|&(_, command)| command)
+    }
+
+    pub fn parse(&self, input: &str, params: &Params) -> Result<Model> {
+        (self.parser)(input, params)
+    }
+
+    pub fn solve(&self, part: u8, model: &Model, params: &Params) -> Result<String> {
+        match part {
+            1 => (self.part1)(model, params),
+            2 => (self.part2)(model, params),
+            _ => unreachable!("a day has two parts"),
+        }
+    }
+}
+
+/// The model a day's parser made, as the type its parts take.
+pub fn model<T: 'static>(model: &Model) -> Result<&T> {
+    model
+        .downcast_ref()
+        .ok_or_else(|| anyhow!("the model is not a {}", std::any::type_name::<T>()))
+}
+
+/// Every day solved in a year, and the directory holding the `day-N` crates.
+pub struct Year {
+    pub year: u16,
+    pub root: PathBuf,
+    pub days: Vec<Day>,
+}
+
+impl Year {
+    pub fn day(&self, day: u8) -> Option<&Day> {
+        self.days.iter().find(|registered| registered.day == day)
+    }
+
+    pub fn day_dir(&self, day: u8) -> PathBuf {
+        self.root.join(format!("day-{day}"))
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.day_dir(day).join("input.txt")
+    }
+
+    // the plain input, or its encrypted copy decrypted with the local key
+    pub fn read_input(&self, day: u8) -> Result<Option<String>> {
+        vault::read(&self.input_path(day))
+    }
+
+    // what `aoc bench --save` adds to, for the charts of `aoc report`
+    pub fn benchmarks_path(&self) -> PathBuf {
+        self.root.join("benchmarks.jsonl")
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.root.join("manifest.toml")
+    }
+
+    // where the `aoc` crate of the year lists its days
+    pub fn registry_path(&self) -> PathBuf {
+        self.root.join("aoc/src/registry.rs")
+    }
+
+    pub fn runner_manifest_path(&self) -> PathBuf {
+        self.root.join("aoc/Cargo.toml")
+    }
+}
+
+// the year picked with `--year`, or all of them
+pub fn select_years(years: &[Year], year: Option<u16>) -> Result<Vec<&Year>> {
+    match year {
+        Some(year) => Ok(vec![years
+            .iter()
+            .find(|registered| registered.year == year)
+            .ok_or_else(|| anyhow!("year {year} is not registered"))?]),
+        None => Ok(years.iter().collect()),
+    }
+}
+
+// the one year a command is about, which has to be named when there are several
+pub fn select_year(years: &[Year], year: Option<u16>) -> Result<&Year> {
+    match select_years(years, year)?[..] {
+        [year] => Ok(year),
+        _ => Err(anyhow!(
+            "more than one year is registered, pick one with --year"
+        )),
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{
+    cli::ExamplesArgs,
+    client::Client,
+    config::Config,
+    day::{select_year, Year},
+    fetch::{cached_page, Cache},
+    manifest::{self, Example, Manifest},
+};
+
+// region:    --- Extraction
+
+/// What a puzzle page shows of its examples.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    // every `<pre><code>` block, in page order
+    pub blocks: Vec<String>,
+    // for each part, the block its example answer is about and the answer
+    pub answers: [Option<(usize, String)>; 2],
+}
+
+impl Extracted {
+    // one example per block with an answer, the blocks are saved as `test-N.txt` from 1
+    pub fn examples(&self) -> Vec<Example> {
+        let mut examples: Vec<Example> = vec![];
+        for (part, answer) in self.answers.iter().enumerate() {
+            let Some((block, answer)) = answer else {
+                continue;
+            };
+            let input = format!("test-{}.txt", block + 1);
+
+            let example = match examples.iter_mut().find(|example| example.input == input) {
+                Some(example) => example,
+                None => {
+                    examples.push(Example {
+                        input,
+                        ..Example::default()
+                    });
+                    examples.last_mut().expect("example just pushed")
+                }
+            };
+            match part {
+                0 => example.part1 = Some(answer.clone()),
+                _ => example.part2 = Some(answer.clone()),
+            }
+        }
+
+        examples
+    }
+}
+
+// The page has an `<article>` per part. The answer to an example is the last emphasized
+// `<code>` of the article, and is about the last example block shown before it, or the one of
+// part 1 when part 2 reuses it.
+pub fn extract(page: &str) -> Extracted {
+    let mut extracted = Extracted::default();
+
+    for (part, (_, article)) in between(page, "<article", "</article>")
+        .into_iter()
+        .take(2)
+        .enumerate()
+    {
+        let first_block = extracted.blocks.len();
+        let blocks = between(article, "<pre><code>", "</code></pre>");
+        extracted
+            .blocks
+            .extend(blocks.iter().map(|(_, block)| text(block)));
+
+        let answer = between(article, "<code><em>", "</em></code>")
+            .into_iter()
+            .chain(between(article, "<em><code>", "</code></em>"))
+            .max_by_key(|(at, _)| *at);
+        let Some((answer_at, answer)) = answer else {
+            continue;
+        };
+
+        let block = match blocks.iter().rposition(|(at, _)| *at < answer_at) {
+            Some(idx) => Some(first_block + idx),
+            None if part > 0 => extracted.answers[0].as_ref().map(|(block, _)| *block),
+            None => None,
+        };
+        extracted.answers[part] = block.map(|block| (block, text(answer)));
+    }
+
+    extracted
+}
+
+// the text between each `open` and the following `close`, and where it starts
+fn between<'a>(html: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = vec![];
+    let mut offset = 0;
+    while let Some(start) = html[offset..].find(open) {
+        let start = offset + start + open.len();
+        let Some(len) = html[start..].find(close) else {
+            break;
+        };
+        found.push((start, &html[start..start + len]));
+        offset = start + len + close.len();
+    }
+
+    found
+}
+
+// without its tags and entities
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// endregion: --- Extraction
+
+pub fn examples(years: &[Year], args: &ExamplesArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+    if year.day(args.day).is_none() {
+        return Err(anyhow!(
+            "day {} of {} is not registered",
+            args.day,
+            year.year
+        ));
+    }
+
+    let page = match &args.page {
+        Some(path) => {
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?
+        }
+        None => {
+            let config = Config::load()?;
+            let cache = Cache::new(&config.cache_dir()?);
+            cached_page(&cache, year.year, args.day, args.refresh, || {
+                Ok(Client::new(config.base_url(), config.session()?))
+            })?
+        }
+    };
+
+    let extracted = extract(&page);
+    if extracted.blocks.is_empty() {
+        return Err(anyhow!("no example in the page"));
+    }
+
+    // review what would change, only replacing anything needs a confirmation
+    let mut replacing = false;
+    let mut files: Vec<(PathBuf, &str)> = vec![];
+    for (idx, block) in extracted.blocks.iter().enumerate() {
+        let path = year.day_dir(args.day).join(format!("test-{}.txt", idx + 1));
+        match fs::read_to_string(&path) {
+            Ok(current) if current == *block => {
+                println!("{}: unchanged", path.display());
+                continue;
+            }
+            Ok(current) => {
+                replacing = true;
+                println!("{}: replaces the current file", path.display());
+                print_changed_lines(&current, block);
+            }
+            Err(_) => println!("{}: new, {} lines", path.display(), block.lines().count()),
+        }
+        files.push((path, block));
+    }
+
+    let manifest_path = year.manifest_path();
+    let current = Manifest::load(&manifest_path)?.examples(args.day).to_vec();
+    let mut examples = extracted.examples();
+    // the page has no parameters, the ones written by hand stay with their file
+    for example in &mut examples {
+        if let Some(known) = current.iter().find(|known| known.input == example.input) {
+            example.params = known.params.clone();
+        }
+    }
+    for example in &examples {
+        println!(
+            "{}: part 1 = {}, part 2 = {}",
+            example.input,
+            example.part1.as_deref().unwrap_or("-"),
+            example.part2.as_deref().unwrap_or("-")
+        );
+    }
+    let update_manifest = !examples.is_empty() && examples != current;
+    if update_manifest && !current.is_empty() {
+        replacing = true;
+        println!(
+            "replaces the {} examples of day-{} in {}",
+            current.len(),
+            args.day,
+            manifest_path.display()
+        );
+    }
+
+    if replacing && !args.yes && !confirm("write these changes?")? {
+        println!("nothing written");
+        return Ok(());
+    }
+
+    for (path, block) in files {
+        fs::write(&path, block).with_context(|| format!("writing {}", path.display()))?;
+    }
+    if update_manifest {
+        manifest::write_examples(&manifest_path, args.day, &examples)?;
+    }
+
+    Ok(())
+}
+
+fn print_changed_lines(current: &str, new: &str) {
+    let current = current.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    for line in 0..current.len().max(new.len()) {
+        if current.get(line) == new.get(line) {
+            continue;
+        }
+        if let Some(text) = current.get(line) {
+            println!("  {:>3} - {text}", line + 1);
+        }
+        if let Some(text) = new.get(line) {
+            println!("  {:>3} + {text}", line + 1);
+        }
+    }
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    print!("{question} [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<p>In this example, the calibration values are <code>12</code> and <code>38</code>.
+Adding these together produces <code><em>50</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54331</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Some digits are spelled out, <code>&lt;one&gt;</code> counts:</p>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<p>Adding these together produces <em><code>112</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        let extracted = extract(PAGE);
+
+        assert_eq!(
+            vec!["1abc2\npqr3stu8vwx\n", "two1nine\neightwothree\n"],
+            extracted.blocks
+        );
+        assert_eq!(
+            [Some((0, "50".to_string())), Some((1, "112".to_string()))],
+            extracted.answers
+        );
+        assert_eq!(
+            vec![
+                Example {
+                    input: "test-1.txt".into(),
+                    part1: Some("50".into()),
+                    ..Example::default()
+                },
+                Example {
+                    input: "test-2.txt".into(),
+                    part2: Some("112".into()),
+                    ..Example::default()
+                },
+            ],
+            extracted.examples()
+        );
+    }
+
+    #[test]
+    fn test_extract_shared_example() {
+        // part 2 without an example of its own is about the one of part 1
+        let page = PAGE.replace(
+            "<pre><code>two1nine\n<em>eight</em>wothree\n</code></pre>",
+            "",
+        );
+        let extracted = extract(&page);
+
+        assert_eq!(1, extracted.blocks.len());
+        assert_eq!(
+            vec![Example {
+                input: "test-1.txt".into(),
+                part1: Some("50".into()),
+                part2: Some("112".into()),
+                ..Example::default()
+            }],
+            extracted.examples()
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            "a <b> & 'c'",
+            text("a &lt;b&gt; &amp; <em>&#39;c&#39;</em>")
+        );
+    }
+}
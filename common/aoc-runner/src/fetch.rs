@@ -0,0 +1,261 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::info;
+
+use crate::{
+    cli::FetchArgs,
+    client::Client,
+    config::Config,
+    day::{select_year, Year},
+};
+
+// region:    --- Cache
+
+/// Downloaded inputs, one file per puzzle at `<dir>/<year>/day-<day>.txt`, next to the
+/// puzzle pages and the answers submitted for them.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day-{day}.txt"))
+    }
+
+    pub fn page_path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day-{day}.html"))
+    }
+
+    // what was submitted for the day, kept next to its input
+    pub fn guesses_path(&self, year: u16, day: u8) -> PathBuf {
+        self.dir
+            .join(year.to_string())
+            .join(format!("day-{day}-guesses.toml"))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Result<Option<String>> {
+        read_cached(&self.path(year, day))
+    }
+
+    pub fn put(&self, year: u16, day: u8, input: &str) -> Result<()> {
+        write_cached(&self.path(year, day), input)
+    }
+
+    pub fn get_page(&self, year: u16, day: u8) -> Result<Option<String>> {
+        read_cached(&self.page_path(year, day))
+    }
+
+    pub fn put_page(&self, year: u16, day: u8, page: &str) -> Result<()> {
+        write_cached(&self.page_path(year, day), page)
+    }
+}
+
+fn read_cached(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+    }
+}
+
+fn write_cached(path: &Path, content: &str) -> Result<()> {
+    fs::create_dir_all(path.parent().expect("cache path has a parent"))?;
+    fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+}
+
+// endregion: --- Cache
+
+pub fn fetch(years: &[Year], args: &FetchArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+    if year.day(args.day).is_none() {
+        return Err(anyhow!(
+            "day {} of {} is not registered",
+            args.day,
+            year.year
+        ));
+    }
+
+    let config = Config::load()?;
+    let cache = Cache::new(&config.cache_dir()?);
+    let input = cached_input(&cache, year.year, args.day, || {
+        Ok(Client::new(config.base_url(), config.session()?))
+    })?;
+
+    // the runner reads the day's own copy, which is never replaced unless it is the empty
+    // placeholder of a new day
+    let path = year.input_path(args.day);
+    if fs::read_to_string(&path).is_ok_and(|current| !current.is_empty()) {
+        println!("{} already exists, kept as it is", path.display());
+    } else {
+        fs::write(&path, input).with_context(|| format!("writing {}", path.display()))?;
+        println!(
+            "{} day {:>2}: input saved to {}",
+            year.year,
+            args.day,
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+// the client is only made, and the session only needed, when the input is not cached yet
+fn cached_input(
+    cache: &Cache,
+    year: u16,
+    day: u8,
+    client: impl FnOnce() -> Result<Client>,
+) -> Result<String> {
+    if let Some(input) = cache.get(year, day)? {
+        info!(
+            "{year} day {day}: using {}",
+            cache.path(year, day).display()
+        );
+        return Ok(normalize(&input));
+    }
+
+    info!("{year} day {day}: downloading input");
+    let input = normalize(&client()?.input(year, day)?);
+    cache.put(year, day, &input)?;
+
+    Ok(input)
+}
+
+// the site ends every input with a newline, which the inputs of the days leave out and their
+// parsers do not expect
+fn normalize(input: &str) -> String {
+    input.trim_end_matches(['\r', '\n']).to_string()
+}
+
+// like the input, but the page grows a part 2 once part 1 is solved, so it can be refreshed
+pub fn cached_page(
+    cache: &Cache,
+    year: u16,
+    day: u8,
+    refresh: bool,
+    client: impl FnOnce() -> Result<Client>,
+) -> Result<String> {
+    if !refresh {
+        if let Some(page) = cache.get_page(year, day)? {
+            info!(
+                "{year} day {day}: using {}",
+                cache.page_path(year, day).display()
+            );
+            return Ok(page);
+        }
+    }
+
+    info!("{year} day {day}: downloading puzzle page");
+    let page = client()?.puzzle(year, day)?;
+    cache.put_page(year, day, &page)?;
+
+    Ok(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use aoc_core::Params;
+
+    use crate::{
+        day::{model, Day},
+        mock::{self, Seen},
+    };
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    fn respond(request: &Seen) -> (u16, String) {
+        match request.url.as_str() {
+            "/2023/day/8/input" if request.cookie == "session=secret" => (200, "LR\n".into()),
+            "/2023/day/6/input" => (200, "7 15 30\n9 40 200\n".into()),
+            "/2023/day/8/input" => (400, "bad session".into()),
+            _ => (404, "not found".into()),
+        }
+    }
+
+    #[test]
+    fn test_cached_input() {
+        let (base_url, server) = mock::serve(1, respond);
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        let client = || Ok(Client::new(&base_url, "secret"));
+
+        assert_eq!("LR", cached_input(&cache, 2023, 8, client).unwrap());
+        assert_eq!(
+            "LR",
+            fs::read_to_string(dir.path().join("2023/day-8.txt")).unwrap()
+        );
+
+        // the second time is read from the cache, the server only ever sees one request
+        assert_eq!("LR", cached_input(&cache, 2023, 8, client).unwrap());
+        let seen = server.join().unwrap();
+        assert_eq!(1, seen.len());
+        assert_eq!("/2023/day/8/input", seen[0].url);
+        assert_eq!("session=secret", seen[0].cookie);
+    }
+
+    #[test]
+    fn test_fetched_input_parses() {
+        let (base_url, server) = mock::serve(1, respond);
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+        // split like the parsers of the days, a trailing newline is an empty last line
+        let day = Day::parsed(
+            6,
+            |input, _| {
+                let rows = input
+                    .split('\n')
+                    .map(|line| line.split(' ').map(str::parse).collect())
+                    .collect::<Result<Vec<Vec<u64>>, _>>()?;
+                Ok(Arc::new(rows))
+            },
+            |rows, _| Ok(model::<Vec<Vec<u64>>>(rows)?.len().to_string()),
+            |_, _| Err(anyhow!("Not Implemented.")),
+        );
+        let params = Params::new();
+        assert!(day.parse("7 15 30\n9 40 200\n", &params).is_err());
+
+        let input = cached_input(&cache, 2023, 6, || Ok(Client::new(&base_url, "secret"))).unwrap();
+        let rows = day.parse(&input, &params).unwrap();
+        assert_eq!("2", day.solve(1, &rows, &params).unwrap());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let (base_url, server) = mock::serve(2, respond);
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Cache::new(dir.path());
+
+        let err =
+            cached_input(&cache, 2023, 8, || Ok(Client::new(&base_url, "wrong"))).unwrap_err();
+        assert!(err.to_string().contains("session"));
+
+        let err =
+            cached_input(&cache, 2023, 9, || Ok(Client::new(&base_url, "secret"))).unwrap_err();
+        assert!(err.to_string().contains("not unlocked"));
+
+        // nothing is cached for a failed download
+        assert_eq!(None, cache.get(2023, 8).unwrap());
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,428 @@
+use std::{fmt, fs, panic, path::PathBuf, sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::Params;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    cli::FuzzArgs,
+    day::{select_year, Day, Year},
+    generate::clock_seed,
+    run::{self, Outcome, Parsed},
+};
+
+// how many inputs a crash is shrunk with at most, each one runs both parts
+const SHRINK_BUDGET: usize = 2_000;
+
+// region:    --- Crash
+
+/// How an input broke a part, where returning an `Err` would have been fine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Crash {
+    Panicked { part: u8, message: String },
+    TimedOut { part: u8 },
+}
+
+impl fmt::Display for Crash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crash::Panicked { part, message } => write!(f, "part {part} panicked: {message}"),
+            Crash::TimedOut { part } => write!(f, "part {part} timed out"),
+        }
+    }
+}
+
+impl Crash {
+    // a shrunk input has to break the same part the same way, the message may change
+    fn same_as(&self, other: &Crash) -> bool {
+        match (self, other) {
+            (Crash::Panicked { part, .. }, Crash::Panicked { part: other, .. }) => part == other,
+            (Crash::TimedOut { part }, Crash::TimedOut { part: other }) => part == other,
+            _ => false,
+        }
+    }
+}
+
+// the puzzle inputs are text with `\n` line ends, anything else is not worth solving
+pub fn normalize(data: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(data).ok()?;
+    Some(text.replace("\r\n", "\n"))
+}
+
+// both parts, with the parameters of the examples
+pub fn check(day: &Day, input: &str, params: &Params, timeout: Duration) -> Result<(), Crash> {
+    let parsed = Parsed::new(day, &Arc::from(input), params, timeout);
+
+    // a crash while parsing shows in part 1, which is what a shrunk input has to break again
+    for part in [1, 2] {
+        match parsed.solve(day, part, params, timeout).0 {
+            Outcome::Solved(_) | Outcome::Failed(_) => {}
+            Outcome::Panicked(message) => return Err(Crash::Panicked { part, message }),
+            Outcome::TimedOut => return Err(Crash::TimedOut { part }),
+        }
+    }
+
+    Ok(())
+}
+
+// endregion: --- Crash
+
+// region:    --- Mutation
+
+// one to four edits of the kind that trip parsers: cut lines, stray characters, odd numbers
+fn mutate(rng: &mut StdRng, corpus: &[Vec<char>]) -> Vec<char> {
+    let mut input = corpus.choose(rng).cloned().unwrap_or_default();
+    // the characters of the examples are the likeliest to get further into a parser
+    let alphabet = corpus.iter().flatten().copied().collect::<Vec<_>>();
+
+    for _ in 0..rng.gen_range(1..=4) {
+        let at = rng.gen_range(0..=input.len());
+        match rng.gen_range(0..7) {
+            0 => input.truncate(at),
+            1 if at < input.len() => {
+                let end = rng.gen_range(at..=input.len().min(at + 8));
+                input.drain(at..end);
+            }
+            2 => {
+                let c = match alphabet.choose(rng) {
+                    Some(&c) if rng.gen_bool(0.8) => c,
+                    _ => char::from(rng.gen_range(b' '..=b'~')),
+                };
+                input.insert(at, c);
+            }
+            3 => input.insert(at, '\n'),
+            4 => {
+                let number = ["0", "1", "-1", "999999999999", "18446744073709551616"]
+                    .choose(rng)
+                    .expect("numbers to pick from");
+                input.splice(at..at, number.chars());
+            }
+            5 => {
+                // a line of another input, repeated or from the other examples
+                let lines = corpus.choose(rng).map_or(vec![], |other| {
+                    other
+                        .split(|&c| c == '\n')
+                        .map(<[char]>::to_vec)
+                        .collect::<Vec<_>>()
+                });
+                if let Some(line) = lines.choose(rng) {
+                    input.splice(at..at, line.iter().copied().chain(['\n']));
+                }
+            }
+            _ if at < input.len() => {
+                // a digit changed keeps the shape of a number
+                if let Some(c) = input[at].to_digit(10) {
+                    input[at] =
+                        char::from_digit((c + rng.gen_range(1..10)) % 10, 10).expect("a digit");
+                } else {
+                    input.remove(at);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    input
+}
+
+// fewer lines, then fewer characters, as long as it breaks the same way
+fn shrink(day: &Day, input: &str, params: &Params, crash: &Crash, timeout: Duration) -> String {
+    let mut input = input.to_string();
+    let mut budget = SHRINK_BUDGET;
+    let still_crashes = |candidate: &str, budget: &mut usize| {
+        *budget = budget.saturating_sub(1);
+        check(day, candidate, params, timeout).is_err_and(|other| other.same_as(crash))
+    };
+
+    let mut line = input.lines().count();
+    while line > 0 && budget > 0 {
+        line -= 1;
+        let candidate = input
+            .lines()
+            .enumerate()
+            .filter(|&(idx, _)| idx != line)
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        if still_crashes(&candidate, &mut budget) {
+            input = candidate;
+        }
+    }
+
+    let mut at = input.chars().count();
+    while at > 0 && budget > 0 {
+        at -= 1;
+        let candidate = input
+            .chars()
+            .enumerate()
+            .filter(|&(idx, _)| idx != at)
+            .map(|(_, c)| c)
+            .collect::<String>();
+        if still_crashes(&candidate, &mut budget) {
+            input = candidate;
+        }
+    }
+
+    input
+}
+
+// endregion: --- Mutation
+
+// region:    --- Regressions
+
+/// Inputs that once broke a day, kept in its `fuzz` directory so they are checked for good.
+pub fn regressions(year: &Year, day: u8) -> Result<Vec<PathBuf>> {
+    let dir = year.day_dir(day).join("fuzz");
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(vec![]);
+    };
+
+    let mut paths = vec![];
+    for entry in entries {
+        let path = entry
+            .with_context(|| format!("listing {}", dir.display()))?
+            .path();
+        if path.extension().is_some_and(|extension| extension == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+// every saved crash of the year, and how it still breaks its day
+pub fn replay(year: &Year, timeout: Duration) -> Result<Vec<(PathBuf, Crash)>> {
+    let mut broken = vec![];
+    for day in &year.days {
+        for path in regressions(year, day.day)? {
+            let data = fs::read(&path).with_context(|| format!("reading {}", path.display()))?;
+            let Some(input) = normalize(&data) else {
+                continue;
+            };
+            if let Err(crash) = check(day, &input, &(day.example_params)(), timeout) {
+                broken.push((path, crash));
+            }
+        }
+    }
+
+    Ok(broken)
+}
+
+// endregion: --- Regressions
+
+pub fn fuzz(years: &[Year], args: &FuzzArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+    let timeout = Duration::from_secs(args.timeout);
+
+    // the crashes are reported once each, not by the panic hook on every run
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = if args.replay {
+        replay_year(year, timeout)
+    } else {
+        fuzz_year(year, args, timeout)
+    };
+    panic::set_hook(hook);
+
+    result
+}
+
+fn replay_year(year: &Year, timeout: Duration) -> Result<()> {
+    let broken = replay(year, timeout)?;
+    for (path, crash) in &broken {
+        println!("{}: {crash}", path.display());
+    }
+
+    match broken.len() {
+        0 => Ok(()),
+        count => Err(anyhow!("{count} saved inputs still break their day")),
+    }
+}
+
+fn fuzz_year(year: &Year, args: &FuzzArgs, timeout: Duration) -> Result<()> {
+    let days = match args.day {
+        Some(day) => vec![*year
+            .day(day)
+            .ok_or_else(|| anyhow!("day {day} of {} is not registered", year.year))?],
+        None => year.days.clone(),
+    };
+    let seed = args.seed.unwrap_or_else(clock_seed);
+    println!("seed {seed}, pass `--seed {seed}` to run the same inputs again");
+
+    for day in days {
+        // each day has its own sequence, so one day gives the same inputs alone or with others
+        let mut rng = StdRng::seed_from_u64(seed ^ u64::from(day.day));
+        let corpus = corpus(year, day.day)?;
+        // the days that use randomness draw from the same seed
+        let params = run::with_seed((day.example_params)(), Some(seed));
+
+        let crashed = (0..args.runs).find_map(|_| {
+            let input = mutate(&mut rng, &corpus).into_iter().collect::<String>();
+            check(&day, &input, &params, timeout)
+                .err()
+                .map(|crash| (input, crash))
+        });
+
+        let Some((input, crash)) = crashed else {
+            println!(
+                "{} day {:>2}: {} runs, no crash",
+                year.year, day.day, args.runs
+            );
+            continue;
+        };
+        // shrinking a timeout would wait for every attempt
+        let input = match crash {
+            Crash::Panicked { .. } => shrink(&day, &input, &params, &crash, timeout),
+            Crash::TimedOut { .. } => input,
+        };
+        let path = save(year, day.day, &input)?;
+        println!(
+            "{} day {:>2}: {crash}, saved to {}",
+            year.year,
+            day.day,
+            path.display()
+        );
+    }
+
+    Ok(())
+}
+
+// the examples of the day, or an empty input for a day without any
+fn corpus(year: &Year, day: u8) -> Result<Vec<Vec<char>>> {
+    let dir = year.day_dir(day);
+    let mut paths = fs::read_dir(&dir)
+        .with_context(|| format!("listing {}", dir.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<Result<Vec<_>>>()?;
+    // in name order, the order of the directory differs between platforms and a seed has to
+    // mutate the same corpus everywhere
+    paths.sort();
+
+    let mut corpus = vec![];
+    for path in paths {
+        let is_example = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("test-") && name.ends_with(".txt"));
+        if is_example {
+            if let Some(input) = normalize(&fs::read(&path)?) {
+                corpus.push(input.chars().collect());
+            }
+        }
+    }
+    if corpus.is_empty() {
+        corpus.push(vec![]);
+    }
+
+    Ok(corpus)
+}
+
+// named after the content, so the same crash found twice is saved once
+fn save(year: &Year, day: u8, input: &str) -> Result<PathBuf> {
+    let dir = year.day_dir(day).join("fuzz");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("crash-{:016x}.txt", fnv1a(input.as_bytes())));
+    fs::write(&path, input).with_context(|| format!("writing {}", path.display()))?;
+
+    Ok(path)
+}
+
+// FNV-1a, the same on every Rust release unlike `DefaultHasher`, or the names would change
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Params;
+
+    use crate::day::{model, Model};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    // panics on any line without a number
+    fn numbers(input: &str, _: &Params) -> Result<Model> {
+        let numbers = input
+            .lines()
+            .map(|line| line.parse::<u64>().expect("a number"))
+            .collect::<Vec<_>>();
+        Ok(Arc::new(numbers))
+    }
+
+    fn sum(numbers: &Model, _: &Params) -> Result<String> {
+        Ok(model::<Vec<u64>>(numbers)?.iter().sum::<u64>().to_string())
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(Some("1\n2\n".to_string()), normalize(b"1\r\n2\n"));
+        assert_eq!(None, normalize(&[0xff, b'1']));
+    }
+
+    #[test]
+    fn test_check_and_shrink() {
+        let day = Day::parsed(1, numbers, sum, sum);
+        let timeout = Duration::from_secs(10);
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+
+        assert_eq!(Ok(()), check(&day, "1\n2", &Params::new(), timeout));
+        let crash = check(&day, "1\n2\nx3\n4", &Params::new(), timeout).unwrap_err();
+        assert_eq!(
+            Crash::Panicked {
+                part: 1,
+                message: "a number: ParseIntError { kind: InvalidDigit }".into()
+            },
+            crash
+        );
+        assert_eq!(
+            "x",
+            shrink(&day, "1\n2\nx3\n4", &Params::new(), &crash, timeout)
+        );
+
+        panic::set_hook(hook);
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fnv1a(b""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fnv1a(b"a"));
+    }
+
+    #[test]
+    fn test_corpus() {
+        let dir = tempfile::tempdir().unwrap();
+        let year = Year {
+            year: 2023,
+            root: dir.path().to_path_buf(),
+            days: vec![],
+        };
+        fs::create_dir_all(year.day_dir(3)).unwrap();
+        for (name, input) in [("test-2.txt", "2"), ("input.txt", "0"), ("test-1.txt", "1")] {
+            fs::write(year.day_dir(3).join(name), input).unwrap();
+        }
+
+        assert_eq!(vec![vec!['1'], vec!['2']], corpus(&year, 3).unwrap());
+    }
+
+    #[test]
+    fn test_mutate() {
+        let corpus = vec!["#.#\n1 2\n".chars().collect::<Vec<_>>()];
+        let mutated = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..10)
+                .map(|_| mutate(&mut rng, &corpus))
+                .collect::<Vec<_>>()
+        };
+
+        // the same seed gives the same inputs
+        assert_eq!(mutated(7), mutated(7));
+        assert!(mutated(7).iter().any(|input| *input != corpus[0]));
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    fs,
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{
+    cli::GenerateArgs,
+    day::{select_year, Year},
+};
+
+// a different seed on every run, printed so the run can be repeated
+pub(crate) fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |now| now.as_nanos() as u64)
+}
+
+pub fn generate(years: &[Year], args: &GenerateArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+    let seed = args.seed.unwrap_or_else(clock_seed);
+
+    let input = generated_input(year, args.day, seed)?;
+
+    // the input alone goes to stdout, as it is without a line end, so it can be piped
+    match &args.output {
+        Some(path) => {
+            fs::write(path, input).with_context(|| format!("writing {}", path.display()))?;
+            println!(
+                "{} day {:>2}: seed {seed} written to {}",
+                year.year,
+                args.day,
+                path.display()
+            );
+        }
+        None => {
+            eprintln!("{} day {:>2}: seed {seed}", year.year, args.day);
+            io::stdout().write_all(input.as_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+fn generated_input(year: &Year, day: u8, seed: u64) -> Result<String> {
+    let day = year
+        .day(day)
+        .ok_or_else(|| anyhow!("day {day} of {} is not registered", year.year))?;
+    let generator = day
+        .generator
+        .ok_or_else(|| anyhow!("day {} of {} has no input generator", day.day, year.year))?;
+
+    Ok(generator(seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, sync::Arc};
+
+    use crate::day::{Day, Model};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_generated_input() {
+        let unimplemented = |_: &Model, _: &_| Err(anyhow!("Not Implemented."));
+        let year = Year {
+            year: 2023,
+            root: PathBuf::new(),
+            days: vec![
+                Day::parsed(1, |_, _| Ok(Arc::new(())), unimplemented, unimplemented),
+                Day::parsed(2, |_, _| Ok(Arc::new(())), unimplemented, unimplemented)
+                    .with_generator(|seed| (seed % 7).to_string()),
+            ],
+        };
+
+        assert_eq!("3", generated_input(&year, 2, 10).unwrap());
+        assert!(generated_input(&year, 1, 10).is_err());
+        assert!(generated_input(&year, 3, 10).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::vault::{self, Key, KEY_VAR};
+
+use crate::{
+    cli::{InputsAction, InputsArgs},
+    day::{select_years, Year},
+};
+
+pub fn inputs(years: &[Year], args: &InputsArgs) -> Result<()> {
+    let years = select_years(years, args.year)?;
+    let key_path = Key::path().ok_or_else(|| anyhow!("no key file, set {KEY_VAR}"))?;
+
+    match &args.action {
+        InputsAction::Encrypt => encrypt(&years, &key_path),
+        InputsAction::Decrypt => decrypt(&years, &Key::read(&key_path)?),
+        InputsAction::Rekey { new_key } => rekey(&years, &key_path, new_key.as_deref()),
+    }
+}
+
+// every day with an input, plain or encrypted, whether it is registered or not
+fn input_paths(years: &[&Year]) -> Vec<(u16, u8, PathBuf)> {
+    years
+        .iter()
+        .flat_map(|year| (1..=25).map(move |day| (year.year, day, year.input_path(day))))
+        .filter(|(_, _, path)| path.exists() || vault::encrypted_path(path).exists())
+        .collect()
+}
+
+// makes a key first if there is none, and leaves the copies that are up to date alone
+fn encrypt(years: &[&Year], key_path: &Path) -> Result<()> {
+    let key = if key_path.exists() {
+        Key::read(key_path)?
+    } else {
+        let key = Key::generate();
+        key.write(key_path)?;
+        println!(
+            "new key written to {}, keep a copy of it somewhere safe",
+            key_path.display()
+        );
+        key
+    };
+
+    for (year, day, path) in input_paths(years) {
+        // the empty placeholder of a new day has nothing to hide
+        let Ok(input) = fs::read(&path) else {
+            continue;
+        };
+        if input.is_empty() {
+            continue;
+        }
+
+        let encrypted = vault::encrypted_path(&path);
+        // encrypting again would change the file, as every copy has its own nonce
+        let current = fs::read(&encrypted).ok();
+        if current.is_some_and(|sealed| key.decrypt(&sealed).is_ok_and(|plain| plain == input)) {
+            continue;
+        }
+
+        write(&encrypted, &key.encrypt(&input))?;
+        println!("{year} day {day:>2}: encrypted to {}", encrypted.display());
+    }
+
+    Ok(())
+}
+
+// a plain input that differs is newer, e.g. fetched again, so it is kept
+fn decrypt(years: &[&Year], key: &Key) -> Result<()> {
+    for (year, day, path) in input_paths(years) {
+        let encrypted = vault::encrypted_path(&path);
+        let Ok(sealed) = fs::read(&encrypted) else {
+            continue;
+        };
+        let input = key
+            .decrypt(&sealed)
+            .with_context(|| format!("decrypting {}", encrypted.display()))?;
+
+        match fs::read(&path) {
+            Ok(current) if current == input => {}
+            Ok(current) if !current.is_empty() => println!(
+                "{year} day {day:>2}: {} differs from its encrypted copy, kept as it is",
+                path.display()
+            ),
+            _ => {
+                write(&path, &input)?;
+                println!("{year} day {day:>2}: decrypted to {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// every input is decrypted before anything is written, so a wrong key changes nothing
+fn rekey(years: &[&Year], key_path: &Path, new_key: Option<&Path>) -> Result<()> {
+    let key = Key::read(key_path)?;
+    let new_key = match new_key {
+        Some(path) => Key::read(path)?,
+        None => Key::generate(),
+    };
+
+    let inputs = input_paths(years)
+        .into_iter()
+        .map(|(_, _, path)| vault::encrypted_path(&path))
+        .filter(|encrypted| encrypted.exists())
+        .map(|encrypted| {
+            let sealed =
+                fs::read(&encrypted).with_context(|| format!("reading {}", encrypted.display()))?;
+            let input = key
+                .decrypt(&sealed)
+                .with_context(|| format!("decrypting {}", encrypted.display()))?;
+            Ok((encrypted, input))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for (encrypted, input) in &inputs {
+        write(encrypted, &new_key.encrypt(input))?;
+    }
+
+    // the old key is kept for the copies of the inputs in the history
+    let old_key_path = PathBuf::from(format!("{}.old", key_path.display()));
+    fs::rename(key_path, &old_key_path)
+        .with_context(|| format!("moving {} aside", key_path.display()))?;
+    new_key.write(key_path)?;
+    println!(
+        "{} inputs encrypted again, the new key is in {} and the old one in {}",
+        inputs.len(),
+        key_path.display(),
+        old_key_path.display()
+    );
+
+    Ok(())
+}
+
+fn write(path: &Path, content: &[u8]) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_encrypt_decrypt_rekey() {
+        let dir = tempfile::tempdir().unwrap();
+        let year = Year {
+            year: 2023,
+            root: dir.path().join("year"),
+            days: vec![],
+        };
+        let key_path = dir.path().join("input.key");
+        for (day, input) in [(3, "467..114.."), (5, "seeds: 79 14 55 13"), (7, "")] {
+            fs::create_dir_all(year.day_dir(day)).unwrap();
+            fs::write(year.input_path(day), input).unwrap();
+        }
+
+        encrypt(&[&year], &key_path).unwrap();
+        let sealed = fs::read(vault::encrypted_path(&year.input_path(5))).unwrap();
+        assert!(!vault::encrypted_path(&year.input_path(7)).exists());
+        // nothing changed, nothing written again
+        encrypt(&[&year], &key_path).unwrap();
+        assert_eq!(
+            sealed,
+            fs::read(vault::encrypted_path(&year.input_path(5))).unwrap()
+        );
+
+        fs::remove_file(year.input_path(5)).unwrap();
+        fs::write(year.input_path(3), "changed").unwrap();
+        decrypt(&[&year], &Key::read(&key_path).unwrap()).unwrap();
+        assert_eq!(
+            "seeds: 79 14 55 13",
+            fs::read_to_string(year.input_path(5)).unwrap()
+        );
+        assert_eq!("changed", fs::read_to_string(year.input_path(3)).unwrap());
+
+        let old_key = Key::read(&key_path).unwrap();
+        rekey(&[&year], &key_path, None).unwrap();
+        let new_key = Key::read(&key_path).unwrap();
+        let sealed = fs::read(vault::encrypted_path(&year.input_path(5))).unwrap();
+        assert_ne!(old_key, new_key);
+        assert!(old_key.decrypt(&sealed).is_err());
+        assert_eq!(
+            b"seeds: 79 14 55 13".to_vec(),
+            new_key.decrypt(&sealed).unwrap()
+        );
+        assert_eq!(
+            old_key,
+            Key::read(&dir.path().join("input.key.old")).unwrap()
+        );
+    }
+}
@@ -0,0 +1,440 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use crate::{
+    cli::{LeaderboardArgs, Report},
+    table::{Align, Table},
+};
+
+// region:    --- Export
+
+/// A private leaderboard as exported by the site, `/<year>/leaderboard/private/view/<id>.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub local_score: u64,
+    // day, then part, as strings
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    // the order stars were given in, which breaks ties between equal timestamps
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    // the site shows members without a name by their id
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star(&self, day: u8, part: u8) -> Option<Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .copied()
+    }
+
+    fn finished(&self, day: u8) -> Option<Star> {
+        self.star(day, 2)
+    }
+}
+
+impl Leaderboard {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("in {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    pub fn year(&self) -> Result<i64> {
+        self.event
+            .parse()
+            .map_err(|_| anyhow!("event `{}` is not a year", self.event))
+    }
+
+    // in a stable order, by id
+    fn members(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|member| member.id);
+        members
+    }
+
+    // the members with the star, first to last
+    fn ranking(&self, day: u8, part: u8) -> Vec<(&Member, Star)> {
+        let mut ranking = self
+            .members()
+            .into_iter()
+            .filter_map(|member| Some((member, member.star(day, part)?)))
+            .collect::<Vec<_>>();
+        ranking.sort_by_key(|(_, star)| (star.get_star_ts, star.star_index));
+        ranking
+    }
+}
+
+// a puzzle opens at midnight in the site's time zone, UTC-5
+pub fn unlock(year: i64, day: u8) -> i64 {
+    days_from_civil(year, 12, day as i64) * 86_400 + 5 * 3_600
+}
+
+// days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+// like the site's personal times, in hours past the opening
+fn since(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+// endregion: --- Export
+
+// region:    --- Reports
+
+// who finished each day first, by part 2 and then by part 1 for those with one star
+pub fn solve_order(leaderboard: &Leaderboard, days: &[u8]) -> Result<Table> {
+    let year = leaderboard.year()?;
+    let mut table = Table::new(&[
+        ("day", Align::Right),
+        ("#", Align::Right),
+        ("member", Align::Left),
+        ("part 1", Align::Right),
+        ("part 2", Align::Right),
+    ]);
+
+    for &day in days {
+        let mut members = leaderboard
+            .members()
+            .into_iter()
+            .filter(|member| member.star(day, 1).is_some())
+            .collect::<Vec<_>>();
+        members.sort_by_key(|member| {
+            let last = member.finished(day).or(member.star(day, 1));
+            (
+                member.finished(day).is_none(),
+                last.map(|star| (star.get_star_ts, star.star_index)),
+            )
+        });
+
+        for (rank, member) in members.into_iter().enumerate() {
+            let time = |part| {
+                member.star(day, part).map_or(String::new(), |star| {
+                    since(star.get_star_ts - unlock(year, day))
+                })
+            };
+            table.push(vec![
+                day.to_string(),
+                (rank + 1).to_string(),
+                member.display_name(),
+                time(1),
+                time(2),
+            ]);
+        }
+    }
+
+    Ok(table)
+}
+
+// how long part 2 took once part 1 was done, quickest first
+pub fn part_gaps(leaderboard: &Leaderboard, days: &[u8]) -> Table {
+    let mut table = Table::new(&[
+        ("day", Align::Right),
+        ("member", Align::Left),
+        ("gap", Align::Right),
+    ]);
+
+    for &day in days {
+        let mut gaps = leaderboard
+            .members()
+            .into_iter()
+            .filter_map(|member| {
+                let gap = member.star(day, 2)?.get_star_ts - member.star(day, 1)?.get_star_ts;
+                Some((gap, member))
+            })
+            .collect::<Vec<_>>();
+        gaps.sort_by_key(|(gap, member)| (*gap, member.id));
+
+        for (gap, member) in gaps {
+            table.push(vec![day.to_string(), member.display_name(), since(gap)]);
+        }
+    }
+
+    table
+}
+
+// The local score gives each star as many points as there are members, less one per member
+// who got it earlier. The exported score is shown next to it, they differ when the site left
+// a day out.
+pub fn local_scores(leaderboard: &Leaderboard) -> Table {
+    let count = leaderboard.members.len() as u64;
+    let mut scores = BTreeMap::<u64, (u64, usize)>::new();
+    for day in 1..=25 {
+        for part in 1..=2 {
+            for (rank, (member, _)) in leaderboard.ranking(day, part).into_iter().enumerate() {
+                let (score, stars) = scores.entry(member.id).or_default();
+                *score += count - rank as u64;
+                *stars += 1;
+            }
+        }
+    }
+
+    let mut members = leaderboard.members();
+    members.sort_by_key(|member| {
+        let score = scores.get(&member.id).map_or(0, |(score, _)| *score);
+        (std::cmp::Reverse(score), member.id)
+    });
+
+    let mut table = Table::new(&[
+        ("#", Align::Right),
+        ("member", Align::Left),
+        ("stars", Align::Right),
+        ("score", Align::Right),
+        ("exported", Align::Right),
+    ]);
+    for (rank, member) in members.into_iter().enumerate() {
+        let (score, stars) = scores.get(&member.id).copied().unwrap_or_default();
+        table.push(vec![
+            (rank + 1).to_string(),
+            member.display_name(),
+            stars.to_string(),
+            score.to_string(),
+            member.local_score.to_string(),
+        ]);
+    }
+
+    table
+}
+
+// A day counts for a streak when both stars came within a day of it opening, the longest
+// streak is the most such days in a row.
+pub fn streaks(leaderboard: &Leaderboard) -> Result<Table> {
+    let year = leaderboard.year()?;
+    let mut table = Table::new(&[
+        ("member", Align::Left),
+        ("finished", Align::Right),
+        ("on the day", Align::Right),
+        ("longest streak", Align::Right),
+        ("days", Align::Left),
+    ]);
+
+    for member in leaderboard.members() {
+        let finished = (1..=25)
+            .filter(|&day| member.finished(day).is_some())
+            .count();
+
+        let mut on_the_day = 0;
+        let (mut longest, mut longest_end, mut current) = (0, 0, 0);
+        for day in 1..=25 {
+            let in_time = member
+                .finished(day)
+                .is_some_and(|star| star.get_star_ts - unlock(year, day) < 86_400);
+            if in_time {
+                on_the_day += 1;
+                current += 1;
+                if current > longest {
+                    (longest, longest_end) = (current, day);
+                }
+            } else {
+                current = 0;
+            }
+        }
+
+        let days = match longest {
+            0 => String::new(),
+            1 => longest_end.to_string(),
+            _ => format!("{}-{longest_end}", longest_end + 1 - longest),
+        };
+        table.push(vec![
+            member.display_name(),
+            finished.to_string(),
+            on_the_day.to_string(),
+            longest.to_string(),
+            days,
+        ]);
+    }
+
+    Ok(table)
+}
+
+// endregion: --- Reports
+
+pub fn leaderboard(args: &LeaderboardArgs) -> Result<()> {
+    let leaderboard = Leaderboard::load(&args.file)?;
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => (1..=25).collect(),
+    };
+    let reports = match &args.report[..] {
+        [] => vec![Report::Order, Report::Gaps, Report::Scores, Report::Streaks],
+        reports => reports.to_vec(),
+    };
+
+    for report in reports {
+        let (title, table) = match report {
+            Report::Order => ("Solve order", solve_order(&leaderboard, &days)?),
+            Report::Gaps => ("Part 1 to part 2", part_gaps(&leaderboard, &days)),
+            Report::Scores => ("Local score", local_scores(&leaderboard)),
+            Report::Streaks => ("Streaks", streaks(&leaderboard)?),
+        };
+
+        if args.markdown {
+            println!("## {title}\n\n{}", table.markdown());
+        } else if table.is_empty() {
+            println!("{title}: nothing yet\n");
+        } else {
+            println!("{title}\n{}", table.terminal());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    // day 1 opened at 1701406800, day 2 a day later
+    const EXPORT: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "ada", "stars": 4, "local_score": 10,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407400, "star_index": 10},
+                        "2": {"get_star_ts": 1701408000, "star_index": 12}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1701493800, "star_index": 20},
+                        "2": {"get_star_ts": 1701497400, "star_index": 21}
+                    }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 3, "local_score": 8,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407100, "star_index": 9},
+                        "2": {"get_star_ts": 1701408000, "star_index": 11}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1701580000, "star_index": 30}
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "grace", "stars": 0, "local_score": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn column(table: &Table, title: &str) -> Vec<String> {
+        let markdown = table.markdown();
+        let mut lines = markdown.lines();
+        let titles = lines.next().unwrap().trim_matches('|').split(" | ");
+        let idx = titles.map(str::trim).position(|t| t == title).unwrap();
+
+        lines
+            .skip(1)
+            .map(|line| {
+                line.trim_matches('|')
+                    .split(" | ")
+                    .nth(idx)
+                    .unwrap()
+                    .trim()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_unlock() {
+        assert_eq!(1701406800, unlock(2023, 1));
+        assert_eq!(1448946000, unlock(2015, 1));
+        assert_eq!("1:02:03", since(3723));
+    }
+
+    #[test]
+    fn test_solve_order() {
+        let leaderboard = Leaderboard::parse(EXPORT).unwrap();
+        let table = solve_order(&leaderboard, &[1, 2]).unwrap();
+
+        // the tie on day 1 part 2 goes to the earlier star
+        assert_eq!(
+            vec!["(anonymous user #2)", "ada", "ada", "(anonymous user #2)"],
+            column(&table, "member")
+        );
+        assert_eq!(
+            vec!["0:20:00", "0:20:00", "1:10:00", ""],
+            column(&table, "part 2")
+        );
+        assert_eq!("24:06:40", column(&table, "part 1")[3]);
+    }
+
+    #[test]
+    fn test_part_gaps() {
+        let leaderboard = Leaderboard::parse(EXPORT).unwrap();
+        let table = part_gaps(&leaderboard, &[1, 2]);
+
+        assert_eq!(vec!["0:10:00", "0:15:00", "1:00:00"], column(&table, "gap"));
+    }
+
+    #[test]
+    fn test_local_scores() {
+        let leaderboard = Leaderboard::parse(EXPORT).unwrap();
+        let table = local_scores(&leaderboard);
+
+        assert_eq!(
+            vec!["ada", "(anonymous user #2)", "grace"],
+            column(&table, "member")
+        );
+        assert_eq!(vec!["10", "8", "0"], column(&table, "score"));
+        assert_eq!(column(&table, "exported"), column(&table, "score"));
+    }
+
+    #[test]
+    fn test_streaks() {
+        let leaderboard = Leaderboard::parse(EXPORT).unwrap();
+        let table = streaks(&leaderboard).unwrap();
+
+        assert_eq!(vec!["2", "1", "0"], column(&table, "longest streak"));
+        assert_eq!(vec!["1-2", "1", ""], column(&table, "days"));
+    }
+}
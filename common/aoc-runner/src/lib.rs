@@ -0,0 +1,28 @@
+// region:    --- Modules
+pub use cli::main;
+pub use day::{model, Command, Day, Generator, Model, ModelSolver, Parser, Year};
+
+mod batch;
+mod bench;
+mod cli;
+pub mod client;
+pub mod config;
+pub mod day;
+pub mod examples;
+pub mod fetch;
+pub mod fuzz;
+mod generate;
+mod inputs;
+pub mod leaderboard;
+pub mod manifest;
+#[cfg(test)]
+mod mock;
+mod record;
+mod repl;
+mod report;
+mod run;
+mod scaffold;
+mod status;
+pub mod submit;
+pub mod table;
+// endregion: --- Modules
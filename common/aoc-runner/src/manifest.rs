@@ -0,0 +1,304 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::Params;
+use serde::{Deserialize, Deserializer};
+use toml::Value;
+use toml_edit::{ArrayOfTables, DocumentMut, InlineTable, Item, Table};
+
+/// What is known about each day of a year beyond its code, read from `manifest.toml`:
+///
+/// ```toml
+/// [day-24.params]
+/// window_start = 200000000000000
+///
+/// [day-25]
+/// timeout = 300 # seconds
+///
+/// [day-1]
+/// part1 = 54331 # the accepted answers, checked by `aoc status`
+///
+/// [[day-1.examples]]
+/// input = "test-1.txt"
+/// part1 = "142"
+///
+/// [[day-24.examples]]
+/// input = "test-1.txt"
+/// part1 = 2
+/// params = { window_start = 7, window_end = 27 } # on top of the day's example defaults
+/// ```
+#[derive(Debug, Default, Deserialize)]
+pub struct Manifest {
+    #[serde(flatten)]
+    days: BTreeMap<String, DayManifest>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayManifest {
+    #[serde(default)]
+    params: BTreeMap<String, Value>,
+    timeout: Option<u64>,
+    #[serde(default, deserialize_with = "answer")]
+    part1: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    part2: Option<String>,
+    #[serde(default)]
+    examples: Vec<Example>,
+}
+
+/// An example input of the puzzle text, in the day's directory, and the answers given for it.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Example {
+    pub input: String,
+    #[serde(default, deserialize_with = "answer")]
+    pub part1: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    pub part2: Option<String>,
+    // what the puzzle text changes for this example, e.g. fewer steps
+    #[serde(default)]
+    pub params: BTreeMap<String, Value>,
+}
+
+impl Example {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => unreachable!("a day has two parts"),
+        }
+    }
+
+    pub fn params(&self) -> Result<Params> {
+        to_params(&self.params).with_context(|| format!("parameters of {}", self.input))
+    }
+}
+
+// answers are compared as text, but numbers are easier to type by hand
+fn answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::String(text) => Ok(Some(text)),
+        Value::Integer(number) => Ok(Some(number.to_string())),
+        _ => Err(serde::de::Error::custom(
+            "an answer should be a string or an integer",
+        )),
+    }
+}
+
+impl Manifest {
+    // a year without a manifest has nothing to add
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).with_context(|| format!("in {}", path.display())),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn day(&self, day: u8) -> Option<&DayManifest> {
+        self.days.get(&format!("day-{day}"))
+    }
+
+    pub fn params(&self, day: u8) -> Result<Params> {
+        self.day(day)
+            .map_or(Ok(Params::new()), |manifest| to_params(&manifest.params))
+            .with_context(|| format!("parameters of day-{day}"))
+    }
+
+    pub fn timeout(&self, day: u8) -> Option<Duration> {
+        self.day(day)
+            .and_then(|manifest| manifest.timeout)
+            .map(Duration::from_secs)
+    }
+
+    // the accepted answer of a part on the real input
+    pub fn answer(&self, day: u8, part: u8) -> Option<&str> {
+        let manifest = self.day(day)?;
+        match part {
+            1 => manifest.part1.as_deref(),
+            2 => manifest.part2.as_deref(),
+            _ => unreachable!("a day has two parts"),
+        }
+    }
+
+    pub fn examples(&self, day: u8) -> &[Example] {
+        self.day(day).map_or(&[], |manifest| &manifest.examples)
+    }
+
+    // the example of a day on the file `input`, e.g. `test-1.txt`
+    pub fn example(&self, day: u8, input: &str) -> Option<&Example> {
+        self.examples(day)
+            .iter()
+            .find(|example| example.input == input)
+    }
+}
+
+// replaces the examples of `day` in the manifest at `path`, keeping the rest of the file as
+// it was written
+pub fn write_examples(path: &Path, day: u8, examples: &[Example]) -> Result<()> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+    };
+    let mut document = content
+        .parse::<DocumentMut>()
+        .with_context(|| format!("in {}", path.display()))?;
+
+    let day_table = document
+        .entry(&format!("day-{day}"))
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("day-{day} in {} is not a table", path.display()))?;
+
+    let mut tables = ArrayOfTables::new();
+    for example in examples {
+        let mut table = Table::new();
+        table.insert("input", toml_edit::value(&example.input));
+        for (key, answer) in [("part1", &example.part1), ("part2", &example.part2)] {
+            if let Some(answer) = answer {
+                table.insert(key, toml_edit::value(answer));
+            }
+        }
+        if !example.params.is_empty() {
+            let mut params = InlineTable::new();
+            for (name, value) in &example.params {
+                let value = value
+                    .to_string()
+                    .parse::<toml_edit::Value>()
+                    .with_context(|| format!("parameter `{name}` of {}", example.input))?;
+                params.insert(name, value);
+            }
+            table.insert("params", toml_edit::value(params));
+        }
+        tables.push(table);
+    }
+    day_table.insert("examples", Item::ArrayOfTables(tables));
+
+    fs::write(path, document.to_string()).with_context(|| format!("writing {}", path.display()))
+}
+
+fn to_params(table: &BTreeMap<String, Value>) -> Result<Params> {
+    table
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(text) => text.clone(),
+                Value::Integer(number) => number.to_string(),
+                Value::Float(number) => number.to_string(),
+                Value::Boolean(flag) => flag.to_string(),
+                _ => return Err(anyhow!("`{name}` should be a string, number or boolean")),
+            };
+
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_params() {
+        let manifest = Manifest::parse(
+            r#"
+            [day-11.params]
+            scale = 10
+
+            [day-24.params]
+            window_start = 7.5
+            window_end = "27"
+            "#,
+        )
+        .expect("valid manifest");
+
+        let params = manifest.params(24).unwrap();
+        assert_eq!(7.5, params.get::<f64>("window_start").unwrap());
+        assert_eq!(27, params.get::<i64>("window_end").unwrap());
+        assert_eq!(
+            10,
+            manifest.params(11).unwrap().get::<u64>("scale").unwrap()
+        );
+        assert!(manifest.params(5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let manifest = Manifest::parse("[day-25]\ntimeout = 300").expect("valid manifest");
+
+        assert_eq!(Some(Duration::from_secs(300)), manifest.timeout(25));
+        assert!(manifest.params(25).unwrap().is_empty());
+        assert_eq!(None, manifest.timeout(24));
+    }
+
+    #[test]
+    fn test_answer() {
+        let manifest =
+            Manifest::parse("[day-1]\npart1 = 54331\npart2 = \"54518\"").expect("valid manifest");
+
+        assert_eq!(Some("54331"), manifest.answer(1, 1));
+        assert_eq!(Some("54518"), manifest.answer(1, 2));
+        assert_eq!(None, manifest.answer(2, 1));
+    }
+
+    #[test]
+    fn test_write_examples() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("manifest.toml");
+        fs::write(&path, "# kept\n[day-24.params]\nwindow_start = 7\n").unwrap();
+
+        let examples = vec![
+            Example {
+                input: "test-1.txt".into(),
+                part1: Some("142".into()),
+                ..Example::default()
+            },
+            Example {
+                input: "test-2.txt".into(),
+                part2: Some("281".into()),
+                params: BTreeMap::from([("steps".into(), Value::Integer(6))]),
+                ..Example::default()
+            },
+        ];
+        write_examples(&path, 1, &examples).unwrap();
+        write_examples(&path, 1, &examples).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# kept\n"));
+        let manifest = Manifest::parse(&content).unwrap();
+        assert_eq!(examples, manifest.examples(1));
+        let example = manifest.example(1, "test-2.txt").unwrap();
+        assert_eq!(6, example.params().unwrap().get::<u64>("steps").unwrap());
+        assert_eq!(None, manifest.example(1, "test-3.txt"));
+        assert_eq!(
+            7,
+            manifest
+                .params(24)
+                .unwrap()
+                .get::<u64>("window_start")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_manifest() {
+        assert!(Manifest::parse("[day-24]\nwindow = 7").is_err());
+        assert!(Manifest::parse("[day-24.params]\nwindow = [7, 27]")
+            .unwrap()
+            .params(24)
+            .is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use std::thread::{self, JoinHandle};
+
+use tiny_http::{Response, Server};
+
+/// A request as the stand-in server saw it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seen {
+    pub method: String,
+    pub url: String,
+    pub cookie: String,
+    pub body: String,
+}
+
+// a local stand-in for the site answering `requests` requests with `respond`'s status and
+// page, the handle gives back every request it saw
+pub fn serve(
+    requests: usize,
+    mut respond: impl FnMut(&Seen) -> (u16, String) + Send + 'static,
+) -> (String, JoinHandle<Vec<Seen>>) {
+    let server = Server::http("127.0.0.1:0").expect("mock server started");
+    let base_url = format!("http://{}", server.server_addr());
+
+    let handle = thread::spawn(move || {
+        let mut seen = vec![];
+        for mut request in server.incoming_requests().take(requests) {
+            let cookie = request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("Cookie"))
+                .map(|header| header.value.to_string())
+                .unwrap_or_default();
+            let mut body = String::new();
+            request
+                .as_reader()
+                .read_to_string(&mut body)
+                .expect("request body read");
+
+            let request_seen = Seen {
+                method: request.method().to_string(),
+                url: request.url().to_string(),
+                cookie,
+                body,
+            };
+            let (status, page) = respond(&request_seen);
+            request
+                .respond(Response::from_string(page).with_status_code(status))
+                .expect("response sent");
+            seen.push(request_seen);
+        }
+        seen
+    });
+
+    (base_url, handle)
+}
@@ -0,0 +1,161 @@
+use std::{io::Write, time::Duration};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::run::Outcome;
+
+// what the template and the stubbed parts return
+pub const NOT_IMPLEMENTED: &str = "Not Implemented.";
+
+// region:    --- Record
+
+/// How a part ended, as the JSON output names it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RecordStatus {
+    Ok,
+    Error,
+    Panic,
+    Timeout,
+    NotImplemented,
+    NoInput,
+    // the day has no crate
+    Missing,
+}
+
+/// One part of one day, a line of `--format jsonl` or an item of `--format json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    // the file a batch ran on, the day's own input otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    pub status: RecordStatus,
+    pub answer: Option<String>,
+    // the accepted answer in the manifest
+    pub expected: Option<String>,
+    pub error: Option<String>,
+    // in seconds, for the parts that ran
+    pub parse_seconds: Option<f64>,
+    pub solve_seconds: Option<f64>,
+    // for the days that use randomness, to repeat the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    // only for `bench`, which gives the median of the runs as the solve time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<usize>,
+}
+
+impl Record {
+    pub fn new(year: u16, day: u8, part: u8, status: RecordStatus) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            input: None,
+            status,
+            answer: None,
+            expected: None,
+            error: None,
+            parse_seconds: None,
+            solve_seconds: None,
+            seed: None,
+            runs: None,
+        }
+    }
+
+    pub fn solved(
+        (year, day, part): (u16, u8, u8),
+        outcome: &Outcome,
+        elapsed: Duration,
+        timeout: Duration,
+    ) -> Self {
+        let (status, answer, error) = match outcome {
+            Outcome::Solved(answer) => (RecordStatus::Ok, Some(answer.clone()), None),
+            Outcome::Failed(err) if err.to_string() == NOT_IMPLEMENTED => {
+                (RecordStatus::NotImplemented, None, None)
+            }
+            Outcome::Failed(err) => (RecordStatus::Error, None, Some(format!("{err:#}"))),
+            Outcome::Panicked(message) => (RecordStatus::Panic, None, Some(message.clone())),
+            Outcome::TimedOut => (
+                RecordStatus::Timeout,
+                None,
+                Some(format!("timed out after {timeout:?}")),
+            ),
+        };
+
+        Self {
+            answer,
+            error,
+            solve_seconds: Some(elapsed.as_secs_f64()),
+            ..Self::new(year, day, part, status)
+        }
+    }
+
+    pub fn with_expected(mut self, expected: Option<&str>) -> Self {
+        self.expected = expected.map(str::to_string);
+        self
+    }
+
+    pub fn with_parse(mut self, elapsed: Option<Duration>) -> Self {
+        self.parse_seconds = elapsed.map(|elapsed| elapsed.as_secs_f64());
+        self
+    }
+}
+
+// endregion: --- Record
+
+// region:    --- Output
+
+// a line at a time, flushed so another tool can follow a long run
+pub fn write_line(out: &mut impl Write, record: &Record) -> Result<()> {
+    serde_json::to_writer(&mut *out, record)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
+
+pub fn write_all(out: &mut impl Write, records: &[Record]) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, records)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+// endregion: --- Output
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_solved() {
+        let second = Duration::from_secs(1);
+        let record = Record::solved((2023, 5, 1), &Outcome::Solved("35".into()), second, second)
+            .with_expected(Some("35"));
+        let mut out = vec![];
+        write_line(&mut out, &record).unwrap();
+
+        assert_eq!(
+            "{\"year\":2023,\"day\":5,\"part\":1,\"status\":\"ok\",\"answer\":\"35\",\
+             \"expected\":\"35\",\"error\":null,\"parse_seconds\":null,\"solve_seconds\":1.0}\n",
+            String::from_utf8(out).unwrap()
+        );
+
+        let failed = Outcome::Failed(anyhow!(NOT_IMPLEMENTED));
+        assert_eq!(
+            RecordStatus::NotImplemented,
+            Record::solved((2023, 5, 2), &failed, second, second).status
+        );
+        let timed_out = Record::solved((2023, 5, 2), &Outcome::TimedOut, second, second);
+        assert_eq!(RecordStatus::Timeout, timed_out.status);
+        assert_eq!(Some("timed out after 1s"), timed_out.error.as_deref());
+    }
+}
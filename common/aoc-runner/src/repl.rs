@@ -0,0 +1,201 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Result};
+use aoc_core::Params;
+
+use crate::{
+    cli::ReplArgs,
+    day::{select_year, Day, Model, Year},
+    manifest::Manifest,
+    run::{self, isolate, Outcome, DEFAULT_TIMEOUT},
+};
+
+// the day's model, parsed once, then a command per line until the input ends or `quit`
+pub fn repl(years: &[Year], args: &ReplArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+    let day = *year
+        .day(args.day)
+        .ok_or_else(|| anyhow!("day {} of {} is not registered", args.day, year.year))?;
+    let manifest = Manifest::load(&year.manifest_path())?;
+    let overrides = args.params.iter().cloned().collect::<Params>();
+
+    let (input, params) = match args.example {
+        Some(number) => {
+            let name = format!("test-{number}.txt");
+            let path = year.day_dir(day.day).join(&name);
+            let input =
+                fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+            (
+                input,
+                run::example_params(&day, &manifest, &name, &overrides)?,
+            )
+        }
+        None => {
+            let input = year
+                .read_input(day.day)?
+                .ok_or_else(|| anyhow!("no input at {}", year.input_path(day.day).display()))?;
+            (input, run::params(&day, &manifest, &overrides)?)
+        }
+    };
+    let timeout = args
+        .timeout
+        .map(Duration::from_secs)
+        .or(manifest.timeout(day.day))
+        .unwrap_or(DEFAULT_TIMEOUT);
+
+    let start = Instant::now();
+    let model = day.parse(&input, &params).context("parsing the input")?;
+    eprintln!(
+        "{} day {:>2}: parsed in {:.2?}, `help` lists the commands",
+        year.year,
+        day.day,
+        start.elapsed()
+    );
+
+    session(
+        &day,
+        &model,
+        &params,
+        timeout,
+        io::stdin().lock(),
+        &mut io::stdout(),
+    )
+}
+
+// a failing, panicking or hanging command is reported and the session goes on
+fn session(
+    day: &Day,
+    model: &Model,
+    params: &Params,
+    timeout: Duration,
+    input: impl BufRead,
+    out: &mut impl Write,
+) -> Result<()> {
+    prompt(day, out)?;
+    for line in input.lines() {
+        let line = line?;
+        let (name, args) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        match name {
+            "" => {}
+            "quit" | "exit" => break,
+            "help" => writeln!(out, "{}", help(day))?,
+            _ => match answer(day, model, params, timeout, name, args.trim()) {
+                Ok(answer) => writeln!(out, "{}", answer.trim_end())?,
+                Err(err) => writeln!(out, "error: {err:#}")?,
+            },
+        }
+        prompt(day, out)?;
+    }
+
+    Ok(())
+}
+
+fn prompt(day: &Day, out: &mut impl Write) -> Result<()> {
+    write!(out, "day {}> ", day.day)?;
+    out.flush()?;
+
+    Ok(())
+}
+
+fn help(day: &Day) -> String {
+    ["part1", "part2"]
+        .into_iter()
+        .chain(day.commands.iter().map(|&(usage, _)| usage))
+        .chain(["help", "quit"])
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// the parts and the day's commands run like a part of `aoc run`, on their own thread
+fn answer(
+    day: &Day,
+    model: &Model,
+    params: &Params,
+    timeout: Duration,
+    name: &str,
+    args: &str,
+) -> Result<String> {
+    let (day, model, params, args) = (*day, Arc::clone(model), params.clone(), args.to_string());
+    let outcome = match name {
+        "part1" => isolate(move || day.solve(1, &model, &params), timeout),
+        "part2" => isolate(move || day.solve(2, &model, &params), timeout),
+        _ => {
+            let command = day
+                .command(name)
+                .ok_or_else(|| anyhow!("no command {name}, `help` lists them"))?;
+            isolate(move || command(&model, &args), timeout)
+        }
+    };
+
+    match outcome {
+        Outcome::Solved(answer) => Ok(answer),
+        Outcome::Failed(err) => Err(err),
+        Outcome::Panicked(message) => Err(anyhow!("panicked: {message}")),
+        Outcome::TimedOut => Err(anyhow!("timed out after {timeout:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day::model;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_session() {
+        let day = Day::parsed(
+            5,
+            |input, _| {
+                let numbers = input
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<Vec<u64>, _>>()?;
+                Ok(Arc::new(numbers))
+            },
+            |parsed, _| Ok(model::<Vec<u64>>(parsed)?.len().to_string()),
+            |parsed, _| Ok(model::<Vec<u64>>(parsed)?.iter().sum::<u64>().to_string()),
+        )
+        .with_commands(&[("nth INDEX", |parsed, args| {
+            let numbers = model::<Vec<u64>>(parsed)?;
+            Ok(numbers[args.parse::<usize>()?].to_string())
+        })]);
+        let model = day.parse("3 4 5", &Params::new()).unwrap();
+
+        let mut out = vec![];
+        let input = "part2\nnth 1\nnth x\nnth 7\n\nmap 3\nhelp\nquit\npart1\n";
+        session(
+            &day,
+            &model,
+            &Params::new(),
+            Duration::from_secs(10),
+            input.as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.split("day 5> ").collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "",
+                "12\n",
+                "4\n",
+                "error: invalid digit found in string\n",
+                "error: panicked: index out of bounds: the len is 3 but the index is 7\n",
+                "",
+                "error: no command map, `help` lists them\n",
+                "part1\npart2\nnth INDEX\nhelp\nquit\n",
+                "",
+            ],
+            lines
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    bench::{self, Saved},
+    cli::ReportArgs,
+    day::{select_years, Year},
+    manifest::Manifest,
+};
+
+// what the day pages show of the files in a day's directory
+const IMAGE_EXTENSIONS: [&str; 4] = ["gif", "png", "jpg", "svg"];
+const CHART_WIDTH: f64 = 600.0;
+const CHART_HEIGHT: f64 = 160.0;
+
+// everything is inline, the site works offline and from a plain directory
+const STYLE: &str = "
+body { font-family: monospace; background: #0f0f23; color: #cccccc; margin: 2em auto; max-width: 60em; }
+a { color: #009900; }
+h1, h2 { color: #00cc00; }
+.calendar { display: grid; grid-template-columns: repeat(5, 1fr); gap: 0.5em; }
+.day { border: 1px solid #333340; padding: 0.5em; }
+.day.missing { color: #555560; }
+.stars { color: #ffff66; }
+.silver { color: #9999cc; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 1em; text-align: left; }
+svg { background: #10101a; }
+img { max-width: 100%; image-rendering: pixelated; }
+";
+
+// region:    --- Collect
+
+/// What the report shows of a day, from the manifest, the saved benchmarks and its directory.
+#[derive(Debug, Default)]
+pub struct DayReport {
+    pub day: u8,
+    pub registered: bool,
+    // the accepted answers, a star each
+    pub answers: [Option<String>; 2],
+    // when and how long, oldest first
+    pub timings: [Vec<(u64, f64)>; 2],
+    pub images: Vec<PathBuf>,
+    pub sources: Vec<PathBuf>,
+}
+
+impl DayReport {
+    fn collect(year: &Year, day: u8, manifest: &Manifest, history: &[Saved]) -> Result<Self> {
+        let dir = year.day_dir(day);
+        let timings = [1, 2].map(|part| {
+            let mut timings = history
+                .iter()
+                .filter(|saved| saved.record.day == day && saved.record.part == part)
+                .filter_map(|saved| Some((saved.at, saved.record.solve_seconds?)))
+                .collect::<Vec<_>>();
+            timings.sort_by_key(|(at, _)| *at);
+            timings
+        });
+
+        let mut sources = files(&dir.join("src"), &["rs"])?;
+        sources.extend(files(&dir.join("src/bin"), &["rs"])?);
+
+        Ok(Self {
+            day,
+            registered: year.day(day).is_some(),
+            answers: [1, 2].map(|part| manifest.answer(day, part).map(str::to_string)),
+            timings,
+            images: files(&dir, &IMAGE_EXTENSIONS)?,
+            sources,
+        })
+    }
+
+    fn stars(&self) -> usize {
+        self.answers.iter().flatten().count()
+    }
+}
+
+// the files right in `dir` with one of the extensions, by name, as absolute paths
+fn files(dir: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(vec![]);
+    };
+
+    let mut files = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        if path.is_file() && extension.is_some_and(|extension| extensions.contains(&extension)) {
+            // the year roots have `..` in them, which the links can't start from
+            files.push(fs::canonicalize(&path)?);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+// endregion: --- Collect
+
+// region:    --- Pages
+
+pub fn report(years: &[Year], args: &ReportArgs) -> Result<()> {
+    fs::create_dir_all(&args.out).with_context(|| format!("creating {}", args.out.display()))?;
+    let out = fs::canonicalize(&args.out)?;
+
+    let mut index = String::from("<h1>Advent of Code</h1>\n");
+    for year in select_years(years, args.year)? {
+        let manifest = Manifest::load(&year.manifest_path())?;
+        let history = bench::history(&year.benchmarks_path())?;
+        let days = (1..=25)
+            .map(|day| DayReport::collect(year, day, &manifest, &history))
+            .collect::<Result<Vec<_>>>()?;
+
+        index.push_str(&calendar(year.year, &days));
+
+        let year_dir = out.join(year.year.to_string());
+        fs::create_dir_all(&year_dir)?;
+        for day in days.iter().filter(|day| day.registered) {
+            // the images are copied, so the site can be moved on its own
+            let image_dir = year_dir.join(format!("day-{}", day.day));
+            if !day.images.is_empty() {
+                fs::create_dir_all(&image_dir)?;
+            }
+            for image in &day.images {
+                let name = image.file_name().expect("a file has a name");
+                fs::copy(image, image_dir.join(name))
+                    .with_context(|| format!("copying {}", image.display()))?;
+            }
+
+            let title = format!("{} day {}", year.year, day.day);
+            let body = day_page(year.year, day, &year_dir);
+            write(
+                &year_dir.join(format!("day-{}.html", day.day)),
+                &page(&title, &body),
+            )?;
+        }
+    }
+
+    let path = out.join("index.html");
+    write(&path, &page("Advent of Code", &index))?;
+    println!("report written to {}", path.display());
+
+    Ok(())
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+// a cell per day, in rows of five like the calendar of the site
+fn calendar(year: u16, days: &[DayReport]) -> String {
+    let stars = days.iter().map(DayReport::stars).sum::<usize>();
+    let mut html = format!("<h2>{year} <span class=\"stars\">{stars}*</span></h2>\n");
+    html.push_str("<div class=\"calendar\">\n");
+    for day in days {
+        let stars = match day.stars() {
+            0 => String::new(),
+            1 => "<span class=\"silver\">*</span>".to_string(),
+            _ => "<span class=\"stars\">**</span>".to_string(),
+        };
+        if day.registered {
+            writeln!(
+                html,
+                "<div class=\"day\"><a href=\"{year}/day-{0}.html\">{0}</a> {stars}</div>",
+                day.day
+            )
+        } else {
+            writeln!(html, "<div class=\"day missing\">{}</div>", day.day)
+        }
+        .expect("writing to a string");
+    }
+    html.push_str("</div>\n");
+
+    html
+}
+
+fn day_page(year: u16, day: &DayReport, year_dir: &Path) -> String {
+    let mut html = format!(
+        "<p><a href=\"../index.html\">calendar</a></p>\n<h1>{year} day {}</h1>\n",
+        day.day
+    );
+
+    html.push_str("<h2>Answers</h2>\n<table>\n");
+    for (part, answer) in day.answers.iter().enumerate() {
+        let time = day.timings[part]
+            .last()
+            .map_or(String::new(), |(_, seconds)| duration(*seconds));
+        writeln!(
+            html,
+            "<tr><th>part {}</th><td>{}</td><td>{time}</td></tr>",
+            part + 1,
+            answer.as_deref().map_or("-".to_string(), escape)
+        )
+        .expect("writing to a string");
+    }
+    html.push_str("</table>\n");
+
+    if day.timings.iter().any(|timings| !timings.is_empty()) {
+        html.push_str("<h2>Benchmarks</h2>\n");
+        for (part, timings) in day.timings.iter().enumerate() {
+            if !timings.is_empty() {
+                writeln!(html, "<h3>part {}</h3>\n{}", part + 1, chart(timings))
+                    .expect("writing to a string");
+            }
+        }
+    }
+
+    if !day.images.is_empty() {
+        html.push_str("<h2>Images</h2>\n");
+        for image in &day.images {
+            let name = image
+                .file_name()
+                .expect("a file has a name")
+                .to_string_lossy();
+            writeln!(
+                html,
+                "<p><img src=\"day-{}/{}\" alt=\"{}\"></p>",
+                day.day,
+                escape(&name),
+                escape(&name)
+            )
+            .expect("writing to a string");
+        }
+    }
+
+    html.push_str("<h2>Source</h2>\n<ul>\n");
+    for source in &day.sources {
+        let link = relative(year_dir, source);
+        writeln!(
+            html,
+            "<li><a href=\"{}\">{}</a></li>",
+            escape(&link.to_string_lossy()),
+            escape(&source_name(source))
+        )
+        .expect("writing to a string");
+    }
+    html.push_str("</ul>\n");
+
+    html
+}
+
+// `src/lib.rs`, `src/bin/main.rs`
+fn source_name(source: &Path) -> String {
+    let components = source
+        .components()
+        .rev()
+        .take_while(|component| component.as_os_str() != "src")
+        .collect::<Vec<_>>();
+    let name = components
+        .iter()
+        .rev()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    format!("src/{name}")
+}
+
+// endregion: --- Pages
+
+// region:    --- Chart
+
+// the solve times of the saved runs, oldest on the left, as a line from zero to the slowest
+fn chart(timings: &[(u64, f64)]) -> String {
+    let slowest = timings
+        .iter()
+        .map(|(_, seconds)| *seconds)
+        .fold(f64::MIN_POSITIVE, f64::max);
+    let step = CHART_WIDTH / (timings.len().max(2) - 1) as f64;
+    let points = timings
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, seconds))| {
+            (
+                idx as f64 * step,
+                CHART_HEIGHT - seconds / slowest * (CHART_HEIGHT - 10.0),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"-10 -10 {} {}\">\n",
+        CHART_WIDTH + 100.0,
+        CHART_HEIGHT + 20.0,
+        CHART_WIDTH + 100.0,
+        CHART_HEIGHT + 20.0
+    );
+    writeln!(
+        svg,
+        "<polyline fill=\"none\" stroke=\"#00cc00\" points=\"{}\"/>",
+        points
+            .iter()
+            .map(|(x, y)| format!("{x:.1},{y:.1}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+    .expect("writing to a string");
+    for ((x, y), (_, seconds)) in points.iter().zip(timings) {
+        writeln!(
+            svg,
+            "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"3\" fill=\"#ffff66\"><title>{}</title></circle>",
+            duration(*seconds)
+        )
+        .expect("writing to a string");
+    }
+    writeln!(
+        svg,
+        "<text x=\"{}\" y=\"10\" fill=\"#cccccc\">{}</text>\n</svg>",
+        CHART_WIDTH + 10.0,
+        duration(slowest)
+    )
+    .expect("writing to a string");
+
+    svg
+}
+
+fn duration(seconds: f64) -> String {
+    format!("{:.2?}", std::time::Duration::from_secs_f64(seconds))
+}
+
+// endregion: --- Chart
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// `to` as seen from the directory `from`, both absolute
+fn relative(from: &Path, to: &Path) -> PathBuf {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    from[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(to[common..].iter().copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{day::Day, day::Model, record::Record, record::RecordStatus};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_relative() {
+        assert_eq!(
+            PathBuf::from("../../year-2023/rust/day-5/src/lib.rs"),
+            relative(
+                Path::new("/repo/site/2023"),
+                Path::new("/repo/year-2023/rust/day-5/src/lib.rs")
+            )
+        );
+        assert_eq!(
+            "src/bin/main.rs",
+            source_name(Path::new("/repo/day-5/src/bin/main.rs"))
+        );
+    }
+
+    #[test]
+    fn test_chart() {
+        let svg = chart(&[(100, 0.002), (200, 0.001), (300, 0.004)]);
+
+        assert_eq!(3, svg.matches("<circle").count());
+        assert!(svg.contains("points=\"0.0,85.0 300.0,122.5 600.0,10.0\""));
+        assert!(svg.contains("4.00ms"));
+    }
+
+    #[test]
+    fn test_report() {
+        let unimplemented = |_: &Model, _: &_| Err(anyhow::anyhow!("Not Implemented."));
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("year-2023/rust");
+        let year = Year {
+            year: 2023,
+            root: root.clone(),
+            days: vec![Day::parsed(
+                5,
+                |_, _| Ok(Arc::new(())),
+                unimplemented,
+                unimplemented,
+            )],
+        };
+        fs::create_dir_all(root.join("day-5/src")).unwrap();
+        fs::write(root.join("day-5/src/lib.rs"), "").unwrap();
+        fs::write(root.join("day-5/seeds.gif"), "GIF89a").unwrap();
+        fs::write(root.join("manifest.toml"), "[day-5]\npart1 = 35\n").unwrap();
+        let record = Record {
+            solve_seconds: Some(0.001),
+            ..Record::new(2023, 5, 1, RecordStatus::Ok)
+        };
+        bench::save(&year.benchmarks_path(), 100, &[record]).unwrap();
+
+        let out = dir.path().join("site");
+        let args = ReportArgs {
+            year: None,
+            out: out.clone(),
+        };
+        report(&[year], &args).unwrap();
+
+        let index = fs::read_to_string(out.join("index.html")).unwrap();
+        let day = fs::read_to_string(out.join("2023/day-5.html")).unwrap();
+        assert!(index.contains("<a href=\"2023/day-5.html\">5</a> <span class=\"silver\">*</span>"));
+        assert!(index.contains("<div class=\"day missing\">6</div>"));
+        assert!(day.contains("<td>35</td><td>1.00ms</td>"));
+        assert!(day.contains("<img src=\"day-5/seeds.gif\""));
+        assert!(day.contains("<a href=\"../../year-2023/rust/day-5/src/lib.rs\">src/lib.rs</a>"));
+        assert!(out.join("2023/day-5/seeds.gif").exists());
+        // nothing is loaded from elsewhere
+        assert!(!index.contains("https://") && !day.contains("https://"));
+    }
+}
@@ -0,0 +1,541 @@
+use std::{
+    any::Any,
+    fs,
+    io::{self, ErrorKind, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Context, Error, Result};
+use aoc_core::{
+    cancel::{self, Token},
+    rng::SEED,
+    Params,
+};
+
+use crate::{
+    batch,
+    cli::{Format, RunArgs},
+    day::{select_years, Day, Model, Year},
+    manifest::Manifest,
+    record::{self, Record, RecordStatus},
+};
+
+// some solvers recurse deep enough to overflow the 8 MiB of the main thread
+const STACK_SIZE: usize = 1 << 30;
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+// how long a timed out part gets to notice it was cancelled before it is left behind
+const GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+pub enum Outcome<T = String> {
+    Solved(T),
+    Failed(Error),
+    // a bug rather than an input the part rejects, with the panic's message
+    Panicked(String),
+    TimedOut,
+}
+
+pub fn run(years: &[Year], args: &RunArgs) -> Result<()> {
+    let mut years = select_years(years, args.year)?;
+    // a day alone is about the years that have it
+    if let (Some(day), None) = (args.day, args.year) {
+        years.retain(|year| year.day(day).is_some());
+        if years.is_empty() {
+            return Err(anyhow!("day {day} is not registered in any year"));
+        }
+    }
+    if let (Some(dir), Some(day)) = (&args.inputs, args.day) {
+        let [year] = years[..] else {
+            return Err(anyhow!(
+                "day {day} is in more than one year, pick one with --year"
+            ));
+        };
+        return batch::batch(year, day, dir, args);
+    }
+
+    let mut records = vec![];
+    if !args.parallel {
+        for year in years {
+            records.extend(run_year(year, args, &mut io::stdout())?);
+        }
+        return finish(args.format, &records);
+    }
+
+    // each year writes to its own buffer, printed in order so the output reads as in serial
+    let outputs = thread::scope(|scope| {
+        let handles = years
+            .iter()
+            .map(|year| {
+                scope.spawn(|| {
+                    let mut out = vec![];
+                    let result = run_year(year, args, &mut out);
+                    (out, result)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("year thread joined"))
+            .collect::<Vec<_>>()
+    });
+
+    for (out, result) in outputs {
+        io::stdout().write_all(&out)?;
+        records.extend(result?);
+    }
+
+    finish(args.format, &records)
+}
+
+// `json` is one array of every part, written once they all ran
+fn finish(format: Format, records: &[Record]) -> Result<()> {
+    match format {
+        Format::Json => record::write_all(&mut io::stdout(), records),
+        Format::Text | Format::Jsonl => Ok(()),
+    }
+}
+
+// prints the text or the JSON lines as the parts finish, and gives back their records
+fn run_year(year: &Year, args: &RunArgs, out: &mut impl Write) -> Result<Vec<Record>> {
+    let days = match args.day {
+        Some(day) => vec![*year
+            .day(day)
+            .ok_or_else(|| anyhow!("day {day} of {} is not registered", year.year))?],
+        None => year.days.clone(),
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let manifest = Manifest::load(&year.manifest_path())?;
+    let overrides = args.params.iter().cloned().collect::<Params>();
+
+    // `test-N.txt` instead of the real input, with the example's parameters and answers
+    let example = args.example.map(|number| format!("test-{number}.txt"));
+
+    let mut records = vec![];
+    for day in days {
+        let (path, input) = match &example {
+            Some(name) => {
+                let path = year.day_dir(day.day).join(name);
+                let input = match fs::read_to_string(&path) {
+                    Ok(input) => Some(input),
+                    Err(err) if err.kind() == ErrorKind::NotFound => None,
+                    Err(err) => {
+                        return Err(err).with_context(|| format!("reading {}", path.display()))
+                    }
+                };
+                (path, input)
+            }
+            None => (year.input_path(day.day), year.read_input(day.day)?),
+        };
+        let Some(input) = input.map(Arc::<str>::from) else {
+            for &part in &parts {
+                let record = Record {
+                    input: example.clone(),
+                    ..Record::new(year.year, day.day, part, RecordStatus::NoInput)
+                };
+                if args.format == Format::Jsonl {
+                    record::write_line(out, &record)?;
+                }
+                records.push(record);
+            }
+            if args.format != Format::Text {
+                continue;
+            }
+            writeln!(
+                out,
+                "{} day {:>2}: no input at {}",
+                year.year,
+                day.day,
+                path.display()
+            )?;
+            continue;
+        };
+        let params = match &example {
+            Some(name) => example_params(&day, &manifest, name, &overrides)?,
+            None => params(&day, &manifest, &overrides)?,
+        };
+        let params = with_seed(params, args.seed);
+        let seed = seed(&params)?;
+        let timeout = args
+            .timeout
+            .map(Duration::from_secs)
+            .or(manifest.timeout(day.day))
+            .unwrap_or(DEFAULT_TIMEOUT);
+
+        // parsed once, both parts run on the same model
+        let parsed = Parsed::new(&day, &input, &params, timeout);
+        for &part in &parts {
+            let (outcome, elapsed) = parsed.solve(&day, part, &params, timeout);
+
+            let expected = match &example {
+                Some(name) => manifest
+                    .example(day.day, name)
+                    .and_then(|example| example.part(part)),
+                None => manifest.answer(day.day, part),
+            };
+            let record = Record {
+                input: example.clone(),
+                seed,
+                ..Record::solved((year.year, day.day, part), &outcome, elapsed, timeout)
+                    .with_expected(expected)
+                    .with_parse(Some(parsed.elapsed))
+            };
+            let failed = matches!(
+                record.status,
+                RecordStatus::Error | RecordStatus::Panic | RecordStatus::Timeout
+            );
+            if args.format == Format::Jsonl {
+                record::write_line(out, &record)?;
+            }
+            records.push(record);
+            if args.format != Format::Text {
+                continue;
+            }
+
+            match outcome {
+                // an example is checked on the spot, the real input is for `aoc status`
+                Outcome::Solved(answer) if example.is_some() => writeln!(
+                    out,
+                    "{} day {:>2} part {part}: {answer:<20} {elapsed:>12.2?}  {}",
+                    year.year,
+                    day.day,
+                    compare(&answer, expected)
+                )?,
+                Outcome::Solved(answer) => writeln!(
+                    out,
+                    "{} day {:>2} part {part}: {answer:<20} {elapsed:>12.2?}",
+                    year.year, day.day
+                )?,
+                Outcome::Failed(err) => writeln!(
+                    out,
+                    "{} day {:>2} part {part}: error: {err:#}",
+                    year.year, day.day
+                )?,
+                Outcome::Panicked(message) => writeln!(
+                    out,
+                    "{} day {:>2} part {part}: panicked: {message}",
+                    year.year, day.day
+                )?,
+                Outcome::TimedOut => writeln!(
+                    out,
+                    "{} day {:>2} part {part}: timed out after {timeout:?}",
+                    year.year, day.day
+                )?,
+            }
+            if let (true, Some(seed)) = (failed, seed) {
+                writeln!(
+                    out,
+                    "{} day {:>2} part {part}: seed {seed}, pass `--seed {seed}` to repeat the run",
+                    year.year, day.day
+                )?;
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+// one part on the real input, with the same parameters and timeout as `run`
+pub fn answer(year: &Year, day: &Day, part: u8) -> Result<String> {
+    let manifest = Manifest::load(&year.manifest_path())?;
+    let params = params(day, &manifest, &Params::new())?;
+    let input = year
+        .read_input(day.day)?
+        .ok_or_else(|| anyhow!("no input at {}", year.input_path(day.day).display()))?;
+    let timeout = manifest.timeout(day.day).unwrap_or(DEFAULT_TIMEOUT);
+
+    match solve(day, part, &Arc::from(input), &params, timeout) {
+        Outcome::Solved(answer) => Ok(answer),
+        Outcome::Failed(err) => Err(err),
+        Outcome::Panicked(message) => Err(anyhow!("panicked: {message}")),
+        Outcome::TimedOut => Err(anyhow!("timed out after {timeout:?}")),
+    }
+}
+
+// the day's defaults for the real input, then the manifest, then the command line
+pub fn params(day: &Day, manifest: &Manifest, overrides: &Params) -> Result<Params> {
+    (day.params)()
+        .overridden_by(&manifest.params(day.day)?)?
+        .overridden_by(overrides)
+}
+
+// the day's defaults for the examples, then the example's in the manifest, then the command
+// line
+pub fn example_params(
+    day: &Day,
+    manifest: &Manifest,
+    input: &str,
+    overrides: &Params,
+) -> Result<Params> {
+    let example = match manifest.example(day.day, input) {
+        Some(example) => example.params()?,
+        None => Params::new(),
+    };
+    (day.example_params)()
+        .overridden_by(&example)?
+        .overridden_by(overrides)
+}
+
+// `seed` replaces the one of a day that uses randomness, and means nothing to the others
+pub fn with_seed(mut params: Params, seed: Option<u64>) -> Params {
+    if let Some(seed) = seed.filter(|_| params.contains(SEED)) {
+        params.set(SEED, seed);
+    }
+    params
+}
+
+pub fn seed(params: &Params) -> Result<Option<u64>> {
+    params.contains(SEED).then(|| params.get(SEED)).transpose()
+}
+
+fn compare(answer: &str, expected: Option<&str>) -> String {
+    match expected {
+        Some(expected) if expected == answer => format!("expected {expected}, matches"),
+        Some(expected) => format!("expected {expected}, differs"),
+        None => "no expected answer in the manifest".to_string(),
+    }
+}
+
+// region:    --- Solve
+
+/// A day's input parsed once for its parts, or how parsing it went wrong.
+pub struct Parsed {
+    outcome: Outcome<Model>,
+    pub elapsed: Duration,
+}
+
+impl Parsed {
+    // on its own thread like a part, under the same timeout
+    pub fn new(day: &Day, input: &Arc<str>, params: &Params, timeout: Duration) -> Self {
+        let (day, input, job_params) = (*day, Arc::clone(input), params.clone());
+        let start = Instant::now();
+        let outcome = isolate(move || day.parse(&input, &job_params), timeout);
+
+        Self {
+            outcome,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    // a part on the model, a failed parse fails every part the same way
+    pub fn solve(
+        &self,
+        day: &Day,
+        part: u8,
+        params: &Params,
+        timeout: Duration,
+    ) -> (Outcome, Duration) {
+        let model = match &self.outcome {
+            Outcome::Solved(model) => Arc::clone(model),
+            Outcome::Failed(err) => {
+                let err = anyhow!("parsing the input: {err:#}");
+                return (Outcome::Failed(err), Duration::ZERO);
+            }
+            Outcome::Panicked(message) => {
+                return (Outcome::Panicked(message.clone()), Duration::ZERO)
+            }
+            Outcome::TimedOut => return (Outcome::TimedOut, Duration::ZERO),
+        };
+
+        let (day, params) = (*day, params.clone());
+        let start = Instant::now();
+        let outcome = isolate(move || day.solve(part, &model, &params), timeout);
+
+        (outcome, start.elapsed())
+    }
+}
+
+// one part of a day, parsing included, for the callers that only want its outcome
+pub fn solve(day: &Day, part: u8, input: &Arc<str>, params: &Params, timeout: Duration) -> Outcome {
+    Parsed::new(day, input, params, timeout)
+        .solve(day, part, params, timeout)
+        .0
+}
+
+// runs on its own thread, so a panicking part is reported like any other error and a part
+// that outlives its timeout can be cancelled, or abandoned if it never checks for it
+pub(crate) fn isolate<T: Send + 'static>(
+    job: impl FnOnce() -> Result<T> + Send + 'static,
+    timeout: Duration,
+) -> Outcome<T> {
+    let token = Token::new();
+    let (sender, receiver) = mpsc::channel();
+
+    let job_token = token.clone();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result =
+                panic::catch_unwind(AssertUnwindSafe(|| cancel::with_token(job_token, job)));
+            // the runner stops listening to an abandoned part
+            let _ = sender.send(result);
+        })
+        .expect("solver thread spawned");
+
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(Ok(answer))) => Outcome::Solved(answer),
+        Ok(Ok(Err(err))) => Outcome::Failed(err),
+        Ok(Err(payload)) => Outcome::Panicked(panic_message(payload)),
+        // a panic that could not be caught, e.g. while unwinding
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panicked("no result".to_string()),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            let _ = receiver.recv_timeout(GRACE_PERIOD);
+            Outcome::TimedOut
+        }
+    }
+}
+
+// what was given to `panic!`, which is almost always a message
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or_else(
+            || "unknown payload".to_string(),
+            |message| message.to_string(),
+        ),
+    }
+}
+
+// endregion: --- Solve
+
+#[cfg(test)]
+mod tests {
+    use crate::day::model;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    // the input itself as the model
+    fn text(input: &str, _: &Params) -> Result<Model> {
+        Ok(Arc::new(input.to_string()))
+    }
+
+    fn spin(_: &Model, _: &Params) -> Result<String> {
+        loop {
+            cancel::check()?;
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    fn answer(text: &Model, _: &Params) -> Result<String> {
+        Ok(model::<String>(text)?.len().to_string())
+    }
+
+    fn crash(_: &Model, _: &Params) -> Result<String> {
+        panic!("crashed")
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = Arc::<str>::from("input");
+        let params = Params::new();
+        // parts that finish get plenty of time, only the spinning one is meant to run out
+        let timeout = Duration::from_secs(10);
+        let day = Day::parsed(1, text, answer, crash);
+
+        assert!(matches!(
+            solve(&day, 1, &input, &params, timeout),
+            Outcome::Solved(answer) if answer == "5"
+        ));
+        assert!(matches!(
+            solve(&day, 2, &input, &params, timeout),
+            Outcome::Panicked(message) if message == "crashed"
+        ));
+        assert!(matches!(
+            solve(
+                &Day::parsed(1, text, spin, spin),
+                1,
+                &input,
+                &params,
+                Duration::from_millis(50)
+            ),
+            Outcome::TimedOut
+        ));
+    }
+
+    // the numbers of the input, parsed once for both parts
+    fn numbers(input: &str, _: &Params) -> Result<Model> {
+        let numbers = input
+            .split(',')
+            .map(|number| Ok(number.parse()?))
+            .collect::<Result<Vec<u64>>>()?;
+        Ok(Arc::new(numbers))
+    }
+
+    #[test]
+    fn test_parsed() {
+        let params = Params::new();
+        let timeout = Duration::from_secs(10);
+        let day = Day::parsed(
+            1,
+            numbers,
+            |numbers, _| Ok(model::<Vec<u64>>(numbers)?.iter().sum::<u64>().to_string()),
+            |numbers, _| Ok(model::<Vec<u64>>(numbers)?.len().to_string()),
+        );
+
+        let parsed = Parsed::new(&day, &Arc::from("1,2,3"), &params, timeout);
+        assert!(matches!(
+            parsed.solve(&day, 1, &params, timeout).0,
+            Outcome::Solved(answer) if answer == "6"
+        ));
+        assert!(matches!(
+            parsed.solve(&day, 2, &params, timeout).0,
+            Outcome::Solved(answer) if answer == "3"
+        ));
+
+        let parsed = Parsed::new(&day, &Arc::from("1,x"), &params, timeout);
+        for part in [1, 2] {
+            assert!(matches!(
+                parsed.solve(&day, part, &params, timeout).0,
+                Outcome::Failed(err) if err.to_string().starts_with("parsing the input: ")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_example_params() {
+        let day = Day::parsed(24, text, answer, answer).with_params(
+            || {
+                Params::new()
+                    .with("window_start", 200)
+                    .with("window_end", 400)
+            },
+            || Params::new().with("window_start", 1).with("window_end", 30),
+        );
+        let manifest = Manifest::parse(
+            "[[day-24.examples]]\ninput = \"test-1.txt\"\nparams = { window_start = 7 }",
+        )
+        .unwrap();
+        let overrides = Params::new().with("window_end", 27);
+
+        let params = example_params(&day, &manifest, "test-1.txt", &overrides).unwrap();
+        assert_eq!(7, params.get::<u64>("window_start").unwrap());
+        assert_eq!(27, params.get::<u64>("window_end").unwrap());
+        let params = example_params(&day, &manifest, "test-2.txt", &Params::new()).unwrap();
+        assert_eq!(1, params.get::<u64>("window_start").unwrap());
+
+        assert_eq!("expected 2, matches", compare("2", Some("2")));
+        assert_eq!("expected 2, differs", compare("3", Some("2")));
+    }
+
+    #[test]
+    fn test_with_seed() {
+        let seeded = Params::new().with(SEED, 2023);
+
+        assert_eq!(Some(7), seed(&with_seed(seeded.clone(), Some(7))).unwrap());
+        assert_eq!(Some(2023), seed(&with_seed(seeded, None)).unwrap());
+        assert_eq!(None, seed(&with_seed(Params::new(), Some(7))).unwrap());
+    }
+}
@@ -0,0 +1,334 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{
+    cli::NewArgs,
+    day::{select_year, Year},
+    manifest::{self, Example},
+};
+
+// region:    --- Template
+
+// `template/day`, a cargo-generate template, as (path in the crate, content)
+const TEMPLATE: [(&str, &str); 7] = [
+    (
+        "Cargo.toml",
+        include_str!("../../../template/day/Cargo.toml"),
+    ),
+    (
+        "src/lib.rs",
+        include_str!("../../../template/day/src/lib.rs"),
+    ),
+    (
+        "src/bin/main.rs",
+        include_str!("../../../template/day/src/bin/main.rs"),
+    ),
+    (
+        "src/bin/part1.rs",
+        include_str!("../../../template/day/src/bin/part1.rs"),
+    ),
+    (
+        "src/bin/part2.rs",
+        include_str!("../../../template/day/src/bin/part2.rs"),
+    ),
+    // placeholders, the binaries need an input to build
+    ("input.txt", include_str!("../../../template/day/input.txt")),
+    (
+        "test-1.txt",
+        include_str!("../../../template/day/test-1.txt"),
+    ),
+];
+
+fn render(template: &str, package: &str) -> String {
+    template
+        .replace("{{project-name}}", package)
+        .replace("{{crate_name}}", &package.replace('-', "_"))
+}
+
+// endregion: --- Template
+
+pub fn new(years: &[Year], args: &NewArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+
+    new_day(year, args.day)?;
+
+    println!(
+        "{} day {:>2}: created {}, rebuild the runner to use it",
+        year.year,
+        args.day,
+        year.day_dir(args.day).display()
+    );
+
+    Ok(())
+}
+
+// the crate, its entry in the runner and an example in the manifest, all checked for an
+// existing day before anything is written
+fn new_day(year: &Year, day: u8) -> Result<()> {
+    let dir = year.day_dir(day);
+    let registry_path = year.registry_path();
+    let runner_manifest_path = year.runner_manifest_path();
+
+    if year.day(day).is_some() || dir.exists() {
+        return Err(anyhow!("day {day} of {} already exists", year.year));
+    }
+    let registry = add_to_registry(&read(&registry_path)?, day)?;
+    let runner_manifest = read(&runner_manifest_path)?;
+    let package = format!("{}day-{day}", package_prefix(&runner_manifest));
+    let runner_manifest = add_dependency(&runner_manifest, day, &package)?;
+
+    for (path, template) in TEMPLATE {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().expect("template files are in the crate"))?;
+        fs::write(&path, render(template, &package))
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    fs::write(&registry_path, registry)?;
+    fs::write(&runner_manifest_path, runner_manifest)?;
+
+    let example = Example {
+        input: "test-1.txt".to_string(),
+        ..Example::default()
+    };
+    manifest::write_examples(&year.manifest_path(), day, &[example])
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+}
+
+// `day!(N, day_N::Puzzle),` in order among the days of `pub fn days()`
+fn add_to_registry(registry: &str, day: u8) -> Result<String> {
+    let lines = registry.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "vec![")
+        .ok_or_else(|| anyhow!("no `vec![` of days in the registry"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "]")
+            .ok_or_else(|| anyhow!("the `vec![` of days is not closed"))?;
+    let indent = lines[start].len() - lines[start].trim_start().len() + 4;
+
+    // entries start at the indentation of the vec, a `Day::parsed(` has its day on the next line
+    let mut insert_at = end;
+    for idx in start + 1..end {
+        let line = lines[idx];
+        if line.len() - line.trim_start().len() != indent {
+            continue;
+        }
+        let number = if let Some(rest) = line.trim().strip_prefix("day!(") {
+            rest.split(',').next()
+        } else if line.trim() == "Day::parsed(" {
+            lines
+                .get(idx + 1)
+                .and_then(|next| next.trim().split(',').next())
+        } else {
+            None
+        };
+
+        match number.and_then(|number| number.trim().parse::<u8>().ok()) {
+            Some(number) if number == day => {
+                return Err(anyhow!("day {day} is already in the registry"))
+            }
+            Some(number) if number > day => {
+                insert_at = idx;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let entry = format!("{}day!({day}, day_{day}::Puzzle),", " ".repeat(indent));
+    let mut lines = lines.into_iter().map(str::to_string).collect::<Vec<_>>();
+    lines.insert(insert_at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Packages are unique across the workspace, so the days of all but one year have their
+// package named with a prefix, `day-13 = { package = "y2015-day-13", path = "../day-13" }`.
+fn package_prefix(runner_manifest: &str) -> &str {
+    runner_manifest
+        .lines()
+        .filter(|line| line.starts_with("day-"))
+        .find_map(|line| {
+            let (_, package) = line.split_once("package = \"")?;
+            let (package, _) = package.split_once('"')?;
+            package.rfind("day-").map(|at| &package[..at])
+        })
+        .unwrap_or_default()
+}
+
+// `day-N = { path = "../day-N" }` in order among the other days of the runner's dependencies
+fn add_dependency(runner_manifest: &str, day: u8, package: &str) -> Result<String> {
+    let lines = runner_manifest.lines().collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or_else(|| anyhow!("no [dependencies] in the runner's Cargo.toml"))?;
+
+    let mut insert_at = None;
+    let mut last_dependency = start;
+    for (idx, line) in lines.iter().enumerate().skip(start + 1) {
+        if line.starts_with('[') {
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        last_dependency = idx;
+
+        let number = line
+            .strip_prefix("day-")
+            .and_then(|rest| rest.split([' ', '=']).next())
+            .and_then(|number| number.parse::<u8>().ok());
+        match number {
+            Some(number) if number == day => {
+                return Err(anyhow!("day-{day} is already a dependency of the runner"))
+            }
+            Some(number) if number > day && insert_at.is_none() => insert_at = Some(idx),
+            _ => {}
+        }
+    }
+
+    let entry = match package.strip_prefix("day-") {
+        Some(_) => format!("day-{day} = {{ path = \"../day-{day}\" }}"),
+        None => format!("day-{day} = {{ package = \"{package}\", path = \"../day-{day}\" }}"),
+    };
+    let mut lines = lines.into_iter().map(str::to_string).collect::<Vec<_>>();
+    lines.insert(insert_at.unwrap_or(last_dependency + 1), entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::manifest::Manifest;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    const REGISTRY: &str = "use aoc_runner::Day;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        day!(1, day_1::Trebuchet),
+        Day::parsed(
+            11,
+            |input, _| Ok(Arc::new(day_11::parse(input.as_bytes())?)),
+            |parsed, _| Ok(model::<day_11::Universe>(parsed)?.part1()?.to_string()),
+            |parsed, params| Ok(model::<day_11::Universe>(parsed)?.part2(params)?.to_string()),
+        )
+        .with_params(day_11::params, day_11::example_params),
+        day!(13, day_13::Patterns, bytes),
+    ]
+}
+";
+
+    const RUNNER_MANIFEST: &str = r#"[package]
+name = "aoc"
+
+[dependencies]
+anyhow.workspace = true
+aoc-runner.workspace = true
+day-1 = { path = "../day-1" }
+day-11 = { path = "../day-11" }
+day-13 = { path = "../day-13" }
+
+[features]
+parallel = ["day-13/parallel"]
+"#;
+
+    #[rstest]
+    #[case(
+        5,
+        "        day!(1, day_1::Trebuchet),\n        day!(5, day_5::Puzzle),\n        Day::parsed(\n"
+    )]
+    #[case(12, "        .with_params(day_11::params, day_11::example_params),\n        day!(12, day_12::Puzzle),\n        day!(13, day_13::Patterns, bytes),\n")]
+    #[case(
+        25,
+        "        day!(13, day_13::Patterns, bytes),\n        day!(25, day_25::Puzzle),\n    ]\n"
+    )]
+    fn test_add_to_registry(#[case] day: u8, #[case] expected: &str) {
+        let registry = add_to_registry(REGISTRY, day).unwrap();
+
+        assert!(registry.contains(expected), "{registry}");
+    }
+
+    #[rstest]
+    #[case(
+        5,
+        "day-1 = { path = \"../day-1\" }\nday-5 = { path = \"../day-5\" }\nday-11"
+    )]
+    #[case(
+        25,
+        "day-13 = { path = \"../day-13\" }\nday-25 = { path = \"../day-25\" }\n\n[features]"
+    )]
+    fn test_add_dependency(#[case] day: u8, #[case] expected: &str) {
+        let runner_manifest = add_dependency(RUNNER_MANIFEST, day, &format!("day-{day}")).unwrap();
+
+        assert!(runner_manifest.contains(expected), "{runner_manifest}");
+    }
+
+    #[test]
+    fn test_package_prefix() {
+        let runner_manifest = RUNNER_MANIFEST.replace(
+            "day-11 = { path = \"../day-11\" }",
+            "day-11 = { package = \"y2015-day-11\", path = \"../day-11\" }",
+        );
+
+        assert_eq!("", package_prefix(RUNNER_MANIFEST));
+        assert_eq!("y2015-", package_prefix(&runner_manifest));
+        assert!(add_dependency(&runner_manifest, 12, "y2015-day-12")
+            .unwrap()
+            .contains("day-12 = { package = \"y2015-day-12\", path = \"../day-12\" }\n"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("aoc/src/registry.rs"), REGISTRY).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), RUNNER_MANIFEST).unwrap();
+        let year = Year {
+            year: 2023,
+            root: root.to_path_buf(),
+            days: vec![],
+        };
+
+        new_day(&year, 7).unwrap();
+
+        let cargo_toml = fs::read_to_string(root.join("day-7/Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"day-7\""));
+        let main = fs::read_to_string(root.join("day-7/src/bin/main.rs")).unwrap();
+        assert!(main.starts_with("use day_7::part1;"));
+        assert!(root.join("day-7/test-1.txt").exists());
+        assert!(fs::read_to_string(root.join("aoc/src/registry.rs"))
+            .unwrap()
+            .contains("day!(7, day_7::Puzzle),"));
+        assert_eq!(
+            "test-1.txt",
+            Manifest::load(&root.join("manifest.toml"))
+                .unwrap()
+                .examples(7)[0]
+                .input
+        );
+
+        // nothing is overwritten
+        fs::write(root.join("day-7/test-1.txt"), "example").unwrap();
+        assert!(new_day(&year, 7).is_err());
+        assert_eq!(
+            "example",
+            fs::read_to_string(root.join("day-7/test-1.txt")).unwrap()
+        );
+        assert!(new_day(&year, 13).is_err());
+    }
+}
@@ -0,0 +1,289 @@
+use std::{fmt::Write as _, fs, io, sync::Arc, time::Duration};
+
+use anyhow::{Context, Error, Result};
+use aoc_core::Params;
+
+use crate::{
+    cli::{Format, StatusArgs},
+    day::{select_years, Year},
+    manifest::Manifest,
+    record::{self, Record, RecordStatus, NOT_IMPLEMENTED},
+    run::{self, Outcome, Parsed, DEFAULT_TIMEOUT},
+    table::{Align, Table},
+};
+
+// region:    --- Status
+
+/// Where a part stands, from not having a crate at all to matching its answer in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Missing,
+    NoInput,
+    Unimplemented,
+    Failing(String),
+    // answered, but the manifest has no answer to check it against
+    Solved(String),
+    Verified,
+}
+
+impl Status {
+    // how the outcome on the real input compares to the accepted answer
+    fn judge(outcome: Outcome, expected: Option<&str>, timeout: Duration) -> Self {
+        match (outcome, expected) {
+            (Outcome::Solved(answer), Some(expected)) if answer == expected => Status::Verified,
+            (Outcome::Solved(answer), Some(expected)) => {
+                Status::Failing(format!("answered {answer}, expected {expected}"))
+            }
+            (Outcome::Solved(answer), None) => Status::Solved(answer),
+            (Outcome::Failed(err), _) if err.to_string() == NOT_IMPLEMENTED => {
+                Status::Unimplemented
+            }
+            (Outcome::Failed(err), _) => Status::Failing(format!("{err:#}")),
+            (Outcome::Panicked(message), _) => Status::Failing(format!("panicked: {message}")),
+            (Outcome::TimedOut, _) => Status::Failing(format!("timed out after {timeout:?}")),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Missing => "missing",
+            Status::NoInput => "no input",
+            Status::Unimplemented => "unimplemented",
+            Status::Failing(_) => "failing",
+            Status::Solved(_) => "solved",
+            Status::Verified => "verified",
+        }
+    }
+}
+
+pub struct PartStatus {
+    pub status: Status,
+    // only for the parts that were run
+    pub elapsed: Option<Duration>,
+}
+
+impl PartStatus {
+    fn new(status: Status) -> Self {
+        Self {
+            status,
+            elapsed: None,
+        }
+    }
+}
+
+// endregion: --- Status
+
+pub fn status(years: &[Year], args: &StatusArgs) -> Result<()> {
+    let mut markdown = String::new();
+    let mut records = vec![];
+
+    for year in select_years(years, args.year)? {
+        let days = year_status(year, args.timeout.map(Duration::from_secs), &mut |record| {
+            if args.format == Format::Jsonl {
+                record::write_line(&mut io::stdout(), &record)?;
+            }
+            records.push(record);
+            Ok(())
+        })?;
+
+        if args.format == Format::Text {
+            println!("{}", terminal_table(year.year, &days));
+            println!("{}\n", summary(year.year, &days));
+        }
+        writeln!(markdown, "## {}\n\n{}", year.year, table(&days).markdown())?;
+    }
+
+    if args.format == Format::Json {
+        record::write_all(&mut io::stdout(), &records)?;
+    }
+
+    if let Some(path) = &args.markdown {
+        fs::write(path, markdown).with_context(|| format!("writing {}", path.display()))?;
+        println!("status written to {}", path.display());
+    }
+
+    Ok(())
+}
+
+// all 25 days, running both parts of every registered day with an input, with the record
+// of each part given to `on_record` as soon as it is known
+fn year_status(
+    year: &Year,
+    timeout: Option<Duration>,
+    on_record: &mut impl FnMut(Record) -> Result<()>,
+) -> Result<Vec<[PartStatus; 2]>> {
+    let manifest = Manifest::load(&year.manifest_path())?;
+
+    let mut days = vec![];
+    for number in 1..=25 {
+        let mut not_run = |status: Status, record_status| {
+            for part in [1, 2] {
+                let record = Record::new(year.year, number, part, record_status)
+                    .with_expected(manifest.answer(number, part));
+                on_record(record)?;
+            }
+            Ok::<_, Error>([status.clone(), status].map(PartStatus::new))
+        };
+        let Some(day) = year.day(number) else {
+            days.push(not_run(Status::Missing, RecordStatus::Missing)?);
+            continue;
+        };
+        let Some(input) = year.read_input(number)?.map(Arc::<str>::from) else {
+            days.push(not_run(Status::NoInput, RecordStatus::NoInput)?);
+            continue;
+        };
+        let params = run::params(day, &manifest, &Params::new())?;
+        let timeout = timeout
+            .or(manifest.timeout(number))
+            .unwrap_or(DEFAULT_TIMEOUT);
+
+        let parsed = Parsed::new(day, &input, &params, timeout);
+        let mut solve = |part| {
+            let (outcome, elapsed) = parsed.solve(day, part, &params, timeout);
+
+            let expected = manifest.answer(number, part);
+            on_record(
+                Record::solved((year.year, number, part), &outcome, elapsed, timeout)
+                    .with_expected(expected)
+                    .with_parse(Some(parsed.elapsed)),
+            )?;
+            Ok::<_, Error>(PartStatus {
+                status: Status::judge(outcome, expected, timeout),
+                elapsed: Some(elapsed),
+            })
+        };
+        days.push([solve(1)?, solve(2)?]);
+    }
+
+    Ok(days)
+}
+
+// region:    --- Report
+
+fn table(days: &[[PartStatus; 2]]) -> Table {
+    let mut table = Table::new(&[
+        ("day", Align::Right),
+        ("part 1", Align::Left),
+        ("time", Align::Right),
+        ("part 2", Align::Left),
+        ("time", Align::Right),
+    ]);
+    for (idx, parts) in days.iter().enumerate() {
+        let mut row = vec![(idx + 1).to_string()];
+        for part in parts {
+            row.push(part.status.label().to_string());
+            row.push(
+                part.elapsed
+                    .map_or(String::new(), |elapsed| format!("{elapsed:.2?}")),
+            );
+        }
+        table.push(row);
+    }
+
+    table
+}
+
+fn terminal_table(year: u16, days: &[[PartStatus; 2]]) -> String {
+    let mut table = format!("{year}\n{}", table(days).terminal());
+
+    // why the failing parts fail, below the table
+    for (idx, parts) in days.iter().enumerate() {
+        for (part, status) in parts.iter().enumerate() {
+            if let Status::Failing(reason) = &status.status {
+                writeln!(table, "day {:>2} part {}: {reason}", idx + 1, part + 1)
+                    .expect("writing to a string");
+            }
+        }
+    }
+
+    table
+}
+
+// how many parts are in each status
+fn summary(year: u16, days: &[[PartStatus; 2]]) -> String {
+    let counts = [
+        Status::Verified,
+        Status::Solved(String::new()),
+        Status::Failing(String::new()),
+        Status::Unimplemented,
+        Status::NoInput,
+        Status::Missing,
+    ]
+    .iter()
+    .map(|status| {
+        let count = days
+            .iter()
+            .flatten()
+            .filter(|part| part.status.label() == status.label())
+            .count();
+        format!("{count} {}", status.label())
+    })
+    .collect::<Vec<_>>();
+
+    format!("{year}: {} of {} parts", counts.join(", "), days.len() * 2)
+}
+
+// endregion: --- Report
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[rstest]
+    #[case(Status::Verified, Outcome::Solved("42".into()), Some("42"))]
+    #[case(Status::Solved("42".into()), Outcome::Solved("42".into()), None)]
+    #[case(
+        Status::Failing("answered 41, expected 42".into()),
+        Outcome::Solved("41".into()),
+        Some("42")
+    )]
+    #[case(Status::Unimplemented, Outcome::Failed(anyhow!("Not Implemented.")), None)]
+    #[case(Status::Failing("no path".into()), Outcome::Failed(anyhow!("no path")), Some("42"))]
+    #[case(Status::Failing("timed out after 1s".into()), Outcome::TimedOut, None)]
+    fn test_judge(
+        #[case] expected: Status,
+        #[case] outcome: Outcome,
+        #[case] answer: Option<&str>,
+    ) {
+        assert_eq!(
+            expected,
+            Status::judge(outcome, answer, Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let days = vec![
+            [
+                PartStatus {
+                    status: Status::Verified,
+                    elapsed: Some(Duration::from_millis(2)),
+                },
+                PartStatus {
+                    status: Status::Failing("timed out after 1s".into()),
+                    elapsed: Some(Duration::from_secs(1)),
+                },
+            ],
+            [Status::Missing, Status::Missing].map(PartStatus::new),
+        ];
+
+        assert_eq!(
+            "| day | part 1 | time | part 2 | time |\n\
+             |---:|:---|---:|:---|---:|\n\
+             | 1 | verified | 2.00ms | failing | 1.00s |\n\
+             | 2 | missing |  | missing |  |\n",
+            table(&days).markdown()
+        );
+        assert!(terminal_table(2023, &days).ends_with("day  1 part 2: timed out after 1s\n"));
+        assert_eq!(
+            "2023: 1 verified, 0 solved, 1 failing, 0 unimplemented, 0 no input, 2 missing of 4 parts",
+            summary(2023, &days)
+        );
+    }
+}
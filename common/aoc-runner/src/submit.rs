@@ -0,0 +1,389 @@
+use std::{fmt, fs, io::ErrorKind, path::Path, time::Duration};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::SubmitArgs,
+    client::Client,
+    config::Config,
+    day::{select_year, Year},
+    fetch::Cache,
+    run,
+};
+
+// region:    --- Verdict
+
+/// How the site took a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(Duration),
+    // the part is solved already, or not unlocked yet
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Verdict {
+    // the message is the only `<article>` of the page
+    pub fn parse(page: &str) -> Self {
+        let text = article_text(page);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        } else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited(wait_time(&text).unwrap_or_default())
+        } else if text.contains("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else {
+            Verdict::Unknown(text)
+        }
+    }
+
+    // what the guess tells about the answer, nothing when it was not judged
+    pub fn feedback(&self) -> Option<Feedback> {
+        match self {
+            Verdict::Correct => Some(Feedback::Correct),
+            Verdict::TooHigh => Some(Feedback::TooHigh),
+            Verdict::TooLow => Some(Feedback::TooLow),
+            Verdict::Wrong => Some(Feedback::Wrong),
+            Verdict::RateLimited(_) | Verdict::WrongLevel | Verdict::Unknown(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::RateLimited(wait) => write!(f, "rate limited, try again in {wait:?}"),
+            Verdict::WrongLevel => write!(f, "not accepted, the part is solved or still locked"),
+            Verdict::Unknown(text) => write!(f, "unrecognised response: {text}"),
+            verdict => write!(f, "{}", verdict.feedback().expect("judged verdict")),
+        }
+    }
+}
+
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// "You have 1m 23s left to wait."
+fn wait_time(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    wait.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+// endregion: --- Verdict
+
+// region:    --- History
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Feedback::Correct => "correct",
+            Feedback::TooHigh => "too high",
+            Feedback::TooLow => "too low",
+            Feedback::Wrong => "wrong",
+        };
+
+        f.write_str(text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub part: u8,
+    pub answer: String,
+    pub feedback: Feedback,
+}
+
+/// Every judged answer submitted for a day, so no guess is wasted on what is already known.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "guess")]
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    // nothing submitted yet when there is no file
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                toml::from_str(&content).with_context(|| format!("in {}", path.display()))
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::create_dir_all(path.parent().expect("history path has a parent"))?;
+        fs::write(path, toml::to_string(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, feedback: Feedback) {
+        self.guesses.push(Guess {
+            part,
+            answer: answer.to_string(),
+            feedback,
+        });
+    }
+
+    // why submitting `answer` would be a wasted guess, if it would be
+    pub fn refusal(&self, part: u8, answer: &str) -> Option<String> {
+        let guesses = self.guesses.iter().filter(|guess| guess.part == part);
+        let number = answer.parse::<i128>().ok();
+
+        for guess in guesses {
+            if guess.feedback == Feedback::Correct {
+                return Some(format!(
+                    "part {part} is already solved with {}",
+                    guess.answer
+                ));
+            }
+            if guess.answer == answer {
+                return Some(format!(
+                    "{answer} was already guessed, it is {}",
+                    guess.feedback
+                ));
+            }
+
+            let bound = guess.answer.parse::<i128>().ok();
+            match (guess.feedback, number, bound) {
+                (Feedback::TooHigh, Some(number), Some(bound)) if number >= bound => {
+                    return Some(format!("{answer} is not below {bound}, which is too high"));
+                }
+                (Feedback::TooLow, Some(number), Some(bound)) if number <= bound => {
+                    return Some(format!("{answer} is not above {bound}, which is too low"));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+// endregion: --- History
+
+pub fn submit(years: &[Year], args: &SubmitArgs) -> Result<()> {
+    let year = select_year(years, args.year)?;
+    let day = year
+        .day(args.day)
+        .ok_or_else(|| anyhow!("day {} of {} is not registered", args.day, year.year))?;
+
+    let answer = match &args.answer {
+        Some(answer) => answer.trim().to_string(),
+        None => run::answer(year, day, args.part)
+            .with_context(|| format!("solving {} day {} part {}", year.year, day.day, args.part))?,
+    };
+
+    let config = Config::load()?;
+    let cache = Cache::new(&config.cache_dir()?);
+    let verdict = submit_guess(
+        &cache.guesses_path(year.year, day.day),
+        year.year,
+        day.day,
+        args.part,
+        &answer,
+        || Ok(Client::new(config.base_url(), config.session()?)),
+    )?;
+
+    println!(
+        "{} day {:>2} part {}: {answer}, {verdict}",
+        year.year, day.day, args.part
+    );
+
+    Ok(())
+}
+
+// checked against the history of the day before anything is sent
+fn submit_guess(
+    history_path: &Path,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+    client: impl FnOnce() -> Result<Client>,
+) -> Result<Verdict> {
+    let mut history = History::load(history_path)?;
+    if let Some(reason) = history.refusal(part, answer) {
+        return Err(anyhow!("not submitted, {reason}"));
+    }
+
+    let verdict = Verdict::parse(&client()?.submit(year, day, part, answer)?);
+    if let Some(feedback) = verdict.feedback() {
+        history.record(part, answer, feedback);
+        history.save(history_path)?;
+    }
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::mock::{self, Seen};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[rstest]
+    #[case(
+        Verdict::Correct,
+        "That's the right answer! You are <em>one gold star</em> closer."
+    )]
+    #[case(
+        Verdict::TooHigh,
+        "That's not the right answer; your answer is too high. Please wait one minute."
+    )]
+    #[case(
+        Verdict::TooLow,
+        "That's not the right answer; your answer is too low."
+    )]
+    #[case(
+        Verdict::Wrong,
+        "That's not the right answer. If you're stuck, make sure you're using the full input."
+    )]
+    #[case(
+        Verdict::RateLimited(Duration::from_secs(83)),
+        "You gave an answer too recently. You have 1m 23s left to wait."
+    )]
+    #[case(
+        Verdict::RateLimited(Duration::from_secs(9)),
+        "You gave an answer too recently. You have 9s left to wait."
+    )]
+    #[case(
+        Verdict::WrongLevel,
+        "You don't seem to be solving the right level. Did you already complete it?"
+    )]
+    #[case(Verdict::Unknown("Something else".into()), "Something else")]
+    fn test_parse_verdict(#[case] expected: Verdict, #[case] message: &str) {
+        assert_eq!(expected, Verdict::parse(&page(message)));
+    }
+
+    #[rstest]
+    #[case(None, 2, "150")]
+    #[case(None, 2, "199")]
+    #[case(Some("already guessed"), 2, "120")]
+    #[case(Some("not below 200"), 2, "250")]
+    #[case(Some("not above 100"), 2, "99")]
+    #[case(Some("already solved"), 1, "43")]
+    fn test_refusal(#[case] expected: Option<&str>, #[case] part: u8, #[case] answer: &str) {
+        let mut history = History::default();
+        history.record(1, "42", Feedback::Correct);
+        history.record(2, "100", Feedback::TooLow);
+        history.record(2, "200", Feedback::TooHigh);
+        history.record(2, "120", Feedback::Wrong);
+
+        let refusal = history.refusal(part, answer);
+        match expected {
+            Some(reason) => assert!(refusal.expect("refused").contains(reason)),
+            None => assert_eq!(None, refusal),
+        }
+    }
+
+    #[test]
+    fn test_submit_guess() {
+        let mut waited = false;
+        let (base_url, server) = mock::serve(3, move |request: &Seen| {
+            let message = match request.body.as_str() {
+                "level=2&answer=300" => "That's not the right answer; your answer is too high.",
+                "level=2&answer=250" if !waited => {
+                    waited = true;
+                    "You gave an answer too recently. You have 30s left to wait."
+                }
+                _ => "That's the right answer!",
+            };
+            (200, page(message))
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("2023/day-20-guesses.toml");
+        let client = || Ok(Client::new(&base_url, "secret"));
+
+        let verdict = |answer| submit_guess(&path, 2023, 20, 2, answer, client);
+        assert_eq!(Verdict::TooHigh, verdict("300").unwrap());
+        // refused without asking the server
+        assert!(verdict("301").is_err());
+        // a rate limited guess says nothing about the answer, so it can be sent again
+        assert_eq!(
+            Verdict::RateLimited(Duration::from_secs(30)),
+            verdict("250").unwrap()
+        );
+        assert_eq!(Verdict::Correct, verdict("250").unwrap());
+
+        let seen = server.join().unwrap();
+        assert_eq!(3, seen.len());
+        assert!(seen.iter().all(|request| request.method == "POST"
+            && request.url == "/2023/day/20/answer"
+            && request.cookie == "session=secret"));
+        assert_eq!(
+            vec![Feedback::TooHigh, Feedback::Correct],
+            History::load(&path)
+                .unwrap()
+                .guesses
+                .iter()
+                .map(|guess| guess.feedback)
+                .collect::<Vec<_>>()
+        );
+    }
+}
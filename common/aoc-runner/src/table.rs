@@ -0,0 +1,121 @@
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Rows of text under titled columns, shown padded in the terminal or as a Markdown table.
+#[derive(Debug, Default)]
+pub struct Table {
+    columns: Vec<(String, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        Self {
+            columns: columns
+                .iter()
+                .map(|(title, align)| (title.to_string(), *align))
+                .collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn push(&mut self, row: Vec<String>) {
+        debug_assert_eq!(self.columns.len(), row.len(), "a cell per column");
+        self.rows.push(row);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn terminal(&self) -> String {
+        let widths = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, (title, _))| {
+                self.rows
+                    .iter()
+                    .map(|row| row[column].chars().count())
+                    .fold(title.chars().count(), usize::max)
+            })
+            .collect::<Vec<_>>();
+
+        let header = self
+            .columns
+            .iter()
+            .map(|(title, _)| title.clone())
+            .collect::<Vec<_>>();
+
+        let mut table = String::new();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let line = row
+                .iter()
+                .zip(&self.columns)
+                .zip(&widths)
+                .map(|((cell, (_, align)), &width)| match align {
+                    Align::Left => format!("{cell:<width$}"),
+                    Align::Right => format!("{cell:>width$}"),
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(table, "{}", line.trim_end()).expect("writing to a string");
+        }
+
+        table
+    }
+
+    pub fn markdown(&self) -> String {
+        let titles = self
+            .columns
+            .iter()
+            .map(|(title, _)| title.as_str())
+            .collect::<Vec<_>>();
+        let aligns = self
+            .columns
+            .iter()
+            .map(|(_, align)| match align {
+                Align::Left => ":---",
+                Align::Right => "---:",
+            })
+            .collect::<Vec<_>>();
+
+        let mut table = format!("| {} |\n|{}|\n", titles.join(" | "), aligns.join("|"));
+        for row in &self.rows {
+            // a `|` in a cell would end it early
+            let cells = row
+                .iter()
+                .map(|cell| cell.replace('|', "\\|"))
+                .collect::<Vec<_>>();
+            writeln!(table, "| {} |", cells.join(" | ")).expect("writing to a string");
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_table() {
+        let mut table = Table::new(&[("day", Align::Right), ("name", Align::Left)]);
+        table.push(vec!["1".into(), "a|b".into()]);
+        table.push(vec!["12".into(), "".into()]);
+
+        assert_eq!("day  name\n  1  a|b\n 12\n", table.terminal());
+        assert_eq!(
+            "| day | name |\n|---:|:---|\n| 1 | a\\|b |\n| 12 |  |\n",
+            table.markdown()
+        );
+    }
+}
@@ -0,0 +1,67 @@
+// Records a 2015 day 18 style light grid (Conway's life with the four corners stuck on).
+//
+//     cargo run -p aoc-visual --example life -- life.gif
+
+use aoc_visual::{Animation, Frame, Palette};
+
+const SIZE: usize = 100;
+const STEPS: usize = 100;
+
+fn main() -> anyhow::Result<()> {
+    let path = std::env::args().nth(1).unwrap_or("life.gif".to_string());
+
+    // deterministic starting pattern, no need for a random crate here
+    let mut seed: u32 = 2015;
+    let mut lights = vec![vec![false; SIZE]; SIZE];
+    for row in lights.iter_mut() {
+        for light in row.iter_mut() {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            *light = (seed >> 16).is_multiple_of(3);
+        }
+    }
+    stick_corners(&mut lights);
+
+    let palette = Palette::new(&[[16, 16, 40], [255, 220, 90]])?;
+    let mut animation = Animation::new(palette).delay(8);
+    for _ in 0..STEPS {
+        animation.push(Frame::from_rows(&lights, |&on| on as u8).scaled(4));
+        lights = step(&lights);
+        stick_corners(&mut lights);
+    }
+    animation.push_with_delay(Frame::from_rows(&lights, |&on| on as u8).scaled(4), 200);
+
+    animation.save(&path)?;
+    println!("{path}");
+
+    Ok(())
+}
+
+fn step(lights: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    (0..SIZE)
+        .map(|row| {
+            (0..SIZE)
+                .map(|col| {
+                    matches!(
+                        (lights[row][col], neighbours(lights, row, col)),
+                        (true, 2) | (_, 3)
+                    )
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn neighbours(lights: &[Vec<bool>], row: usize, col: usize) -> usize {
+    let rows = row.saturating_sub(1)..=(row + 1).min(SIZE - 1);
+    let cols = col.saturating_sub(1)..=(col + 1).min(SIZE - 1);
+
+    rows.flat_map(|nbr_row| cols.clone().map(move |nbr_col| (nbr_row, nbr_col)))
+        .filter(|&(nbr_row, nbr_col)| (nbr_row, nbr_col) != (row, col) && lights[nbr_row][nbr_col])
+        .count()
+}
+
+fn stick_corners(lights: &mut [Vec<bool>]) {
+    for (row, col) in [(0, 0), (0, SIZE - 1), (SIZE - 1, 0), (SIZE - 1, SIZE - 1)] {
+        lights[row][col] = true;
+    }
+}
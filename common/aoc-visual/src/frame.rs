@@ -0,0 +1,90 @@
+/// A snapshot of a simulation: one palette index per cell, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> u8) -> Self {
+        let mut cells = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                cells.push(f(row, col));
+            }
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    // rows are expected to be of equal length, as every grid in the puzzles is
+    pub fn from_rows<T>(rows: &[impl AsRef<[T]>], mut f: impl FnMut(&T) -> u8) -> Self {
+        let width = rows.first().map_or(0, |row| row.as_ref().len());
+
+        Self::from_fn(width, rows.len(), |row, col| f(&rows[row].as_ref()[col]))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[u8] {
+        &self.cells
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.width + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, index: u8) {
+        self.cells[row * self.width + col] = index;
+    }
+
+    // every cell becomes a factor x factor block of pixels
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_fn(self.width * factor, self.height * factor, |row, col| {
+            self.get(row / factor, col / factor)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn from_rows_keeps_layout() {
+        let frame = Frame::from_rows(&[b"#.", b".#", b"##"], |&b| (b == b'#') as u8);
+
+        assert_eq!((2, 3), (frame.width(), frame.height()));
+        assert_eq!(&[1, 0, 0, 1, 1, 1], frame.cells());
+    }
+
+    #[rstest]
+    #[case(1, vec![1, 2, 3, 4])]
+    #[case(2, vec![1, 1, 2, 2, 1, 1, 2, 2, 3, 3, 4, 4, 3, 3, 4, 4])]
+    fn test_scaled(#[case] factor: usize, #[case] expected: Vec<u8>) {
+        let frame = Frame::from_fn(2, 2, |row, col| (row * 2 + col + 1) as u8);
+
+        assert_eq!(expected, frame.scaled(factor).cells());
+    }
+}
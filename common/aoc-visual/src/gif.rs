@@ -0,0 +1,222 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::{frame::Frame, lzw, palette::Palette};
+
+// region:    --- Encoder
+
+/// Streams frames into a looping GIF89a. All frames share the size and palette given up front.
+pub struct Encoder<W: Write> {
+    writer: W,
+    width: u16,
+    height: u16,
+    colors: usize,
+    min_code_size: u8,
+}
+
+impl<W: Write> Encoder<W> {
+    pub fn new(mut writer: W, width: usize, height: usize, palette: &Palette) -> Result<Self> {
+        let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(anyhow!("{width}x{height} is too large for a GIF"));
+        };
+        let table_bits = palette.table_bits();
+
+        writer.write_all(b"GIF89a")?;
+
+        // logical screen descriptor, followed by the global colour table
+        writer.write_all(&width.to_le_bytes())?;
+        writer.write_all(&height.to_le_bytes())?;
+        writer.write_all(&[0b1111_0000 | (table_bits - 1), 0, 0])?;
+        for idx in 0..1 << table_bits {
+            writer.write_all(palette.colors().get(idx).unwrap_or(&[0, 0, 0]))?;
+        }
+
+        // NETSCAPE2.0 application extension, loop forever
+        writer.write_all(&[0x21, 0xFF, 0x0B])?;
+        writer.write_all(b"NETSCAPE2.0")?;
+        writer.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+
+        Ok(Self {
+            writer,
+            width,
+            height,
+            colors: palette.len(),
+            min_code_size: std::cmp::max(2, table_bits),
+        })
+    }
+
+    // delay is in hundredths of a second
+    pub fn frame(&mut self, frame: &Frame, delay: u16) -> Result<()> {
+        if (frame.width(), frame.height()) != (self.width as usize, self.height as usize) {
+            return Err(anyhow!(
+                "frame is {}x{}, expected {}x{}",
+                frame.width(),
+                frame.height(),
+                self.width,
+                self.height
+            ));
+        }
+        if let Some(index) = frame
+            .cells()
+            .iter()
+            .find(|&&idx| idx as usize >= self.colors)
+        {
+            return Err(anyhow!(
+                "colour index {index} is outside of a {} colour palette",
+                self.colors
+            ));
+        }
+
+        // graphic control extension, disposal method 1 (leave the frame in place)
+        self.writer.write_all(&[0x21, 0xF9, 0x04, 0b0000_0100])?;
+        self.writer.write_all(&delay.to_le_bytes())?;
+        self.writer.write_all(&[0x00, 0x00])?;
+
+        // image descriptor covering the whole screen, no local colour table
+        self.writer.write_all(&[0x2C, 0, 0, 0, 0])?;
+        self.writer.write_all(&self.width.to_le_bytes())?;
+        self.writer.write_all(&self.height.to_le_bytes())?;
+        self.writer.write_all(&[0x00])?;
+
+        // image data, split into sub-blocks of at most 255 bytes
+        self.writer.write_all(&[self.min_code_size])?;
+        for block in lzw::compress(frame.cells(), self.min_code_size).chunks(255) {
+            self.writer.write_all(&[block.len() as u8])?;
+            self.writer.write_all(block)?;
+        }
+        self.writer.write_all(&[0x00])?;
+
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.write_all(&[0x3B])?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+// endregion: --- Encoder
+
+// region:    --- Animation
+
+/// Frames recorded from a simulation, kept until they are written out as one GIF.
+#[derive(Debug, Clone)]
+pub struct Animation {
+    palette: Palette,
+    delay: u16,
+    frames: Vec<(Frame, u16)>,
+}
+
+impl Animation {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            delay: 10,
+            frames: vec![],
+        }
+    }
+
+    // default delay between frames, in hundredths of a second
+    pub fn delay(self, delay: u16) -> Self {
+        Self { delay, ..self }
+    }
+
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push((frame, self.delay));
+    }
+
+    pub fn push_with_delay(&mut self, frame: Frame, delay: u16) {
+        self.frames.push((frame, delay));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn encode<W: Write>(&self, writer: W) -> Result<W> {
+        let Some((first, _)) = self.frames.first() else {
+            return Err(anyhow!("an animation needs at least one frame"));
+        };
+
+        let mut encoder = Encoder::new(writer, first.width(), first.height(), &self.palette)?;
+        for (frame, delay) in &self.frames {
+            encoder.frame(frame, *delay)?;
+        }
+
+        encoder.finish()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+
+        self.encode(BufWriter::new(file))?;
+
+        Ok(())
+    }
+}
+
+// endregion: --- Animation
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn palette() -> Palette {
+        Palette::new(&[[0, 0, 0], [255, 255, 255], [255, 0, 0]]).expect("valid palette")
+    }
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_layout() {
+        let mut animation = Animation::new(palette()).delay(25);
+        animation.push(Frame::from_fn(3, 2, |row, col| ((row + col) % 3) as u8));
+        animation.push_with_delay(Frame::new(3, 2), 100);
+
+        let bytes = animation.encode(vec![]).expect("encoded");
+
+        assert_eq!(b"GIF89a", &bytes[..6]);
+        // width, height, global colour table flag with 4 entries
+        assert_eq!(&[3, 0, 2, 0, 0b1111_0001], &bytes[6..11]);
+        assert_eq!(&[255, 0, 0, 0, 0, 0], &bytes[19..25]);
+        assert_eq!(b"NETSCAPE2.0", &bytes[28..39]);
+        assert_eq!(Some(&0x3B), bytes.last());
+
+        let delays: Vec<u16> = bytes
+            .windows(4)
+            .enumerate()
+            .filter(|(_, window)| window[..3] == [0x21, 0xF9, 0x04])
+            .map(|(pos, _)| u16::from_le_bytes([bytes[pos + 4], bytes[pos + 5]]))
+            .collect();
+        assert_eq!(vec![25, 100], delays);
+    }
+
+    #[rstest]
+    #[case(Frame::new(2, 2))]
+    #[case(Frame::from_fn(3, 3, |_, _| 3))]
+    fn test_invalid_frames(#[case] frame: Frame) {
+        let mut encoder = Encoder::new(vec![], 3, 3, &palette()).expect("encoder");
+
+        assert!(encoder.frame(&frame, 10).is_err());
+    }
+
+    #[test]
+    fn test_empty_animation() {
+        assert!(Animation::new(palette()).encode(vec![]).is_err());
+    }
+}
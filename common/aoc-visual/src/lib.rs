@@ -0,0 +1,10 @@
+// region:    --- Modules
+pub use frame::Frame;
+pub use gif::{Animation, Encoder};
+pub use palette::{Palette, Rgb};
+
+pub mod frame;
+pub mod gif;
+mod lzw;
+pub mod palette;
+// endregion: --- Modules
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+const MAX_CODES: u16 = 4096;
+
+// region:    --- Bit Packing
+
+// GIF packs variable width codes least significant bit first
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    filled: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: vec![],
+            buffer: 0,
+            filled: 0,
+        }
+    }
+
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.filled;
+        self.filled += width;
+
+        while self.filled >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.filled -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.filled > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+// endregion: --- Bit Packing
+
+// region:    --- Compression
+
+/// Compresses palette indices with the variable-length LZW flavour used by GIF image data.
+/// `min_code_size` is the number of bits of a palette index, at least 2.
+pub fn compress(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end_of_information = clear + 1;
+
+    let mut writer = BitWriter::new();
+    let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end_of_information + 1;
+    let mut code_size = min_code_size + 1;
+
+    writer.write(clear, code_size);
+
+    let mut prefix: Option<u16> = None;
+    for &index in indices {
+        let Some(current) = prefix else {
+            prefix = Some(index as u16);
+            continue;
+        };
+
+        if let Some(&code) = dictionary.get(&(current, index)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        writer.write(current, code_size);
+
+        if next_code < MAX_CODES {
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            dictionary.insert((current, index), next_code);
+            next_code += 1;
+        } else {
+            // table is full, start over
+            writer.write(clear, code_size);
+            dictionary.clear();
+            next_code = end_of_information + 1;
+            code_size = min_code_size + 1;
+        }
+
+        prefix = Some(index as u16);
+    }
+
+    if let Some(current) = prefix {
+        writer.write(current, code_size);
+    }
+    writer.write(end_of_information, code_size);
+
+    writer.finish()
+}
+
+// endregion: --- Compression
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    // straightforward decoder, only used to check the encoder round trips
+    fn decompress(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end_of_information = clear + 1;
+        let initial: Vec<Vec<u8>> = (0..clear)
+            .map(|idx| vec![idx as u8])
+            .chain([vec![], vec![]])
+            .collect();
+
+        let mut dictionary = initial.clone();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+
+        let mut bit = 0;
+        while bit + code_size as usize <= bytes.len() * 8 {
+            let mut code = 0usize;
+            for offset in 0..code_size as usize {
+                let pos = bit + offset;
+                code |= (((bytes[pos / 8] >> (pos % 8)) & 1) as usize) << offset;
+            }
+            bit += code_size as usize;
+
+            if code == clear {
+                dictionary = initial.clone();
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end_of_information {
+                break;
+            }
+
+            let entry = if code < dictionary.len() {
+                dictionary[code].clone()
+            } else {
+                let mut entry = previous.clone().expect("code refers to previous entry");
+                entry.push(entry[0]);
+                entry
+            };
+            output.extend(&entry);
+
+            if let Some(mut previous) = previous {
+                if dictionary.len() < MAX_CODES as usize {
+                    previous.push(entry[0]);
+                    dictionary.push(previous);
+                }
+            }
+            if dictionary.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+
+            previous = Some(entry);
+        }
+
+        output
+    }
+
+    #[test]
+    fn quick_test() {}
+
+    #[rstest]
+    #[case(vec![], 2)]
+    #[case(vec![0], 2)]
+    #[case(vec![1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2], 2)]
+    #[case((0..=255).collect(), 8)]
+    #[case((0..10_000).map(|n| (n % 3) as u8).collect(), 2)]
+    #[case((0..50_000u32).map(|n| (n.wrapping_mul(2654435761) >> 24) as u8).collect(), 8)]
+    fn test_round_trip(#[case] indices: Vec<u8>, #[case] min_code_size: u8) {
+        let compressed = compress(&indices, min_code_size);
+
+        assert_eq!(indices, decompress(&compressed, min_code_size));
+    }
+
+    #[test]
+    fn test_known_stream() {
+        // the 10x10 sample image from the GIF89a walkthrough on matthewflickinger.com,
+        // encoded with a 2 bit minimum code size
+        let indices: Vec<u8> = [
+            "1111122222",
+            "1111122222",
+            "1111122222",
+            "1110000222",
+            "1110000222",
+            "2220000111",
+            "2220000111",
+            "2222211111",
+            "2222211111",
+            "2222211111",
+        ]
+        .iter()
+        .flat_map(|row| row.bytes().map(|b| b - b'0'))
+        .collect();
+
+        let expected = [
+            0x8C, 0x2D, 0x99, 0x87, 0x2A, 0x1C, 0xDC, 0x33, 0xA0, 0x02, 0x75, 0xEC, 0x95, 0xFA,
+            0xA8, 0xDE, 0x60, 0x8C, 0x04, 0x91, 0x4C, 0x01,
+        ];
+
+        assert_eq!(expected.to_vec(), compress(&indices, 2));
+    }
+}
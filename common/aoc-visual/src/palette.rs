@@ -0,0 +1,72 @@
+use anyhow::{anyhow, Result};
+
+pub type Rgb = [u8; 3];
+
+/// Colours a frame's cell indices refer to. GIF allows at most 256 of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: Vec<Rgb>,
+}
+
+impl Palette {
+    pub fn new(colors: &[Rgb]) -> Result<Self> {
+        if colors.is_empty() || colors.len() > 256 {
+            return Err(anyhow!(
+                "a palette holds 1 to 256 colours, got {}",
+                colors.len()
+            ));
+        }
+
+        Ok(Self {
+            colors: colors.to_vec(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors
+    }
+
+    // number of bits needed to index the table, the table itself is padded to 2^bits entries
+    pub fn table_bits(&self) -> u8 {
+        let mut bits = 1;
+        while (1 << bits) < self.colors.len() {
+            bits += 1;
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(1, 2)]
+    #[case(2, 3)]
+    #[case(2, 4)]
+    #[case(3, 5)]
+    #[case(8, 256)]
+    fn test_table_bits(#[case] expected: u8, #[case] len: usize) {
+        let palette = Palette::new(&vec![[0, 0, 0]; len]).expect("valid palette");
+
+        assert_eq!(expected, palette.table_bits());
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(257)]
+    fn test_invalid_palette(#[case] len: usize) {
+        assert!(Palette::new(&vec![[0, 0, 0]; len]).is_err());
+    }
+}
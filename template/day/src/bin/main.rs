@@ -2,11 +2,11 @@ use {{crate_name}}::part1;
 use {{crate_name}}::part2;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt"));
+    let result = part1(&aoc_core::input!());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_str!("../../input.txt"));
+    let result = part2(&aoc_core::input!());
 
     println!("Part 2: {result:?}");
 }
\ No newline at end of file
@@ -1,11 +1,33 @@
-use anyhow::{Result, anyhow};
+use anyhow::{anyhow, Result};
+
+/// The puzzle input once parsed, both parts run on it.
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    pub lines: Vec<String>,
+}
+
+pub fn parse(input: &str) -> Result<Puzzle> {
+    let lines = input.lines().map(str::to_string).collect();
+
+    Ok(Puzzle { lines })
+}
 
 pub fn part1(input: &str) -> Result<u64> {
-    Err(anyhow!("Not Implemented."))
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u64> {
-    Err(anyhow!("Not Implemented."))
+    parse(input)?.part2()
+}
+
+impl Puzzle {
+    pub fn part1(&self) -> Result<u64> {
+        Err(anyhow!("Not Implemented."))
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        Err(anyhow!("Not Implemented."))
+    }
 }
 
 #[cfg(test)]
@@ -32,4 +54,4 @@ mod tests {
 
         assert_eq!(expected, result);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,31 @@
+use std::path::PathBuf;
+
+use aoc_runner::Year;
+
+mod registry;
+
+// the days of 2015, in the directory of their crates
+pub fn year() -> Year {
+    Year {
+        year: 2015,
+        root: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(".."),
+        days: registry::days(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use aoc_runner::fuzz;
+
+    use super::*;
+
+    // the inputs `aoc fuzz` once broke a day with
+    #[test]
+    fn test_fuzz_regressions() {
+        let broken = fuzz::replay(&year(), Duration::from_secs(10)).unwrap();
+
+        assert!(broken.is_empty(), "{broken:?}");
+    }
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use aoc_runner::{model, Day};
+
+// adapts a day's `parse` and the parts of its model to the runner, `params` for days that take them
+macro_rules! day {
+    ($day:literal, $krate:ident :: $model:ident) => {
+        Day::parsed(
+            $day,
+            |input, _| Ok(Arc::new($krate::parse(input)?)),
+            |parsed, _| Ok(model::<$krate::$model>(parsed)?.part1()?.to_string()),
+            |parsed, _| Ok(model::<$krate::$model>(parsed)?.part2()?.to_string()),
+        )
+    };
+    ($day:literal, $krate:ident :: $model:ident, params) => {
+        Day::parsed(
+            $day,
+            |input, _| Ok(Arc::new($krate::parse(input)?)),
+            |parsed, params| Ok(model::<$krate::$model>(parsed)?.part1(params)?.to_string()),
+            |parsed, params| Ok(model::<$krate::$model>(parsed)?.part2(params)?.to_string()),
+        )
+        .with_params($krate::params, $krate::example_params)
+    };
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        day!(13, day_13::SeatingPlan),
+        day!(15, day_15::Ingredients),
+        day!(16, day_16::Aunts, params),
+    ]
+}
@@ -1,12 +1,12 @@
-use day_13::part1;
-use day_13::part2;
+use y2015_day_13::part1;
+use y2015_day_13::part2;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt"));
+    let result = part1(&aoc_core::input!());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_str!("../../input.txt"));
+    let result = part2(&aoc_core::input!());
 
     println!("Part 2: {result:?}");
 }
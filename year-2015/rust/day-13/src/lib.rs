@@ -1,5 +1,5 @@
 // region:    --- Modules
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use nom::{
     branch::alt,
@@ -10,62 +10,111 @@ use nom::{
     sequence::{delimited, preceded},
     IResult,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 // endregion: --- Modules
 
-pub fn part1(input: &str) -> Result<i32> {
-    let (_, rel_map) = parse_relationships(input).expect("parse success");
-    let people: Vec<&str> = rel_map.keys().cloned().collect();
+/// How much each guest gains or loses sitting next to each other guest.
+#[derive(Debug, Clone)]
+pub struct SeatingPlan {
+    pub happiness: HashMap<String, HashMap<String, i32>>,
+}
 
-    let first_person = *people.first().expect("people not empty");
-    let sitting_cycles = people[1..]
+pub fn parse(input: &str) -> Result<SeatingPlan> {
+    let (_, rel_map) = parse_relationships(input).map_err(|err| anyhow!("parse error: {err}"))?;
+    // every guest can end up next to every other one
+    let guests: HashSet<&str> = rel_map
         .iter()
-        .cloned()
-        .permutations(people.len() - 1)
-        .map(|mut vec| {
-            vec.push(first_person);
-            vec
+        .flat_map(|(person, book)| book.keys().chain([person]))
+        .copied()
+        .collect();
+    if guests.len() < 2 {
+        return Err(anyhow!("parse error: fewer than two guests"));
+    }
+    for (person, neighbour) in guests.iter().tuple_combinations() {
+        for (lhs, rhs) in [(person, neighbour), (neighbour, person)] {
+            if !rel_map.get(lhs).is_some_and(|book| book.contains_key(rhs)) {
+                return Err(anyhow!("parse error: no happiness for {lhs} next to {rhs}"));
+            }
+        }
+    }
+    let happiness = rel_map
+        .into_iter()
+        .map(|(person, book)| {
+            let book = book
+                .into_iter()
+                .map(|(neighbour, h)| (neighbour.to_string(), h))
+                .collect();
+            (person.to_string(), book)
         })
-        .collect::<Vec<_>>();
+        .collect();
 
-    Ok(sitting_cycles
-        .iter()
-        .map(|cycle| {
-            let mut sum = cycle
-                .windows(2)
-                .map(|pair| rel_map[pair[0]][pair[1]] + rel_map[pair[1]][pair[0]])
-                .sum();
-            sum += rel_map[cycle[0]][cycle[cycle.len() - 1]]
-                + rel_map[cycle[cycle.len() - 1]][cycle[0]];
-
-            sum
-        })
-        .max()
-        .expect("cycle not empty"))
+    Ok(SeatingPlan { happiness })
+}
+
+pub fn part1(input: &str) -> Result<i32> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<i32> {
-    let (_, rel_map) = parse_relationships(input).expect("parse success");
-    let people: Vec<&str> = rel_map.keys().cloned().collect();
+    parse(input)?.part2()
+}
 
-    let sitting_cycles = people
-        .iter()
-        .cloned()
-        .permutations(people.len())
-        .collect::<Vec<_>>();
+impl SeatingPlan {
+    pub fn part1(&self) -> Result<i32> {
+        let rel_map = &self.happiness;
+        let people: Vec<&str> = rel_map.keys().map(String::as_str).collect();
 
-    Ok(sitting_cycles
-        .iter()
-        .map(|cycle| {
-            let sum = cycle
-                .windows(2)
-                .map(|pair| rel_map[pair[0]][pair[1]] + rel_map[pair[1]][pair[0]])
-                .sum();
-            sum
-        })
-        .max()
-        .expect("cycle not empty"))
+        let first_person = *people.first().expect("people not empty");
+        let sitting_cycles = people[1..]
+            .iter()
+            .cloned()
+            .permutations(people.len() - 1)
+            .map(|mut vec| {
+                vec.push(first_person);
+                vec
+            })
+            .collect::<Vec<_>>();
+
+        Ok(sitting_cycles
+            .iter()
+            .map(|cycle| {
+                let mut sum = cycle
+                    .windows(2)
+                    .map(|pair| rel_map[pair[0]][pair[1]] + rel_map[pair[1]][pair[0]])
+                    .sum();
+                sum += rel_map[cycle[0]][cycle[cycle.len() - 1]]
+                    + rel_map[cycle[cycle.len() - 1]][cycle[0]];
+
+                sum
+            })
+            .max()
+            .expect("cycle not empty"))
+    }
+
+    pub fn part2(&self) -> Result<i32> {
+        let rel_map = &self.happiness;
+        let people: Vec<&str> = rel_map.keys().map(String::as_str).collect();
+
+        let sitting_cycles = people
+            .iter()
+            .cloned()
+            .permutations(people.len())
+            .collect::<Vec<_>>();
+
+        Ok(sitting_cycles
+            .iter()
+            .map(|cycle| {
+                let sum = cycle
+                    .windows(2)
+                    .map(|pair| rel_map[pair[0]][pair[1]] + rel_map[pair[1]][pair[0]])
+                    .sum();
+                sum
+            })
+            .max()
+            .expect("cycle not empty"))
+    }
 }
+
 // region:    --- Parsing
 type RelationshipMap<'a> = HashMap<&'a str, HashMap<&'a str, i32>>;
 fn parse_relationship(input: &str) -> IResult<&str, (&str, i32, &str)> {
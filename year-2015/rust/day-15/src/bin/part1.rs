@@ -1,7 +1,7 @@
-use day_15::part1;
+use y2015_day_15::part1;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt")).expect("Part 1 failed to run");
+    let result = part1(&aoc_core::input!()).expect("Part 1 failed to run");
 
     println!("{result}");
 }
@@ -1,174 +1,240 @@
-#![feature(int_roundings)]
-
 use anyhow::{anyhow, Result};
+use aoc_core::{debug, trace};
 use nom::{
     bytes::complete::tag,
     character::complete::{self, alpha1, space1},
-    combinator::all_consuming,
+    combinator::{all_consuming, verify},
     multi::separated_list1,
     sequence::{preceded, tuple},
     IResult,
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::{
     cmp::{max, min},
     ops::{Div, Range},
 };
 
-pub fn part1(input: &str) -> Result<u64> {
-    let ingreds: Vec<Vec<i64>> = input
-        .lines()
-        .map(|line| parse_line(line).unwrap().1)
-        .collect();
-
-    println!("{ingreds:?}");
+/// The properties of each ingredient, with calories last.
+#[derive(Debug, Clone)]
+pub struct Ingredients(pub Vec<Vec<i64>>);
 
-    // narrow down acceptable ranges for each ingred.
-    let mut ranges = [0..101, 0..101, 0..101, 0..101];
-    for (left, right) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)] {
-        for idx in 0..4 {
-            let (ll, rr) = get_range_limits(ingreds[left][idx], ingreds[right][idx], 100);
-            println!("> {:?}, {:?}", ll, rr);
-            ranges[left] = max(ranges[left].start, ll.start)..min(ranges[left].end, ll.end);
-            ranges[right] = max(ranges[right].start, rr.start)..min(ranges[right].end, rr.end);
-        }
+pub fn parse(input: &str) -> Result<Ingredients> {
+    let ingreds = input
+        .lines()
+        .map(|line| {
+            parse_line(line)
+                .map(|(_, props)| props)
+                .map_err(|err| anyhow!("parse error: {err}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // the recipes are searched for four ingredients with four properties and calories
+    if ingreds.len() != 4 || ingreds.iter().any(|props| props.len() != 5) {
+        return Err(anyhow!(
+            "parse error: expected 4 ingredients with 5 properties each"
+        ));
     }
 
-    println!("{:?}", ranges);
+    Ok(Ingredients(ingreds))
+}
 
-    // brute force
-    let mut score = 0;
-    for i in ranges[0].clone() {
-        for j in ranges[1].start..ranges[1].end {
-            if i + j >= 100 {
-                break;
-            }
-            for k in ranges[2].start..ranges[2].end {
-                if i + j + k >= 100 {
-                    break;
-                }
-                let l = 100 - i - j - k;
-
-                // println!("{} {} {} {}", i, j, k, l);
-                let mut ingreds = ingreds.clone();
-                [i, j, k, l].iter().enumerate().for_each(|(idx, quantity)| {
-                    ingreds[idx].iter_mut().for_each(|value| {
-                        *value *= quantity;
-                    })
-                });
-                // dbg!(&ingreds);
-
-                let sum: Vec<i64> = (0..4)
-                    .map(|idx| {
-                        let prop: i64 = ingreds.iter().map(|ingred| ingred[idx]).sum();
-                        if prop.is_negative() {
-                            0
-                        } else {
-                            prop
-                        }
-                    })
-                    .collect();
-                let new_score = sum.iter().product();
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
 
-                score = max(score, new_score);
+impl Ingredients {
+    pub fn part1(&self) -> Result<u64> {
+        let ingreds = &self.0;
+
+        debug!("ingredients: {ingreds:?}");
+
+        // narrow down acceptable ranges for each ingred.
+        let mut ranges = [0..101, 0..101, 0..101, 0..101];
+        for (left, right) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)] {
+            for (idx, (&lhs, &rhs)) in ingreds[left]
+                .iter()
+                .zip(&ingreds[right])
+                .take(4)
+                .enumerate()
+            {
+                let (ll, rr) = get_range_limits(lhs, rhs, 100);
+                trace!("ingredients {left} and {right}, property {idx}: {ll:?}, {rr:?}");
+                ranges[left] = max(ranges[left].start, ll.start)..min(ranges[left].end, ll.end);
+                ranges[right] = max(ranges[right].start, rr.start)..min(ranges[right].end, rr.end);
             }
         }
-    }
 
-    Ok(score as u64)
-}
-
-pub fn part2(input: &str) -> Result<u64> {
-    let ingreds: Vec<Vec<i64>> = input
-        .lines()
-        .map(|line| parse_line(line).unwrap().1)
-        .collect();
+        debug!("quantity ranges: {ranges:?}");
+
+        // brute force
+        #[cfg(feature = "parallel")]
+        let first = ranges[0].clone().into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let first = ranges[0].clone();
+
+        let score = first
+            .map(|i| {
+                let mut score = 0;
+                for j in ranges[1].start..ranges[1].end {
+                    if i + j >= 100 {
+                        break;
+                    }
+                    for k in ranges[2].start..ranges[2].end {
+                        if i + j + k >= 100 {
+                            break;
+                        }
+                        let l = 100 - i - j - k;
+
+                        // println!("{} {} {} {}", i, j, k, l);
+                        let mut ingreds = ingreds.clone();
+                        [i, j, k, l].iter().enumerate().for_each(|(idx, quantity)| {
+                            ingreds[idx].iter_mut().for_each(|value| {
+                                *value *= quantity;
+                            })
+                        });
+                        // dbg!(&ingreds);
+
+                        let sum: Vec<i64> = (0..4)
+                            .map(|idx| {
+                                let prop: i64 = ingreds.iter().map(|ingred| ingred[idx]).sum();
+                                if prop.is_negative() {
+                                    0
+                                } else {
+                                    prop
+                                }
+                            })
+                            .collect();
+                        let new_score = sum.iter().product();
+
+                        score = max(score, new_score);
+                    }
+                }
 
-    println!("{ingreds:?}");
+                score
+            })
+            .max()
+            .unwrap_or(0);
 
-    // narrow down acceptable ranges for each ingred.
-    let mut ranges = [0..101, 0..101, 0..101, 0..101];
-    for (left, right) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)] {
-        for idx in 0..4 {
-            let (ll, rr) = get_range_limits(ingreds[left][idx], ingreds[right][idx], 100);
-            println!("> {:?}, {:?}", ll, rr);
-            ranges[left] = max(ranges[left].start, ll.start)..min(ranges[left].end, ll.end);
-            ranges[right] = max(ranges[right].start, rr.start)..min(ranges[right].end, rr.end);
-        }
+        Ok(score as u64)
     }
 
-    println!("{:?}", ranges);
-
-    // brute force
-    let mut score = 0;
-    // for i in ranges[0].clone() {
-    //     for j in ranges[1].start..ranges[1].end {
-    //         if i + j >= 100 {
-    //             break;
-    //         }
-    //         for k in ranges[2].start..ranges[2].end {
-    for i in 0..101 {
-        for j in 0..101 {
-            if i + j >= 100 {
-                break;
+    pub fn part2(&self) -> Result<u64> {
+        let ingreds = &self.0;
+
+        debug!("ingredients: {ingreds:?}");
+
+        // narrow down acceptable ranges for each ingred.
+        let mut ranges = [0..101, 0..101, 0..101, 0..101];
+        for (left, right) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)] {
+            for (idx, (&lhs, &rhs)) in ingreds[left]
+                .iter()
+                .zip(&ingreds[right])
+                .take(4)
+                .enumerate()
+            {
+                let (ll, rr) = get_range_limits(lhs, rhs, 100);
+                trace!("ingredients {left} and {right}, property {idx}: {ll:?}, {rr:?}");
+                ranges[left] = max(ranges[left].start, ll.start)..min(ranges[left].end, ll.end);
+                ranges[right] = max(ranges[right].start, rr.start)..min(ranges[right].end, rr.end);
             }
-            for k in 0..101 {
-                if i + j + k >= 100 {
-                    break;
-                }
-                let l = 100 - i - j - k;
+        }
 
-                let total_calorie: i64 = ingreds
-                    .iter()
-                    .zip([i, j, k, l])
-                    .map(|(ingred, quantity)| ingred[4] * quantity)
-                    .sum();
+        debug!("quantity ranges: {ranges:?}");
+
+        // brute force
+        #[cfg(feature = "parallel")]
+        let first = (0..101).into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let first = 0..101;
+
+        // for i in ranges[0].clone() {
+        //     for j in ranges[1].start..ranges[1].end {
+        //         if i + j >= 100 {
+        //             break;
+        //         }
+        //         for k in ranges[2].start..ranges[2].end {
+        let score = first
+            .map(|i| {
+                let mut score = 0;
+                for j in 0..101 {
+                    if i + j >= 100 {
+                        break;
+                    }
+                    for k in 0..101 {
+                        if i + j + k >= 100 {
+                            break;
+                        }
+                        let l = 100 - i - j - k;
 
-                if total_calorie != 500 {
-                    continue;
-                }
+                        let total_calorie: i64 = ingreds
+                            .iter()
+                            .zip([i, j, k, l])
+                            .map(|(ingred, quantity)| ingred[4] * quantity)
+                            .sum();
+
+                        if total_calorie != 500 {
+                            continue;
+                        }
 
-                let mut ingreds = ingreds.clone();
-                [i, j, k, l].iter().enumerate().for_each(|(idx, quantity)| {
-                    ingreds[idx].iter_mut().for_each(|value| {
-                        *value *= quantity;
-                    })
-                });
-                // dbg!(&ingreds);
-
-                let sum: Vec<i64> = (0..4)
-                    .map(|idx| {
-                        let prop: i64 = ingreds.iter().map(|ingred| ingred[idx]).sum();
-                        if prop.is_negative() {
-                            0
-                        } else {
-                            prop
+                        let mut ingreds = ingreds.clone();
+                        [i, j, k, l].iter().enumerate().for_each(|(idx, quantity)| {
+                            ingreds[idx].iter_mut().for_each(|value| {
+                                *value *= quantity;
+                            })
+                        });
+                        // dbg!(&ingreds);
+
+                        let sum: Vec<i64> = (0..4)
+                            .map(|idx| {
+                                let prop: i64 = ingreds.iter().map(|ingred| ingred[idx]).sum();
+                                if prop.is_negative() {
+                                    0
+                                } else {
+                                    prop
+                                }
+                            })
+                            .collect();
+                        let new_score = sum.iter().product();
+
+                        if new_score > 0 {
+                            trace!("{i} {j} {k} {l}: {new_score}");
                         }
-                    })
-                    .collect();
-                let new_score = sum.iter().product();
 
-                if new_score > 0 {
-                    println!("{} {} {} {} {}", i, j, k, l, new_score);
+                        score = max(score, new_score);
+                    }
                 }
 
-                score = max(score, new_score);
-            }
-        }
-    }
+                score
+            })
+            .max()
+            .unwrap_or(0);
 
-    Ok(score as u64)
+        Ok(score as u64)
+    }
 }
 
 // region:    --- Parser
 
+// small enough for the score of 100 teaspoons to fit in an i64
+const MAX_PROPERTY: i64 = 100;
+
 fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
     let (_rest, data) = all_consuming(preceded(
         tuple((alpha1, tag(": "))),
-        separated_list1(tag(", "), preceded(tuple((alpha1, space1)), complete::i64)),
+        separated_list1(
+            tag(", "),
+            preceded(
+                tuple((alpha1, space1)),
+                verify(complete::i64, |value| value.abs() <= MAX_PROPERTY),
+            ),
+        ),
     ))(input)?;
 
-    dbg!(&_rest);
-    dbg!(&data);
+    trace!("parsed {data:?}");
 
     Ok((_rest, data))
 }
@@ -192,7 +258,7 @@ fn get_range_limits(lhs: i64, rhs: i64, total: i64) -> (Range<i64>, Range<i64>)
         let alpha = min(lhs, rhs).abs();
         let beta = max(lhs, rhs);
         let lower_bounded = (total * alpha).div(alpha + beta) + 1..total + 1;
-        let upper_bounded = 0..(total * beta).div_ceil(alpha + beta);
+        let upper_bounded = 0..div_ceil(total * beta, alpha + beta);
 
         if lhs.is_negative() {
             (upper_bounded, lower_bounded)
@@ -202,12 +268,20 @@ fn get_range_limits(lhs: i64, rhs: i64, total: i64) -> (Range<i64>, Range<i64>)
     }
 }
 
+// the quotient rounded towards positive infinity, `i64::div_ceil` is not stable
+fn div_ceil(lhs: i64, rhs: i64) -> i64 {
+    let quotient = lhs / rhs;
+    if lhs % rhs != 0 && (lhs > 0) == (rhs > 0) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
 // endregion: --- Part 1
 
 #[cfg(test)]
 mod tests {
-    #![feature(int_roundings)]
-
     use rstest::rstest;
 
     use super::*;
@@ -267,4 +341,14 @@ mod tests {
     ) {
         assert_eq!(expected, get_range_limits(lhs, rhs, total));
     }
+
+    #[rstest]
+    #[case(7, 2, 4)]
+    #[case(6, 2, 3)]
+    #[case(-7, 2, -3)]
+    #[case(7, -2, -3)]
+    #[case(-7, -2, 4)]
+    fn test_div_ceil(#[case] lhs: i64, #[case] rhs: i64, #[case] expected: i64) {
+        assert_eq!(expected, div_ceil(lhs, rhs));
+    }
 }
@@ -1,12 +1,13 @@
-use day_16::part1;
-use day_16::part2;
+use y2015_day_16::params;
+use y2015_day_16::part1;
+use y2015_day_16::part2;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt"));
+    let result = part1(&aoc_core::input!(), &params());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_str!("../../input.txt"));
+    let result = part2(&aoc_core::input!(), &params());
 
     println!("Part 2: {result:?}");
 }
\ No newline at end of file
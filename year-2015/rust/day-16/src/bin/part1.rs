@@ -1,7 +1,8 @@
-use day_16::part1;
+use y2015_day_16::params;
+use y2015_day_16::part1;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt")).expect("Part 1 failed to run");
+    let result = part1(&aoc_core::input!(), &params()).expect("Part 1 failed to run");
 
     println!("{result}");
 }
@@ -10,6 +11,8 @@ fn main() {
 mod tests {
     use rstest::rstest;
 
+    use y2015_day_16::example_params;
+
     use super::*;
 
     #[test]
@@ -18,7 +21,8 @@ mod tests {
     #[rstest]
     #[case(0)]
     fn test_using_test_input_1(#[case] expected: u64) {
-        let result = part1(include_str!("../../test-1.txt")).expect("Part 1 failed to run");
+        let result = part1(include_str!("../../test-1.txt"), &example_params())
+            .expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
     }
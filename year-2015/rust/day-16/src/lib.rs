@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::{anyhow, Result};
+use aoc_core::Params;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, alpha1, line_ending},
@@ -9,56 +10,121 @@ use nom::{
     IResult,
 };
 
-pub fn part1(input: &str) -> Result<u64> {
-    let (_, aunt_props) = parse_aunts(input).expect("parse ok");
-
-    let mut details = HashMap::<&str, u32>::new();
-    details.insert("children", 3);
-    details.insert("cats", 7);
-    details.insert("samoyeds", 2);
-    details.insert("pomeranians", 3);
-    details.insert("akitas", 0);
-    details.insert("vizslas", 0);
-    details.insert("goldfish", 5);
-    details.insert("trees", 3);
-    details.insert("cars", 2);
-    details.insert("perfumes", 1);
-
-    let aunts: Vec<_> = aunt_props
-        .iter()
-        .filter(|aunt| aunt.1.iter().all(|prop| details[prop.0] == prop.1))
-        .collect();
-
-    Ok(aunts[0].0 as u64)
+// region:    --- Params
+
+const COMPOUNDS: [&str; 10] = [
+    "children",
+    "cats",
+    "samoyeds",
+    "pomeranians",
+    "akitas",
+    "vizslas",
+    "goldfish",
+    "trees",
+    "cars",
+    "perfumes",
+];
+
+// what the MFCSAM detected in the gift
+pub fn params() -> Params {
+    COMPOUNDS
+        .into_iter()
+        .zip([3, 7, 2, 3, 0, 0, 5, 3, 2, 1])
+        .fold(Params::new(), |params, (compound, amount)| {
+            params.with(compound, amount)
+        })
 }
 
-pub fn part2(input: &str) -> Result<u64> {
-    let (_, aunt_props) = parse_aunts(input).expect("parse ok");
-
-    let mut details = HashMap::<&str, u32>::new();
-    details.insert("children", 3);
-    details.insert("cats", 7);
-    details.insert("samoyeds", 2);
-    details.insert("pomeranians", 3);
-    details.insert("akitas", 0);
-    details.insert("vizslas", 0);
-    details.insert("goldfish", 5);
-    details.insert("trees", 3);
-    details.insert("cars", 2);
-    details.insert("perfumes", 1);
-
-    let aunts: Vec<_> = aunt_props
-        .iter()
-        .filter(|aunt| {
-            aunt.1.iter().all(|prop| match prop {
-                prop if prop.0 == "cats" || prop.0 == "trees" => details[prop.0] < prop.1,
-                prop if prop.0 == "pomeranians" || prop.0 == "goldfish" => details[prop.0] > prop.1,
-                prop => details[prop.0] == prop.1,
-            })
+pub fn example_params() -> Params {
+    params()
+}
+
+fn mfcsam_readings(params: &Params) -> Result<HashMap<&'static str, u32>> {
+    COMPOUNDS
+        .into_iter()
+        .map(|compound| Ok((compound, params.get(compound)?)))
+        .collect()
+}
+
+// endregion: --- Params
+
+/// Every aunt Sue by number, with the compounds remembered about her.
+#[derive(Debug, Clone)]
+pub struct Aunts(pub Vec<(u32, Vec<(String, u32)>)>);
+
+pub fn parse(input: &str) -> Result<Aunts> {
+    let (_, aunt_props) = parse_aunts(input).map_err(|err| anyhow!("parse error: {err}"))?;
+    let aunts = aunt_props
+        .into_iter()
+        .map(|(id, props)| {
+            let props = props
+                .into_iter()
+                .map(|(compound, amount)| {
+                    if !COMPOUNDS.contains(&compound) {
+                        return Err(anyhow!("parse error: Sue {id} has an unknown {compound}"));
+                    }
+                    Ok((compound.to_string(), amount))
+                })
+                .collect::<Result<_>>()?;
+            Ok((id, props))
         })
-        .collect();
+        .collect::<Result<_>>()?;
+
+    Ok(Aunts(aunts))
+}
+
+pub fn part1(input: &str, params: &Params) -> Result<u64> {
+    parse(input)?.part1(params)
+}
+
+pub fn part2(input: &str, params: &Params) -> Result<u64> {
+    parse(input)?.part2(params)
+}
+
+impl Aunts {
+    pub fn part1(&self, params: &Params) -> Result<u64> {
+        let aunt_props = &self.0;
+
+        let details = mfcsam_readings(params)?;
 
-    Ok(aunts[0].0 as u64)
+        let aunts: Vec<_> = aunt_props
+            .iter()
+            .filter(|aunt| aunt.1.iter().all(|prop| details[prop.0.as_str()] == prop.1))
+            .collect();
+
+        let (id, _) = aunts
+            .first()
+            .ok_or_else(|| anyhow!("no aunt Sue matches the readings"))?;
+
+        Ok(*id as u64)
+    }
+
+    pub fn part2(&self, params: &Params) -> Result<u64> {
+        let aunt_props = &self.0;
+
+        let details = mfcsam_readings(params)?;
+
+        let aunts: Vec<_> = aunt_props
+            .iter()
+            .filter(|aunt| {
+                aunt.1.iter().all(|prop| match prop {
+                    prop if prop.0 == "cats" || prop.0 == "trees" => {
+                        details[prop.0.as_str()] < prop.1
+                    }
+                    prop if prop.0 == "pomeranians" || prop.0 == "goldfish" => {
+                        details[prop.0.as_str()] > prop.1
+                    }
+                    prop => details[prop.0.as_str()] == prop.1,
+                })
+            })
+            .collect();
+
+        let (id, _) = aunts
+            .first()
+            .ok_or_else(|| anyhow!("no aunt Sue matches the readings"))?;
+
+        Ok(*id as u64)
+    }
 }
 
 // region:    --- Parser
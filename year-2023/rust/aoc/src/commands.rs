@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+use aoc_core::cancel;
+use aoc_runner::{model, Model};
+
+// the commands of `aoc repl`, each answering the rest of its line from a day's model
+
+// `map 79` follows a seed through every map, `map 79 14` maps the seeds 79..=92 at once
+pub fn map(parsed: &Model, args: &str) -> Result<String> {
+    let almanac = model::<day_5::Almanac>(parsed)?;
+    let numbers = args
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    match numbers[..] {
+        [seed] => Ok(almanac
+            .follow(seed)
+            .iter()
+            .map(|(category, id)| format!("{category} {id}"))
+            .collect::<Vec<_>>()
+            .join(" -> ")),
+        [start, length] if length > 0 => Ok(almanac
+            .condensed
+            .map_range((start..=start + length - 1).into())
+            .iter()
+            .map(ToString::to_string)
+            .collect()),
+        _ => Err(anyhow!("usage: map SEED [LENGTH]")),
+    }
+}
+
+// `route {x=787,m=2655,a=1222,s=2876}` lists the workflows the part goes through
+pub fn route(parsed: &Model, args: &str) -> Result<String> {
+    let workflows = model::<day_19::Workflows>(parsed)?;
+    let part = day_19::parse_part(args)?;
+
+    Ok(format!("{part}: {}", workflows.route(part)?.join(" -> ")))
+}
+
+// `press 10` pushes the button of a fresh network, then shows the state it is left in
+pub fn press(parsed: &Model, args: &str) -> Result<String> {
+    let mut network = model::<day_20::ModuleNetwork>(parsed)?.clone();
+    let presses = args.parse::<u64>()?;
+
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..presses {
+        cancel::check()?;
+        let pulses = network.press();
+        low += pulses.0;
+        high += pulses.1;
+    }
+
+    Ok(format!(
+        "{presses} presses: {low} low and {high} high pulses\n{network}"
+    ))
+}
+
+// `fall 3` lists the bricks that fall once the fourth lowest brick is disintegrated
+pub fn fall(parsed: &Model, args: &str) -> Result<String> {
+    let stack = model::<day_22::BrickStack>(parsed)?;
+    let brick = args.parse::<usize>()?;
+
+    let fallen = stack.fall(brick)?;
+    let mut answer = format!("{}: {} bricks fall", stack.bricks[brick], fallen.len());
+    for brick in fallen {
+        answer.push_str(&format!("\n{brick}"));
+    }
+
+    Ok(answer)
+}
@@ -0,0 +1,5 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    aoc_runner::main(vec![aoc_2023::year()])
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use aoc_runner::{model, Day};
+
+use crate::commands;
+
+// adapts a day's `parse` and the parts of its model to the runner, for days taking `&str` or `&[u8]`
+macro_rules! day {
+    ($day:literal, $krate:ident :: $model:ident) => {
+        Day::parsed(
+            $day,
+            |input, _| Ok(Arc::new($krate::parse(input)?)),
+            |parsed, _| Ok(model::<$krate::$model>(parsed)?.part1()?.to_string()),
+            |parsed, _| Ok(model::<$krate::$model>(parsed)?.part2()?.to_string()),
+        )
+    };
+    ($day:literal, $krate:ident :: $model:ident, bytes) => {
+        Day::parsed(
+            $day,
+            |input, _| Ok(Arc::new($krate::parse(input.as_bytes())?)),
+            |parsed, _| Ok(model::<$krate::$model>(parsed)?.part1()?.to_string()),
+            |parsed, _| Ok(model::<$krate::$model>(parsed)?.part2()?.to_string()),
+        )
+    };
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        day!(1, day_1::CalibrationDocument),
+        day!(2, day_2::Games),
+        day!(3, day_3::Schematic),
+        day!(4, day_4::Scratchcards),
+        day!(5, day_5::Almanac)
+            .with_generator(|seed| day_5::generator::generate(seed, &Default::default()))
+            .with_commands(&[("map SEED [LENGTH]", commands::map)]),
+        day!(6, day_6::Races),
+        day!(7, day_7::CamelCards),
+        day!(8, day_8::Network),
+        day!(9, day_9::Report),
+        day!(10, day_10::Sketch, bytes),
+        Day::parsed(
+            11,
+            |input, _| Ok(Arc::new(day_11::parse(input.as_bytes())?)),
+            |parsed, _| Ok(model::<day_11::Universe>(parsed)?.part1()?.to_string()),
+            |parsed, params| {
+                Ok(model::<day_11::Universe>(parsed)?
+                    .part2(params)?
+                    .to_string())
+            },
+        )
+        .with_params(day_11::params, day_11::example_params),
+        day!(12, day_12::ConditionRecords)
+            .with_generator(|seed| day_12::generator::generate(seed, &Default::default())),
+        day!(13, day_13::Patterns, bytes),
+        day!(14, day_14::Platform, bytes),
+        day!(15, day_15::InitializationSequence, bytes),
+        day!(16, day_16::Contraption, bytes),
+        day!(18, day_18::DigPlan),
+        Day::parsed(
+            19,
+            |input, _| Ok(Arc::new(day_19::parse(input)?)),
+            |parsed, _| Ok(model::<day_19::Workflows>(parsed)?.part1()?.to_string()),
+            |parsed, params| {
+                Ok(model::<day_19::Workflows>(parsed)?
+                    .part2(params)?
+                    .to_string())
+            },
+        )
+        .with_params(day_19::params, day_19::example_params)
+        .with_generator(|seed| day_19::generator::generate(seed, &Default::default()))
+        .with_commands(&[("route PART", commands::route)]),
+        day!(20, day_20::ModuleNetwork).with_commands(&[("press TIMES", commands::press)]),
+        Day::parsed(
+            21,
+            |input, _| Ok(Arc::new(day_21::parse(input)?)),
+            |parsed, params| Ok(model::<day_21::Garden>(parsed)?.part1(params)?.to_string()),
+            |parsed, params| Ok(model::<day_21::Garden>(parsed)?.part2(params)?.to_string()),
+        )
+        .with_params(day_21::params, day_21::example_params),
+        day!(22, day_22::BrickStack)
+            .with_generator(|seed| day_22::generator::generate(seed, &Default::default()))
+            .with_commands(&[("fall BRICK", commands::fall)]),
+        day!(23, day_23::Trails),
+        Day::parsed(
+            24,
+            |input, _| Ok(Arc::new(day_24::parse(input)?)),
+            |parsed, params| {
+                Ok(model::<day_24::Hailstorm>(parsed)?
+                    .part1(params)?
+                    .to_string())
+            },
+            |parsed, _| Ok(model::<day_24::Hailstorm>(parsed)?.part2()?.to_string()),
+        )
+        .with_params(day_24::params, day_24::example_params)
+        .with_generator(|seed| day_24::generator::generate(seed, &Default::default())),
+        Day::parsed(
+            25,
+            |input, _| Ok(Arc::new(day_25::parse(input)?)),
+            |parsed, params| Ok(model::<day_25::Wiring>(parsed)?.part1(params)?.to_string()),
+            |parsed, _| Ok(model::<day_25::Wiring>(parsed)?.part2()?.to_string()),
+        )
+        .with_params(day_25::params, day_25::example_params),
+    ]
+}
@@ -8,12 +8,35 @@ use nom::{
     IResult,
 };
 
+/// The lines of the calibration document, each read for digits by one part and for spelled
+/// out digits too by the other.
+#[derive(Debug, Clone)]
+pub struct CalibrationDocument {
+    pub lines: Vec<String>,
+}
+
+pub fn parse(input: &str) -> Result<CalibrationDocument> {
+    Ok(CalibrationDocument {
+        lines: input.lines().map(str::to_string).collect(),
+    })
+}
+
 pub fn part1(input: &str) -> Result<u32> {
-    Ok(input.lines().flat_map(|line| extract(line)).sum())
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u32> {
-    Ok(input.lines().flat_map(|line| extract2(line)).sum())
+    parse(input)?.part2()
+}
+
+impl CalibrationDocument {
+    pub fn part1(&self) -> Result<u32> {
+        Ok(self.lines.iter().flat_map(|line| extract(line)).sum())
+    }
+
+    pub fn part2(&self) -> Result<u32> {
+        Ok(self.lines.iter().flat_map(|line| extract2(line)).sum())
+    }
 }
 
 // region:    --- Part 1
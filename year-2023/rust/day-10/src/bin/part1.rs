@@ -1,7 +1,7 @@
 use day_10::part1;
 
 fn main() {
-    let result = part1(include_bytes!("../../input.txt")).expect("Part 1 failed to run");
+    let result = part1(aoc_core::input!().as_bytes()).expect("Part 1 failed to run");
 
     println!("{result}");
 }
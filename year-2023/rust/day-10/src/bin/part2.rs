@@ -1,7 +1,7 @@
 use day_10::part2;
 
 fn main() {
-    let result = part2(include_bytes!("../../input.txt")).expect("Part 2 failed to run");
+    let result = part2(aoc_core::input!().as_bytes()).expect("Part 2 failed to run");
 
     println!("{result}");
 }
@@ -1,70 +1,108 @@
 use std::collections::HashSet;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use coordinates::{neighbour_coord_in_direction, Coord};
 use direction::Direction;
 use itertools::Itertools;
-use pipe::{C7, F, J, L, PIPE};
+use pipe::{can_connect_to, PipeType, C7, DASH, F, J, L, PIPE};
 
-use crate::pipe::{connected, NEWLINE, S};
+use crate::pipe::{connected, NEWLINE, S, TILES};
 
 mod coordinates;
 mod direction;
 mod pipe;
 
-pub fn part1(input: &[u8]) -> Result<u64> {
-    let grid: Vec<&[u8]> = input.split(|b| *b == NEWLINE).collect();
+/// The rows of the sketch of the pipes, the start among them.
+#[derive(Debug, Clone)]
+pub struct Sketch {
+    pub grid: Vec<Vec<u8>>,
+}
 
-    let start_coord = find_start(&grid);
+pub fn parse(input: &[u8]) -> Result<Sketch> {
+    let mut grid = input
+        .split(|b| *b == NEWLINE)
+        .map(<[u8]>::to_vec)
+        .collect::<Vec<_>>();
+    // the line end of the last row
+    if grid.len() > 1 && grid.last().is_some_and(Vec::is_empty) {
+        grid.pop();
+    }
+
+    let width = grid[0].len();
+    if width == 0 || grid.iter().any(|row| row.len() != width) {
+        return Err(anyhow!("parse error: the rows are not all as long"));
+    }
+    if let Some(tile) = grid.iter().flatten().find(|tile| !TILES.contains(tile)) {
+        return Err(anyhow!("parse error: unknown tile {:?}", char::from(*tile)));
+    }
 
-    let (steps, _) = trace_loop(&grid, start_coord);
+    Ok(Sketch { grid })
+}
 
-    Ok(steps / 2)
+pub fn part1(input: &[u8]) -> Result<u64> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &[u8]) -> Result<u64> {
-    let grid: Vec<&[u8]> = input.split(|b| *b == NEWLINE).collect();
+    parse(input)?.part2()
+}
 
-    let start_coord = find_start(&grid);
+impl Sketch {
+    fn rows(&self) -> Vec<&[u8]> {
+        self.grid.iter().map(Vec::as_slice).collect()
+    }
 
-    let (_, loop_coords) = trace_loop(&grid, start_coord);
+    pub fn part1(&self) -> Result<u64> {
+        let grid = self.rows();
 
-    // TODO: automatic modifiy starting point as a proper pipe;
-    // let grid: Vec<&[u8]> = include_bytes!("../test-3-2.txt")
-    // let grid: Vec<&[u8]> = include_bytes!("../test-4-2.txt")
-    // let grid: Vec<&[u8]> = include_bytes!("../test-5-2.txt")
-    let grid: Vec<&[u8]> = include_bytes!("../input-2.txt")
-        .split(|b| *b == NEWLINE)
-        .collect();
+        let start_coord = find_start(&grid)?;
 
-    // for each cell on each row, we look into the right direction
-    // if the number of actual loop vertial crossing is odd, then it has to be inside.
-    Ok((0..grid.len())
-        .cartesian_product(0..grid[0].len())
-        // for cell no on the loop and not on the right edge
-        .filter(|coord @ (_, col)| !loop_coords.contains(coord) && *col != grid[0].len() - 1)
-        // inside criteria
-        .filter(|coord| is_inside(&grid, &loop_coords, *coord))
-        .count() as u64)
+        let (steps, _) = trace_loop(&grid, start_coord)?;
+
+        Ok(steps / 2)
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        let grid = self.rows();
+
+        let start_coord = find_start(&grid)?;
+
+        let (_, loop_coords) = trace_loop(&grid, start_coord)?;
+
+        // the start is counted as the pipe that it stands for in the loop
+        let mut rows = self.grid.clone();
+        rows[start_coord.0][start_coord.1] = start_pipe(&grid, &loop_coords, start_coord);
+        let grid: Vec<&[u8]> = rows.iter().map(Vec::as_slice).collect();
+
+        // for each cell on each row, we look into the right direction
+        // if the number of actual loop vertial crossing is odd, then it has to be inside.
+        Ok((0..grid.len())
+            .cartesian_product(0..grid[0].len())
+            // for cell no on the loop and not on the right edge
+            .filter(|coord @ (_, col)| !loop_coords.contains(coord) && *col != grid[0].len() - 1)
+            // inside criteria
+            .filter(|coord| is_inside(&grid, &loop_coords, *coord))
+            .count() as u64)
+    }
 }
 
 // region:    --- Part 1
 
-fn find_start(grid: &[&[u8]]) -> (usize, usize) {
+fn find_start(grid: &[&[u8]]) -> Result<(usize, usize)> {
     (0..grid.len())
         .cartesian_product(0..grid[0].len())
         .find(|(row, col)| grid[*row][*col] == S)
-        .expect("start should exits")
+        .ok_or_else(|| anyhow!("no start"))
 }
 
-fn trace_loop(grid: &[&[u8]], start_coord: Coord) -> (u64, HashSet<Coord>) {
+fn trace_loop(grid: &[&[u8]], start_coord: Coord) -> Result<(u64, HashSet<Coord>)> {
     let mut cur = start_coord;
     let mut steps = 0;
     let mut from = None;
     let mut loop_coords = HashSet::new();
 
     loop {
-        let (new_cur, new_from) = walk(grid, cur, from);
+        let (new_cur, new_from) = walk(grid, cur, from)?;
         steps += 1;
         loop_coords.insert(cur);
         cur = new_cur;
@@ -74,11 +112,15 @@ fn trace_loop(grid: &[&[u8]], start_coord: Coord) -> (u64, HashSet<Coord>) {
         }
     }
 
-    (steps, loop_coords)
+    Ok((steps, loop_coords))
 }
 
 // walk anticlockwise, up first
-fn walk(grid: &[&[u8]], pos: Coord, from: Option<Direction>) -> ((usize, usize), Direction) {
+fn walk(
+    grid: &[&[u8]],
+    pos: Coord,
+    from: Option<Direction>,
+) -> Result<((usize, usize), Direction)> {
     Direction::ALL
         .into_iter()
         // do not walk back to prev pos
@@ -97,12 +139,29 @@ fn walk(grid: &[&[u8]], pos: Coord, from: Option<Direction>) -> ((usize, usize),
         })
         .map(|(dir, nbr_coord)| (nbr_coord, dir.opposite()))
         .next()
-        .expect("connected nbr exists")
+        .ok_or_else(|| anyhow!("the loop breaks off at {pos:?}"))
 }
 // endregion: --- Part 1
 
 // region:    --- Part 2
 
+// the pipe connecting the start to its two neighbours on the loop
+fn start_pipe(grid: &[&[u8]], loop_coords: &HashSet<Coord>, start_coord: Coord) -> PipeType {
+    let dirs = Direction::ALL
+        .into_iter()
+        .filter(|dir| {
+            neighbour_coord_in_direction(*dir, start_coord, grid.len(), grid[0].len())
+                .filter(|nbr_coord| loop_coords.contains(nbr_coord))
+                .is_some_and(|(row, col)| connected(S, grid[row][col], *dir))
+        })
+        .collect_vec();
+
+    [PIPE, DASH, F, L, C7, J]
+        .into_iter()
+        .find(|pipe| dirs.iter().all(|dir| can_connect_to(*pipe).contains(dir)))
+        .unwrap_or(S)
+}
+
 fn is_inside(grid: &[&[u8]], loop_coords: &HashSet<Coord>, (row, col): Coord) -> bool {
     // count all PIPE, F, L, 7, J on the right of grid[row][col]
     let mut counts = (col + 1..grid[0].len())
@@ -134,11 +193,11 @@ mod tests {
         let grid: Vec<&[u8]> = include_bytes!("../test-1.txt")
             .split(|b| *b == NEWLINE)
             .collect();
-        assert_eq!((1, 1), find_start(&grid));
+        assert_eq!((1, 1), find_start(&grid).unwrap());
 
         let grid: Vec<&[u8]> = include_bytes!("../test-2.txt")
             .split(|b| *b == NEWLINE)
             .collect();
-        assert_eq!((2, 0), find_start(&grid));
+        assert_eq!((2, 0), find_start(&grid).unwrap());
     }
 }
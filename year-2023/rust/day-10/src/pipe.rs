@@ -9,6 +9,7 @@ pub const J: u8 = b'J';
 pub const DASH: u8 = b'-';
 pub const PIPE: u8 = b'|';
 pub const DOT: u8 = b'.';
+pub const TILES: [u8; 8] = [S, F, L, C7, J, DASH, PIPE, DOT];
 
 pub type PipeType = u8;
 
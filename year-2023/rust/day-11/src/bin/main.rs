@@ -1,12 +1,13 @@
+use day_11::params;
 use day_11::part1;
 use day_11::part2;
 
 fn main() {
-    let result = part1(include_bytes!("../../input.txt"));
+    let result = part1(aoc_core::input!().as_bytes());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_bytes!("../../input.txt"), 1_000_000);
+    let result = part2(aoc_core::input!().as_bytes(), &params());
 
     println!("Part 2: {result:?}");
 }
@@ -1,7 +1,7 @@
 use day_11::part1;
 
 fn main() {
-    let result = part1(include_bytes!("../../input.txt")).expect("Part 1 failed to run");
+    let result = part1(aoc_core::input!().as_bytes()).expect("Part 1 failed to run");
 
     println!("{result}");
 }
@@ -17,7 +17,7 @@ mod tests {
 
     #[rstest]
     #[case(374)]
-    fn test_using_test_input_1(#[case] expected: u64) {
+    fn test_using_test_input_1(#[case] expected: u128) {
         let result = part1(include_bytes!("../../test-1.txt")).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
@@ -1,7 +1,8 @@
+use day_11::params;
 use day_11::part2;
 
 fn main() {
-    let result = part2(include_bytes!("../../input.txt"), 1_000_000).expect("Part 2 failed to run");
+    let result = part2(aoc_core::input!().as_bytes(), &params()).expect("Part 2 failed to run");
 
     println!("{result}");
 }
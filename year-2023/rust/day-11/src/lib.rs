@@ -1,56 +1,107 @@
 use std::cmp::{max, min};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use aoc_core::Params;
 
-pub fn part1(input: &[u8]) -> Result<u64> {
-    let mut universe = Galaxy::new(input.split(|b| *b == b'\n').collect());
+// region:    --- Params
 
-    universe.expand();
+// every empty row or column of part 2 is replaced by scale of them
+pub fn params() -> Params {
+    Params::new().with("scale", 1_000_000)
+}
+
+pub fn example_params() -> Params {
+    Params::new().with("scale", 100)
+}
 
-    let galaxies: Vec<Coord> = universe.find_galaxies();
+// endregion: --- Params
 
-    let mut sum = 0;
-    for g1 in 0..galaxies.len() {
-        for g2 in g1 + 1..galaxies.len() {
-            sum += universe.distance_between(galaxies[g1], galaxies[g2], 1);
-        }
+pub fn parse(input: &[u8]) -> Result<Universe> {
+    let mut grid = input
+        .split(|b| *b == b'\n')
+        .map(<[u8]>::to_vec)
+        .collect::<Vec<_>>();
+    // the line end of the last row
+    if grid.len() > 1 && grid.last().is_some_and(Vec::is_empty) {
+        grid.pop();
     }
 
-    Ok(sum)
-}
+    let width = grid[0].len();
+    if grid.iter().any(|row| row.len() != width) {
+        return Err(anyhow!("parse error: the rows are not all as long"));
+    }
+    if let Some(pixel) = grid.iter().flatten().find(|pixel| !b".#".contains(pixel)) {
+        return Err(anyhow!(
+            "parse error: unknown pixel {:?}",
+            char::from(*pixel)
+        ));
+    }
 
-pub fn part2(input: &[u8], scale_factor: u64) -> Result<u64> {
-    let mut universe = Galaxy::new(input.split(|b| *b == b'\n').collect());
+    let mut universe = Universe::new(grid);
 
     universe.expand();
 
-    let galaxies: Vec<Coord> = universe.find_galaxies();
+    Ok(universe)
+}
+
+pub fn part1(input: &[u8]) -> Result<u128> {
+    parse(input)?.part1()
+}
 
-    let mut sum = 0;
-    for g1 in 0..galaxies.len() {
-        for g2 in g1 + 1..galaxies.len() {
-            sum += universe.distance_between(galaxies[g1], galaxies[g2], scale_factor - 1);
+pub fn part2(input: &[u8], params: &Params) -> Result<u128> {
+    parse(input)?.part2(params)
+}
+
+impl Universe {
+    pub fn part1(&self) -> Result<u128> {
+        let galaxies: Vec<Coord> = self.find_galaxies();
+
+        let mut sum = 0;
+        for g1 in 0..galaxies.len() {
+            for g2 in g1 + 1..galaxies.len() {
+                sum += self.distance_between(galaxies[g1], galaxies[g2], 1);
+            }
         }
+
+        Ok(sum)
     }
 
-    Ok(sum)
+    // a big enough scale takes the distances past u64
+    pub fn part2(&self, params: &Params) -> Result<u128> {
+        let scale_factor = params.get::<u128>("scale")?;
+        if scale_factor == 0 {
+            return Err(anyhow!("scale should be at least 1"));
+        }
+
+        let galaxies: Vec<Coord> = self.find_galaxies();
+
+        let mut sum = 0;
+        for g1 in 0..galaxies.len() {
+            for g2 in g1 + 1..galaxies.len() {
+                sum += self.distance_between(galaxies[g1], galaxies[g2], scale_factor - 1);
+            }
+        }
+
+        Ok(sum)
+    }
 }
 
 type Coord = (usize, usize);
 
 fn manhatten_distance(c1: Coord, c2: Coord) -> u64 {
-    ((c2.0 as isize - c1.0 as isize).abs() + (c2.1 as isize - c1.1 as isize).abs()) as u64
+    (c1.0.abs_diff(c2.0) + c1.1.abs_diff(c2.1)) as u64
 }
 
-#[derive(Debug)]
-struct Galaxy<'a> {
-    grid: Vec<&'a [u8]>,
+/// The image of the universe, with the rows and the columns that expand as it is observed.
+#[derive(Debug, Clone)]
+pub struct Universe {
+    grid: Vec<Vec<u8>>,
     rows_expanded: Vec<usize>,
     cols_expanded: Vec<usize>,
 }
 
-impl<'a> Galaxy<'a> {
-    fn new(grid: Vec<&'a [u8]>) -> Self {
+impl Universe {
+    fn new(grid: Vec<Vec<u8>>) -> Self {
         Self {
             grid,
             rows_expanded: Vec::new(),
@@ -103,15 +154,15 @@ impl<'a> Galaxy<'a> {
             .collect()
     }
 
-    fn distance_between(&self, galaxy1: Coord, galaxy2: Coord, scale: u64) -> u64 {
-        manhatten_distance(galaxy1, galaxy2)
+    fn distance_between(&self, galaxy1: Coord, galaxy2: Coord, scale: u128) -> u128 {
+        u128::from(manhatten_distance(galaxy1, galaxy2))
             + (min(galaxy1.0, galaxy2.0) + 1..max(galaxy1.0, galaxy2.0))
                 .filter(|row| self.rows_expanded.contains(row))
-                .count() as u64
+                .count() as u128
                 * scale
             + (min(galaxy1.1, galaxy2.1) + 1..max(galaxy1.1, galaxy2.1))
                 .filter(|col| self.cols_expanded.contains(col))
-                .count() as u64
+                .count() as u128
                 * scale
     }
 }
@@ -141,12 +192,7 @@ mod tests {
     #[case(false, 1)]
     #[case(true, 3)]
     fn test_expand_row(#[case] expected: bool, #[case] row: usize) {
-        let mut universe = Galaxy::new(
-            include_bytes!("../test-1.txt")
-                .split(|b| *b == b'\n')
-                .collect(),
-        );
-        universe.expand();
+        let universe = parse(include_bytes!("../test-1.txt")).unwrap();
 
         assert_eq!(expected, universe.rows_expanded.contains(&row))
     }
@@ -156,12 +202,7 @@ mod tests {
     #[case(false, 1)]
     #[case(true, 2)]
     fn test_expand_col(#[case] expected: bool, #[case] col: usize) {
-        let mut universe = Galaxy::new(
-            include_bytes!("../test-1.txt")
-                .split(|b| *b == b'\n')
-                .collect(),
-        );
-        universe.expand();
+        let universe = parse(include_bytes!("../test-1.txt")).unwrap();
 
         assert_eq!(expected, universe.cols_expanded.contains(&col))
     }
@@ -170,16 +211,11 @@ mod tests {
     #[case(15, (0, 3), (8, 7))]
     #[case(9, (5, 1), (9, 4))]
     fn test_distance_after_expansion(
-        #[case] expected: u64,
+        #[case] expected: u128,
         #[case] galaxy1: Coord,
         #[case] galaxy2: Coord,
     ) {
-        let mut universe = Galaxy::new(
-            include_bytes!("../test-1.txt")
-                .split(|b| *b == b'\n')
-                .collect(),
-        );
-        universe.expand();
+        let universe = parse(include_bytes!("../test-1.txt")).unwrap();
 
         assert_eq!(expected, universe.distance_between(galaxy1, galaxy2, 1))
     }
@@ -187,10 +223,15 @@ mod tests {
     #[rstest]
     #[case(1030, 10)]
     #[case(8410, 100)]
-    fn test_distance_after_expansion_scaled(#[case] expected: u64, #[case] scale: u64) {
+    #[case(82_000_000_000_210, 1_000_000_000_000)]
+    fn test_distance_after_expansion_scaled(#[case] expected: u128, #[case] scale: u128) {
         assert_eq!(
             expected,
-            part2(include_bytes!("../test-1.txt"), scale).unwrap()
+            part2(
+                include_bytes!("../test-1.txt"),
+                &example_params().with("scale", scale)
+            )
+            .unwrap()
         )
     }
 }
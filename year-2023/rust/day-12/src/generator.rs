@@ -0,0 +1,94 @@
+// region:    --- Modules
+use aoc_core::rng;
+use itertools::Itertools;
+use rand::Rng;
+// endregion: --- Modules
+
+/// How big a generated list of springs is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub rows: usize,
+    // the longest row, the others are shorter
+    pub width: usize,
+    // chance of a spring being unknown
+    pub unknown: f64,
+}
+
+impl Default for Size {
+    // close to the real input
+    fn default() -> Self {
+        Self {
+            rows: 1000,
+            width: 20,
+            unknown: 0.5,
+        }
+    }
+}
+
+/// Rows of springs, the same for the same seed.
+///
+/// Each row is made from a known arrangement whose springs are then partly hidden, so every
+/// row has at least one arrangement.
+pub fn generate(seed: u64, size: &Size) -> String {
+    let mut rng = rng::seeded(seed);
+    let unknown = size.unknown.clamp(0.0, 1.0);
+
+    (0..size.rows.max(1))
+        .map(|_| {
+            let width = rng.gen_range(1..=size.width.max(1));
+            let mut springs = (0..width)
+                .map(|_| if rng.gen_bool(0.5) { b'#' } else { b'.' })
+                .collect::<Vec<_>>();
+            // a row has at least one group of damaged springs
+            if !springs.contains(&b'#') {
+                springs[rng.gen_range(0..width)] = b'#';
+            }
+
+            let groups = springs
+                .iter()
+                .group_by(|&&spring| spring)
+                .into_iter()
+                .filter(|(spring, _)| *spring == b'#')
+                .map(|(_, group)| group.count().to_string())
+                .join(",");
+            let row = springs
+                .into_iter()
+                .map(|spring| {
+                    if rng.gen_bool(unknown) {
+                        '?'
+                    } else {
+                        spring as char
+                    }
+                })
+                .collect::<String>();
+
+            format!("{row} {groups}")
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{part1, part2};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_generate() {
+        let size = Size {
+            rows: 20,
+            width: 12,
+            unknown: 0.5,
+        };
+        let input = generate(7, &size);
+
+        assert_eq!(input, generate(7, &size));
+        assert_ne!(input, generate(8, &size));
+        // every row has the arrangement it was made from
+        assert!(part1(&input).unwrap() >= 20);
+        assert!(part2(&input).unwrap() >= 20);
+    }
+}
@@ -1,6 +1,5 @@
 // region:    --- Modules
-#![allow(unstable_name_collisions)]
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use cached::proc_macro::cached;
 use itertools::Itertools;
 use parser::{parse_line, parse_line2};
@@ -9,41 +8,69 @@ use token::Token;
 // endregion: --- Modules
 
 mod cacher;
+pub mod generator;
 mod parser;
 mod token;
 
-pub fn part1(input: &str) -> Result<u64> {
-    let sum = input
+/// The condition records, the springs of each row and the sizes of its damaged groups.
+#[derive(Debug, Clone)]
+pub struct ConditionRecords {
+    // as part 1 reads them, springs as bytes
+    rows: Vec<(Vec<u8>, Vec<u32>)>,
+    // and as part 2 does, springs as tokens
+    token_rows: Vec<(Vec<Token>, Vec<u8>)>,
+}
+
+pub fn parse(input: &str) -> Result<ConditionRecords> {
+    let (rows, token_rows) = input
         .split("\n")
         .map(|line| {
-            let (_, (space, arrangement)) = parse_line(line).expect("parse correct");
-
-            constrainted_arrangement(&space, &arrangement)
+            let (_, row) = parse_line(line).map_err(|err| anyhow!("parse error: {err}"))?;
+            let (_, token_row) = parse_line2(line).map_err(|err| anyhow!("parse error: {err}"))?;
+            Ok((row, token_row))
         })
-        .sum::<u64>();
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
 
-    Ok(sum)
+    Ok(ConditionRecords { rows, token_rows })
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u64> {
-    let sum = input
-        .split("\n")
-        .map(|line| {
-            let (_, (tokens, seq)) = parse_line2(line).expect("parse correct");
+    parse(input)?.part2()
+}
+
+impl ConditionRecords {
+    pub fn part1(&self) -> Result<u64> {
+        Ok(self
+            .rows
+            .iter()
+            .map(|(space, arrangement)| constrainted_arrangement(space, arrangement))
+            .sum())
+    }
 
-            // expand space
-            let tokens = iter::repeat(tokens)
-                .take(5)
-                .intersperse(vec![Token::Uncertain])
+    pub fn part2(&self) -> Result<u64> {
+        Ok(self
+            .token_rows
+            .iter()
+            .map(|(tokens, seq)| {
+                // expand space, `Itertools::intersperse` as the one of `Iterator` is not stable
+                let tokens = Itertools::intersperse(
+                    iter::repeat_n(tokens.clone(), 5),
+                    vec![Token::Uncertain],
+                )
                 .flatten()
                 .collect_vec();
-            let seq = iter::repeat(seq).take(5).flatten().collect_vec();
-
-            combinations(tokens, seq)
-        })
-        .sum::<u64>();
+                let seq = iter::repeat_n(seq.clone(), 5).flatten().collect_vec();
 
-    Ok(sum)
+                combinations(tokens, seq)
+            })
+            .sum())
+    }
 }
 
 // region:    --- Part 1
@@ -69,14 +96,12 @@ fn can_substitute(space: &[u8], hash_len: usize) -> (bool, usize) {
 }
 
 // find how many possible combinations for the sequential arrangement in free space
-// TODO: dynamic programming possible
-// static mut RESULT: OnceLock<HashMap<(u32, Vec<u32>), u64>> = OnceLock::new();
+// cached as the recursion revisits the same free space and arrangement tail exponentially often
+#[cached(
+    key = "(u32, Vec<u32>)",
+    convert = r#"{ (free_space, arrangement.to_vec()) }"#
+)]
 fn free_arrangement(free_space: u32, arrangement: &[u32]) -> u64 {
-    // let map = unsafe { RESULT.get_or_init(|| HashMap::new()) };
-
-    // match map.get(&(free_space, arrangement.to_vec())) {
-    //     Some(result) => return *result,
-    //     None => {
     // early return for zero cases
     if arrangement.len() == 0 {
         return 1;
@@ -84,14 +109,16 @@ fn free_arrangement(free_space: u32, arrangement: &[u32]) -> u64 {
 
     // works for free space is zero or > 0
     // early return for inadequate space
-    if free_space < arrangement.iter().sum::<u32>() + arrangement.len() as u32 - 1 {
+    let needed_space =
+        arrangement.iter().map(|&len| u64::from(len)).sum::<u64>() + arrangement.len() as u64 - 1;
+    if u64::from(free_space) < needed_space {
         return 0;
     }
 
     // We pick the first cell(s) to be either . or # and consume the arrangement
     // remember after a cell as need a . for separation
     // we recursively count the remaining possbility
-    let result = free_arrangement(
+    free_arrangement(
         free_space - arrangement[0] - {
             if arrangement.len() == 1 {
                 0
@@ -100,16 +127,12 @@ fn free_arrangement(free_space: u32, arrangement: &[u32]) -> u64 {
             }
         },
         &arrangement[1..],
-    ) + free_arrangement(free_space - 1, arrangement);
-
-    // let mut_map = unsafe { RESULT.get_mut().unwrap() };
-    // mut_map
-    //     .entry((free_space, arrangement.to_vec()))
-    //     .or_insert(result);
-
-    result
-    // }
-    // }
+    ) + if free_space > 0 {
+        // an arrangement of empty groups fits in no space at all
+        free_arrangement(free_space - 1, arrangement)
+    } else {
+        0
+    }
 }
 
 // find combinations but this time we have some predefined . or #
@@ -130,30 +153,15 @@ fn constrainted_arrangement(space: &[u8], arrangement: &[u32]) -> u64 {
     if space[0] == b'.' {
         constrainted_arrangement(&space[1..], arrangement)
     } else if space[0] == b'?' {
-        // let (can_sub, sub_count) = can_substitute(space, arrangement[0] as usize);
-        // // then we substitute the first cells with . or with #
-        // constrainted_arrangement(&space[1..], arrangement)
-        //     + if can_sub {
-        //         constrainted_arrangement(&space[sub_count..], &arrangement[1..])
-        //     } else {
-        //         0
-        //     }
-
-        // we take as many ? spaces as we can until we reach . or one cell before #
-        // then the combinations will be
-        // max(?subspce, partial arrange * constrainted_arrangement(rest_space, rest_arrangement))
-
-        let n = space.iter().take_while(|&&b| b == b'?').count();
-        // // space cant be all ? otherwise it will be short circuiteed
-        let free_space_count = if space[n] == b'#' { n - 1 } else { n };
-
-        (0..arrangement.len())
-            .map(|cut| {
-                free_arrangement(free_space_count as u32, &arrangement[0..cut])
-                    * constrainted_arrangement(&space[free_space_count..], &arrangement[cut..])
-            })
-            .max()
-            .unwrap()
+        // taking a run of ? as free space loops on `?#`, where the run is empty,
+        // so the first cell is substituted with . or with # instead
+        let (can_sub, sub_count) = can_substitute(space, arrangement[0] as usize);
+        constrainted_arrangement(&space[1..], arrangement)
+            + if can_sub {
+                constrainted_arrangement(&space[sub_count..], &arrangement[1..])
+            } else {
+                0
+            }
     } else {
         let (can_sub, sub_count) = can_substitute(space, arrangement[0] as usize);
         // then we substitute the first cells with #
@@ -455,7 +463,12 @@ mod tests {
     use rstest::rstest;
     use rstest_reuse::{self, *};
 
-    use crate::parser::tokens;
+    use aoc_core::differential::{self, without_one};
+
+    use crate::{
+        generator::{self, Size},
+        parser::tokens,
+    };
 
     use super::*;
 
@@ -481,6 +494,22 @@ mod tests {
         assert_eq!(expected, combinations(tokens, seq))
     }
 
+    #[rstest]
+    #[case(1, "???.### 1,1,3")]
+    #[case(4, ".??..??...?##. 1,1,3")]
+    #[case(1, "?#?#?#?#?#?#?#? 1,3,1,6")]
+    #[case(1, "????.#...#... 4,1,1")]
+    #[case(4, "????.######..#####. 1,6,5")]
+    #[case(10, "?###???????? 3,2,1")]
+    #[case(1, "?# 1")]
+    #[case(1, "??# 2")]
+    #[case(0, "?#. 3")]
+    fn test_constrainted_arrangement(#[case] expected: u64, #[case] fixture: &str) {
+        let (_, (space, arrangement)) = parse_line(fixture).expect("parse ok");
+
+        assert_eq!(expected, constrainted_arrangement(&space, &arrangement))
+    }
+
     #[rstest]
     #[case(3, "###....")]
     #[case(4, "###.####..#")]
@@ -561,7 +590,8 @@ mod tests {
     #[case(1,3,&[3])]
     #[case(1,3,&[1, 1])]
     #[case(0,3,&[1, 2])]
-    #[case(0,100,&[1, 1, 1, 1, 1,1, 1, 1, 1, 1, 1, 1, 1, 1])]
+    // 14 groups of 1 in 100 cells, choose the 14 cells among 87
+    #[case(5408707663065150,100,&[1, 1, 1, 1, 1,1, 1, 1, 1, 1, 1, 1, 1, 1])]
     fn test_free_arrangement(
         #[case] expected: u64,
         #[case] free_space: u32,
@@ -591,6 +621,91 @@ mod tests {
         assert_eq!(expected, can_substitute(space, hash_len))
     }
 
+    type Row = (Vec<u8>, Vec<u32>);
+
+    // every way to fill in the unknown springs, checked against the groups
+    fn brute_force((springs, groups): &Row) -> u64 {
+        let unknown = springs
+            .iter()
+            .positions(|&spring| spring == b'?')
+            .collect_vec();
+
+        (0..1u32 << unknown.len())
+            .filter(|mask| {
+                let mut filled = springs.clone();
+                for (bit, &idx) in unknown.iter().enumerate() {
+                    filled[idx] = if mask & 1 << bit == 0 { b'.' } else { b'#' };
+                }
+                let filled_groups = filled
+                    .split(|&spring| spring != b'#')
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len() as u32)
+                    .collect_vec();
+                filled_groups == *groups
+            })
+            .count() as u64
+    }
+
+    fn rows(seed: u64) -> Vec<Row> {
+        let size = Size {
+            rows: 300,
+            width: 12,
+            unknown: 0.5,
+        };
+        generator::generate(seed, &size)
+            .lines()
+            .map(|line| {
+                let (_, (springs, groups)) = parse_line(line).expect("parse ok");
+                (springs, groups)
+            })
+            .collect()
+    }
+
+    // a spring less, or a group less
+    fn smaller((springs, groups): &Row) -> Vec<Row> {
+        without_one(springs)
+            .into_iter()
+            .map(|springs| (springs, groups.clone()))
+            .chain(
+                without_one(groups)
+                    .into_iter()
+                    .map(|groups| (springs.clone(), groups)),
+            )
+            .collect()
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn test_differential_constrainted_arrangement(#[case] seed: u64) {
+        let fast = |(springs, groups): &Row| constrainted_arrangement(springs, groups);
+
+        if let Err(counterexample) = differential::check(rows(seed), brute_force, fast, smaller) {
+            panic!("{counterexample}");
+        }
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn test_differential_combinations(#[case] seed: u64) {
+        let fast = |(springs, groups): &Row| {
+            let tokens = springs
+                .iter()
+                .map(|&spring| match spring {
+                    b'.' => Token::Empty,
+                    b'#' => Token::Block,
+                    _ => Token::Uncertain,
+                })
+                .collect();
+            combinations(tokens, groups.iter().map(|&len| len as u8).collect())
+        };
+
+        if let Err(counterexample) = differential::check(rows(seed), brute_force, fast, smaller) {
+            panic!("{counterexample}");
+        }
+    }
+
     #[template]
     #[rstest]
     #[case(0, " 3,2,1")]
@@ -1,9 +1,11 @@
 use nom::{
-    bytes::complete::{is_a, tag, take_till},
-    character::complete,
-    multi::separated_list1,
+    branch::alt,
+    bytes::complete::{tag, take_till},
+    character::complete::{self, char},
+    combinator::value,
+    multi::{many1, separated_list1},
     sequence::separated_pair,
-    IResult, Parser,
+    IResult,
 };
 
 use crate::token::Token;
@@ -21,20 +23,11 @@ pub fn parse_line(input: &str) -> IResult<&str, (Vec<u8>, Vec<u32>)> {
 
 // part 2
 pub fn tokens(input: &str) -> IResult<&str, Vec<Token>> {
-    is_a(".?#")
-        .map(|string: &str| {
-            string
-                .chars()
-                .into_iter()
-                .map(|c| match c {
-                    '.' => Token::Empty,
-                    '#' => Token::Block,
-                    '?' => Token::Uncertain,
-                    _ => unreachable!("invalid character"),
-                })
-                .collect::<Vec<Token>>()
-        })
-        .parse(input)
+    many1(alt((
+        value(Token::Empty, char('.')),
+        value(Token::Block, char('#')),
+        value(Token::Uncertain, char('?')),
+    )))(input)
 }
 
 pub fn parse_line2(input: &str) -> IResult<&str, (Vec<Token>, Vec<u8>)> {
@@ -2,11 +2,11 @@ use day_13::part1;
 use day_13::part2;
 
 fn main() {
-    let result = part1(include_bytes!("../../input.txt"));
+    let result = part1(aoc_core::input!().as_bytes());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_bytes!("../../input.txt"));
+    let result = part2(aoc_core::input!().as_bytes());
 
     println!("Part 2: {result:?}");
 }
@@ -6,23 +6,70 @@ use nom::{
     multi::{many1, separated_list1},
     IResult,
 };
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-pub fn part1(input: &[u8]) -> Result<u64> {
-    let (_, maps) = parse_file(input).expect("parse file ok");
+/// The rows of each pattern of ash and rocks, in the order of the notes.
+#[derive(Debug, Clone)]
+pub struct Patterns(pub Vec<Vec<Vec<u8>>>);
 
-    Ok(maps
+pub fn parse(input: &[u8]) -> Result<Patterns> {
+    let (_, maps) = parse_file(input).map_err(|err| anyhow!("parse error: {err:?}"))?;
+    if maps
         .iter()
-        .map(|m| {
-            find_horizontal_mirror(m).unwrap_or(0) * 100 + find_vertical_mirror(m).unwrap_or(0)
-        })
-        .sum::<usize>() as u64)
+        .any(|map| map.iter().any(|row| row.len() != map[0].len()))
+    {
+        return Err(anyhow!(
+            "parse error: the rows of a pattern are not all as long"
+        ));
+    }
+
+    Ok(Patterns(
+        maps.into_iter()
+            .map(|map| map.into_iter().map(<[u8]>::to_vec).collect())
+            .collect(),
+    ))
+}
+
+pub fn part1(input: &[u8]) -> Result<u64> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &[u8]) -> Result<u64> {
-    let (_, maps) = parse_file(input).expect("parse file ok");
+    parse(input)?.part2()
+}
 
-    Ok(maps
-        .iter()
+impl Patterns {
+    fn maps(&self) -> Vec<Map<'_>> {
+        self.0
+            .iter()
+            .map(|map| map.iter().map(Vec::as_slice).collect())
+            .collect()
+    }
+
+    pub fn part1(&self) -> Result<u64> {
+        Ok(self
+            .maps()
+            .iter()
+            .map(|m| {
+                find_horizontal_mirror(m).unwrap_or(0) * 100 + find_vertical_mirror(m).unwrap_or(0)
+            })
+            .sum::<usize>() as u64)
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        part2_of(&self.maps())
+    }
+}
+
+fn part2_of(maps: &[Map]) -> Result<u64> {
+    // each map has its own smudge
+    #[cfg(feature = "parallel")]
+    let maps_iter = maps.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let maps_iter = maps.iter();
+
+    let sum = maps_iter
         .enumerate()
         .map(|(id, m)| {
             let orig_hori = find_horizontal_mirror(m);
@@ -46,29 +93,29 @@ pub fn part2(input: &[u8]) -> Result<u64> {
                         && (new_hori, new_vert) != (orig_hori, orig_vert)
                     {
                         let Some(new_hori) = new_hori else {
-                            return new_vert.unwrap();
+                            return new_vert.ok_or_else(|| anyhow!("no mirror"));
                         };
 
                         let Some(new_vert) = new_vert else {
-                            return new_hori * 100;
+                            return Ok(new_hori * 100);
                         };
 
-                        return if Some(new_hori) == orig_hori {
+                        return Ok(if Some(new_hori) == orig_hori {
                             new_vert
                         } else {
                             new_hori * 100
-                        };
+                        });
                     }
 
                     toggle(&mut map, row, col);
                 }
             }
 
-            // println!("{id}");
-            panic!("should always find one smudge")
-            // 0
+            Err(anyhow!("pattern {id} has no smudge"))
         })
-        .sum::<usize>() as u64)
+        .sum::<Result<usize>>()?;
+
+    Ok(sum as u64)
 }
 
 // region:    --- Parsing
@@ -141,7 +188,7 @@ fn toggle(map: &mut [Vec<u8>], row: usize, col: usize) {
     match map[row][col] {
         b'.' => map[row][col] = b'#',
         b'#' => map[row][col] = b'.',
-        _ => panic!("imp byte"),
+        _ => unreachable!("the parser only keeps . and #"),
     }
 }
 
@@ -180,9 +227,9 @@ mod tests {
 
     #[test]
     fn quick_test() {
-        let input = include_bytes!("../input.txt");
+        let input = aoc_core::input!();
 
-        let (_, maps) = parse_file(input).expect("parse ok");
+        let (_, maps) = parse_file(input.as_bytes()).expect("parse ok");
 
         println!(
             "{:?}",
@@ -0,0 +1,14 @@
+use aoc_visual::Animation;
+use day_14::{palette, spin_cycle_frames};
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or("day-14.gif".to_string());
+
+    let mut animation = Animation::new(palette()).delay(5);
+    for frame in spin_cycle_frames(aoc_core::input!().as_bytes(), 50) {
+        animation.push(frame.scaled(4));
+    }
+    animation.save(&path).expect("GIF written");
+
+    println!("{path}");
+}
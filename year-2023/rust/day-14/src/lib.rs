@@ -1,15 +1,51 @@
 #![allow(unused)]
 
 use anyhow::{anyhow, Result};
+use aoc_core::{cancel, debug};
+use aoc_visual::{Frame, Palette};
 use itertools::Itertools;
 use nom::bytes::complete::is_a;
 use nom::bytes::complete::tag;
 use nom::multi::separated_list1;
 use nom::IResult;
 use std::collections::HashMap;
+
+/// The rows of the platform as it is before any tilt, rounded rocks, cube rocks and space.
+#[derive(Debug, Clone)]
+pub struct Platform(pub Vec<Vec<u8>>);
+
+pub fn parse(input: &[u8]) -> Result<Platform> {
+    let (_, rows) = parse_map(input).map_err(|err| anyhow!("parse error: {err:?}"))?;
+    if rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err(anyhow!("parse error: the rows are not all as long"));
+    }
+
+    Ok(Platform(rows.into_iter().map(<[u8]>::to_vec).collect()))
+}
+
 pub fn part1(input: &[u8]) -> Result<u64> {
-    let (_, input) = parse_map(input).expect("parse ok");
+    parse(input)?.part1()
+}
+
+pub fn part2(input: &[u8]) -> Result<u64> {
+    parse(input)?.part2()
+}
+
+impl Platform {
+    fn map(&self) -> Map<'_> {
+        self.0.iter().map(Vec::as_slice).collect()
+    }
+
+    pub fn part1(&self) -> Result<u64> {
+        part1_of(&self.map())
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        part2_of(&self.map())
+    }
+}
 
+fn part1_of(input: &Map) -> Result<u64> {
     let mut grid = Grid(vec![vec![Rock::Empty; input.len() + 1]; input[0].len()]);
 
     for row in 0..input[0].len() {
@@ -53,29 +89,8 @@ pub fn part1(input: &[u8]) -> Result<u64> {
     Ok(score)
 }
 
-pub fn part2(input: &[u8]) -> Result<u64> {
-    let (_, input) = parse_map(input).expect("parse ok");
-
-    let mut grid = Grid(vec![vec![Rock::Empty; input.len() + 2]; input[0].len() + 2]);
-    let grid_width = input.len() + 2;
-    let grid_height = input[0].len() + 2;
-
-    // fill first and last column with Cube
-    for row in 0..grid_height {
-        grid.0[row][grid_width - 1] = Rock::Cube;
-    }
-    for row in 0..grid_height {
-        grid.0[row][0] = Rock::Cube;
-    }
-    // fill first and last row with Cube
-    grid.0[0].fill(Rock::Cube);
-    grid.0[grid_height - 1].fill(Rock::Cube);
-
-    for (to_col, from_row) in (0..input.len()).rev().enumerate() {
-        for idx in 0..input[0].len() {
-            grid.0[idx + 1][to_col + 1] = input[from_row][idx].into();
-        }
-    }
+fn part2_of(input: &Map) -> Result<u64> {
+    let mut grid = Grid::padded(input);
     // println!("{}", grid);
 
     let mut patterns = HashMap::new();
@@ -83,6 +98,7 @@ pub fn part2(input: &[u8]) -> Result<u64> {
     let mut cycle_end = 0;
     for cycle_count in 1..=1000000000 {
         // dbg!(cycle_count);
+        cancel::check()?;
         grid.cycle();
 
         // let summary = grid.summary();
@@ -96,22 +112,45 @@ pub fn part2(input: &[u8]) -> Result<u64> {
         }
         // println!("{}", grid);
     }
-    dbg!(cycle_end);
-    //
-    dbg!(cycle);
     let cycle_begin = cycle_end - cycle;
-    dbg!(cycle_begin + (1000000000 - cycle_end) % cycle);
+    debug!("cycle of {cycle} spins, repeating from spin {cycle_begin}");
 
     let end_grid = patterns
         .iter()
         .filter(|(grid, c)| **c == cycle_begin + (1000000000 - cycle_end) % cycle)
         .exactly_one()
-        .expect("!")
+        .map_err(|_| anyhow!("no spin in the cycle lands on the last one"))?
         .0;
 
     Ok(end_grid.score())
 }
 
+// region:    --- Visualisation
+
+// Empty, Round and Cube, in the order of the Rock discriminants
+pub fn palette() -> Palette {
+    Palette::new(&[[20, 20, 35], [235, 200, 120], [95, 95, 115]]).expect("valid palette")
+}
+
+// one frame after every tilt of the spin cycle, starting with the untouched platform
+pub fn spin_cycle_frames(input: &[u8], cycles: usize) -> Vec<Frame> {
+    let (_, input) = parse_map(input).expect("parse ok");
+
+    let mut grid = Grid::padded(&input);
+    let mut frames = vec![grid.frame(0)];
+    for _ in 0..cycles {
+        for rotations in 0..4 {
+            grid.rolling();
+            frames.push(grid.frame(rotations));
+            grid.rotate();
+        }
+    }
+
+    frames
+}
+
+// endregion: --- Visualisation
+
 // region:    --- Parsing
 type Map<'a> = Vec<&'a [u8]>;
 
@@ -157,6 +196,33 @@ impl std::fmt::Display for Rock {
 struct Grid(Vec<Vec<Rock>>);
 
 impl Grid {
+    // the map rotated clockwise, so rolling along a row tilts it north,
+    // surrounded by a border of cubes.
+    fn padded(input: &Map) -> Self {
+        let mut grid = Grid(vec![vec![Rock::Empty; input.len() + 2]; input[0].len() + 2]);
+        let grid_width = input.len() + 2;
+        let grid_height = input[0].len() + 2;
+
+        // fill first and last column with Cube
+        for row in 0..grid_height {
+            grid.0[row][grid_width - 1] = Rock::Cube;
+        }
+        for row in 0..grid_height {
+            grid.0[row][0] = Rock::Cube;
+        }
+        // fill first and last row with Cube
+        grid.0[0].fill(Rock::Cube);
+        grid.0[grid_height - 1].fill(Rock::Cube);
+
+        for (to_col, from_row) in (0..input.len()).rev().enumerate() {
+            for (idx, &rock) in input[from_row].iter().enumerate() {
+                grid.0[idx + 1][to_col + 1] = rock.into();
+            }
+        }
+
+        grid
+    }
+
     fn rotate(&mut self) {
         let mut matrix = vec![vec![Rock::Empty; self.0.len()]; self.0[0].len()];
 
@@ -196,6 +262,21 @@ impl Grid {
         self.rotate(); // back to north
     }
 
+    // snapshot in the orientation of the puzzle input, the grid having been rotated
+    // `rotations` times since it was last facing north
+    fn frame(&self, rotations: usize) -> Frame {
+        let mut grid = self.clone();
+        for _ in 0..(4 - rotations % 4) % 4 {
+            grid.rotate();
+        }
+
+        let width = grid.0[0].len();
+        let height = grid.0.len();
+        Frame::from_fn(height - 2, width - 2, |row, col| {
+            grid.0[col + 1][width - 2 - row] as u8
+        })
+    }
+
     // for part 2 only
     fn score(&self) -> u64 {
         let mut score = 0;
@@ -235,4 +316,20 @@ mod tests {
 
     #[test]
     fn quick_test() {}
+
+    #[test]
+    fn test_frames_follow_input_orientation() {
+        let input = include_bytes!("../test-1.txt");
+        let frames = spin_cycle_frames(input, 1);
+
+        assert_eq!(5, frames.len());
+        assert_eq!(
+            Frame::from_rows(&parse_map(input).unwrap().1, |&b| Rock::from(b) as u8),
+            frames[0]
+        );
+
+        // after the north tilt, the top row of the example reads OOOO.#.O..
+        let top_row: Vec<u8> = (0..10).map(|col| frames[1].get(0, col)).collect();
+        assert_eq!(vec![1, 1, 1, 1, 0, 2, 0, 1, 0, 0], top_row);
+    }
 }
@@ -4,11 +4,11 @@ use day_15::part2;
 fn main() {
     // let result = part1(include_bytes!("../../test-1.txt"));
 
-    let result = part1(include_bytes!("../../input.txt"));
+    let result = part1(aoc_core::input!().as_bytes());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_bytes!("../../input.txt"));
+    let result = part2(aoc_core::input!().as_bytes());
 
     println!("Part 2: {result:?}");
 }
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use nom::bytes::complete::is_not;
 use nom::bytes::complete::tag;
@@ -6,24 +6,54 @@ use nom::multi::separated_list1;
 use nom::IResult;
 use std::collections::BTreeMap;
 
-pub fn part1(input: &[u8]) -> Result<u64> {
-    let (_, strings) = parse_file(input).expect("parse ok");
+/// The steps of the initialization sequence, in order.
+#[derive(Debug, Clone)]
+pub struct InitializationSequence(pub Vec<Vec<u8>>);
+
+pub fn parse(input: &[u8]) -> Result<InitializationSequence> {
+    let (_, strings) = parse_file(input).map_err(|err| anyhow!("parse error: {err:?}"))?;
 
-    let sum = strings.iter().fold(0, |acc, string| acc + hash(string));
+    Ok(InitializationSequence(
+        strings.into_iter().map(<[u8]>::to_vec).collect(),
+    ))
+}
 
-    Ok(sum.into())
+pub fn part1(input: &[u8]) -> Result<u64> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &[u8]) -> Result<u64> {
-    let (_, strings) = parse_file(input).expect("parse ok");
+    parse(input)?.part2()
+}
+
+impl InitializationSequence {
+    pub fn part1(&self) -> Result<u64> {
+        let sum = self.0.iter().fold(0, |acc, string| acc + hash(string));
 
+        Ok(sum.into())
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        part2_of(self.0.iter().map(Vec::as_slice))
+    }
+}
+
+fn part2_of<'a>(strings: impl Iterator<Item = &'a [u8]>) -> Result<u64> {
     let mut boxes = BTreeMap::new();
 
-    strings.into_iter().for_each(|string| {
+    for string in strings {
         if string.contains(&b'=') {
             let pair: Vec<_> = string.split(|&character| character == b'=').collect();
             let label = pair[0];
-            let new_focal_len = std::str::from_utf8(pair[1]).unwrap().parse().unwrap();
+            let new_focal_len = std::str::from_utf8(pair[1])
+                .ok()
+                .and_then(|focal_len| focal_len.parse().ok())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "no focal length in {}",
+                        String::from_utf8_lossy(string).trim_end()
+                    )
+                })?;
             let box_id = hash(label);
 
             boxes
@@ -58,7 +88,7 @@ pub fn part2(input: &[u8]) -> Result<u64> {
                 }
             });
         }
-    });
+    }
 
     Ok(boxes.iter().fold(0, |acc, (id, lenses)| {
         acc + lenses
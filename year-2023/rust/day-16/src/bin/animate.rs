@@ -0,0 +1,15 @@
+use aoc_visual::Animation;
+use day_16::{beam_frames, palette};
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or("day-16.gif".to_string());
+
+    let mut animation = Animation::new(palette()).delay(4);
+    let frames = beam_frames(aoc_core::input!().as_bytes(), 200).expect("frames of the beam");
+    for frame in frames {
+        animation.push(frame.scaled(4));
+    }
+    animation.save(&path).expect("GIF written");
+
+    println!("{path}");
+}
@@ -2,25 +2,56 @@
 #![allow(dead_code)]
 
 use anyhow::{anyhow, Result};
+use aoc_visual::{Frame, Palette};
 use nom::{bytes::complete::is_a, character::complete::newline, multi::separated_list1, IResult};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use types::{goto_checked, Direction, Map, Ray};
 
 mod types;
 
+/// The rows of the contraption, its mirrors and splitters among empty space.
+#[derive(Debug, Clone)]
+pub struct Contraption(pub Vec<Vec<u8>>);
+
+pub fn parse(input: &[u8]) -> Result<Contraption> {
+    let (_, map) = parse_map(input).map_err(|err| anyhow!("parse error: {err:?}"))?;
+    if map.iter().any(|row| row.len() != map[0].len()) {
+        return Err(anyhow!("parse error: the rows are not all as long"));
+    }
+
+    Ok(Contraption(map.into_iter().map(<[u8]>::to_vec).collect()))
+}
+
 pub fn part1(input: &[u8]) -> Result<u64> {
-    let (_, map) = parse_map(input).expect("parse ok");
-
-    Ok(simulate(
-        Ray {
-            pos: (0, 0),
-            dir: Direction::Right,
-        },
-        &map,
-    ))
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &[u8]) -> Result<u64> {
-    let (_, map) = parse_map(input).expect("parse ok");
+    parse(input)?.part2()
+}
+
+impl Contraption {
+    fn map(&self) -> Map<'_> {
+        self.0.iter().map(Vec::as_slice).collect()
+    }
+
+    pub fn part1(&self) -> Result<u64> {
+        Ok(simulate(
+            Ray {
+                pos: (0, 0),
+                dir: Direction::Right,
+            },
+            &self.map(),
+        ))
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        part2_of(&self.map())
+    }
+}
+
+fn part2_of(map: &Map) -> Result<u64> {
     let num_row = map.len();
     let num_col = map[0].len();
 
@@ -58,18 +89,77 @@ pub fn part2(input: &[u8]) -> Result<u64> {
             .collect::<Vec<Ray>>(),
     );
 
+    // every starting ray is simulated on its own
+    #[cfg(feature = "parallel")]
+    let initial_rays = initial_rays.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let initial_rays = initial_rays.into_iter();
+
     Ok(initial_rays
-        .into_iter()
-        .map(|ray| simulate(ray, &map))
+        .map(|ray| simulate(ray, map))
         .max()
         .expect("max should exist"))
 }
 
+// region:    --- Visualisation
+
+// empty tile, mirror or splitter, energized tile
+pub fn palette() -> Palette {
+    Palette::new(&[[10, 10, 25], [110, 110, 140], [255, 180, 40]]).expect("valid palette")
+}
+
+// the beam of part 1 spreading, one frame every `every` tiles entered
+pub fn beam_frames(input: &[u8], every: usize) -> Result<Vec<Frame>> {
+    if every == 0 {
+        return Err(anyhow!("frames have to be at least one tile apart"));
+    }
+    let contraption = parse(input)?;
+    let map = contraption.map();
+    let ray = Ray {
+        pos: (0, 0),
+        dir: Direction::Right,
+    };
+
+    let mut frames = vec![];
+    let mut entered = 0;
+    let visits = trace(ray, &map, |visits| {
+        if entered % every == 0 {
+            frames.push(energized_frame(&map, visits));
+        }
+        entered += 1;
+    });
+    frames.push(energized_frame(&map, &visits));
+
+    Ok(frames)
+}
+
+fn energized_frame(map: &Map, visits: &[Vec<u32>]) -> Frame {
+    Frame::from_fn(map[0].len(), map.len(), |row, col| {
+        if visits[row][col] > 0 {
+            2
+        } else if map[row][col] != b'.' {
+            1
+        } else {
+            0
+        }
+    })
+}
+
+// endregion: --- Visualisation
+
 fn parse_map(input: &[u8]) -> IResult<&[u8], Map> {
     separated_list1(newline, is_a(".|-/\\"))(input)
 }
 
 fn simulate(ray: Ray, map: &Vec<&[u8]>) -> u64 {
+    trace(ray, map, |_| {})
+        .iter()
+        .map(|row| row.iter().filter(|&&elem| elem > 0).count())
+        .sum::<usize>() as u64
+}
+
+// visit counts per tile, `observe` sees them every time a ray enters a tile
+fn trace(ray: Ray, map: &[&[u8]], mut observe: impl FnMut(&[Vec<u32>])) -> Vec<Vec<u32>> {
     let num_row = map.len();
     let num_col = map[0].len();
 
@@ -79,6 +169,7 @@ fn simulate(ray: Ray, map: &Vec<&[u8]>) -> u64 {
     while let Some(mut ray) = rays.pop() {
         loop {
             visits[ray.pos.0][ray.pos.1] += 1;
+            observe(&visits);
 
             match map[ray.pos.0][ray.pos.1] {
                 // Empty tile
@@ -200,9 +291,6 @@ fn simulate(ray: Ray, map: &Vec<&[u8]>) -> u64 {
     }
 
     visits
-        .iter()
-        .map(|row| row.iter().filter(|&&elem| elem > 0).count())
-        .sum::<usize>() as u64
 }
 
 #[cfg(test)]
@@ -213,4 +301,21 @@ mod tests {
 
     #[test]
     fn quick_test() {}
+
+    #[test]
+    fn test_last_frame_matches_part1() {
+        let input = include_bytes!("../test-1.txt");
+        let frames = beam_frames(input, 10).unwrap();
+
+        let energized = frames
+            .last()
+            .expect("at least one frame")
+            .cells()
+            .iter()
+            .filter(|&&cell| cell == 2)
+            .count() as u64;
+
+        assert_eq!(part1(input).unwrap(), energized);
+        assert!(beam_frames(input, 0).is_err());
+    }
 }
@@ -1,6 +1,7 @@
 use std::collections::{BTreeMap, HashMap, VecDeque};
 
 use anyhow::{anyhow, Result};
+use aoc_core::{debug, log::Level, log_enabled, trace};
 use itertools::Itertools;
 use nom::{
     bytes::complete::{tag, take_until, take_while_m_n},
@@ -11,16 +12,72 @@ use nom::{
     IResult,
 };
 
+/// The dig plan, read as written for part 1 and from the colors' hex codes for part 2.
+#[derive(Debug, Clone)]
+pub struct DigPlan {
+    instructions: Vec<DugInstruction>,
+    hex_instructions: Vec<DugInstruction2>,
+}
+
+pub fn parse(input: &str) -> Result<DigPlan> {
+    let (_, instructions) = parse_file(input).map_err(|err| anyhow!("parse error: {err}"))?;
+    let (_, hex_instructions) = parse_file2(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(DigPlan {
+        instructions,
+        hex_instructions,
+    })
+}
+
 pub fn part1(input: &str) -> Result<u64> {
-    let size = 801;
+    parse(input)?.part1()
+}
 
-    let mut field = vec![vec![0; size]; size];
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
 
-    let mut pos = (size / 2, size / 2);
+impl DigPlan {
+    pub fn part1(&self) -> Result<u64> {
+        let plan = self
+            .instructions
+            .iter()
+            .map(|&(direction, len, _)| (direction, u32::from(len)))
+            .collect_vec();
 
-    let (_, instructions) = parse_file(input).expect("parse ok");
+        Ok(flood_fill(&plan))
+    }
 
-    instructions.iter().for_each(|inst| {
+    pub fn part2(&self) -> Result<u64> {
+        vertex_area(&self.hex_instructions)
+    }
+}
+
+// region:    --- Flood Fill
+
+// digs the trench on a field and fills what is outside of it, slow but plainly right
+fn flood_fill(plan: &[DugInstruction2]) -> u64 {
+    // a field fitting the plan with a free border, so the outside is connected around it
+    let (mut row, mut col, mut min, mut max) = (0i64, 0i64, (0i64, 0i64), (0i64, 0i64));
+    for &(direction, len) in plan {
+        let len = i64::from(len);
+        match direction {
+            Direction::Up => row -= len,
+            Direction::Down => row += len,
+            Direction::Left => col -= len,
+            Direction::Right => col += len,
+        }
+        min = (min.0.min(row), min.1.min(col));
+        max = (max.0.max(row), max.1.max(col));
+    }
+    let size = (max.0 - min.0).max(max.1 - min.1) as usize + 3;
+
+    let mut field = vec![vec![0; size]; size];
+
+    let mut pos = ((1 - min.0) as usize, (1 - min.1) as usize);
+    field[pos.0][pos.1] = 1;
+
+    plan.iter().for_each(|inst| {
         match inst.0 {
             Direction::Up => {
                 for row in pos.0 - inst.1 as usize..pos.0 {
@@ -49,55 +106,42 @@ pub fn part1(input: &str) -> Result<u64> {
         }
     });
 
-    // floodfill
-    let mut to_fill = VecDeque::new();
-
-    // find first 0 after first 1 appearance
-    let mut one_appeared = 0;
-    let mut fill_target = (0, 0);
-    for row in 0..size {
-        if field[row][size / 2] == 1 {
-            one_appeared += 1;
-        }
-        if one_appeared >= 1 && field[row][size / 2] == 0 {
-            fill_target = (row, size / 2);
-            break;
-        }
-    }
-
-    to_fill.push_back(fill_target);
+    // floodfill the outside from a corner of the border, as 2
+    let mut to_fill = VecDeque::from([(0, 0)]);
+    field[0][0] = 2;
 
     while let Some(pos) = to_fill.pop_front() {
         let nbrs = neighbours(pos, size);
 
         for nbr in nbrs {
-            if field[nbr.0][nbr.1] == 1 {
+            if field[nbr.0][nbr.1] != 0 {
                 continue;
             } else {
-                field[nbr.0][nbr.1] = 1;
+                field[nbr.0][nbr.1] = 2;
                 to_fill.push_back(nbr);
             }
         }
     }
 
-    let matrix = Matrix(field.clone());
-    println!("{}", matrix);
+    if log_enabled!(Level::Trace) {
+        trace!("dug out:\n{}", Matrix(field.clone()));
+    }
 
-    // count
+    // count the trench and the inside
     let cubic = field
         .iter()
-        .map(|row| row.iter().filter(|land| **land == 1).count())
+        .map(|row| row.iter().filter(|land| **land != 2).count())
         .sum::<usize>();
 
-    Ok(cubic as u64)
+    cubic as u64
 }
 
-pub fn part2(input: &str) -> Result<u64> {
-    // let size = 801;
-    // let mut field = vec![vec![0; size]; size];
-    // let mut pos = (size / 2, size / 2);
-    let (_, instructions) = parse_file2(input).expect("parse ok");
+// endregion: --- Flood Fill
+
+// region:    --- Vertex Geometry
 
+// the area from the corners alone, for trenches too long to dig on a field
+fn vertex_area(instructions: &[DugInstruction2]) -> Result<u64> {
     let mut vertices: Vec<(i64, i64)> = vec![(0, 0)];
     let mut pos = (0, 0);
     instructions.iter().for_each(|inst| {
@@ -118,7 +162,11 @@ pub fn part2(input: &str) -> Result<u64> {
         vertices.push(pos);
     });
 
-    assert_eq!(vertices[0], *vertices.last().unwrap());
+    if pos != (0, 0) {
+        return Err(anyhow!(
+            "the trench ends at {pos:?}, away from where it starts"
+        ));
+    }
 
     // let vertices = vec![(0,0), (0, 2), (2, 2), (2, 0), (0, 0)];
     // normalize
@@ -160,43 +208,18 @@ pub fn part2(input: &str) -> Result<u64> {
     inner_area = inner_area / 2;
     inner_area = inner_area.abs();
 
-    let edges: i64 = vertices
-        .iter()
-        .tuple_windows()
-        .map(|(l, r)| {
-            let vertical = r.0 - l.0;
-            let horizontal = r.1 - l.1;
-
-            let len = (vertical > 0).then(|| vertical).unwrap_or(0)
-                + (horizontal < 0).then(|| horizontal.abs()).unwrap_or(0);
-
-            len
-        })
-        .sum();
+    // the trench is a cube wide, half of it lies outside the corners. Counting the cubes on the
+    // line (Pick's theorem) works whichever way round the trench is dug.
+    let edges: i64 = instructions.iter().map(|inst| inst.1 as i64).sum();
 
-    dbg!(&instructions);
+    trace!("{instructions:?}");
+    debug!("inner area: {inner_area}, edges: {edges}");
 
-    let corner_turn_up_left = instructions
-        .iter()
-        .tuple_windows()
-        .filter(|(l, r)| l.0 == Direction::Up && r.0 == Direction::Left)
-        .count();
-
-    let corner_turn_left_up = instructions
-        .iter()
-        .tuple_windows()
-        .filter(|(l, r)| l.0 == Direction::Left && r.0 == Direction::Up)
-        .count();
-
-    dbg!(edges);
-    dbg!(corner_turn_up_left);
-    dbg!(corner_turn_left_up);
-
-    // sum.abs();
-    Ok((inner_area + edges - corner_turn_up_left as i64 + corner_turn_left_up as i64) as u64)
-    // Ok((inner_area) as u64)
+    Ok((inner_area + edges / 2 + 1) as u64)
 }
 
+// endregion: --- Vertex Geometry
+
 fn neighbours(pos: Coord, max: usize) -> Vec<Coord> {
     let up = (pos.0 != 0).then(|| (pos.0 - 1, pos.1));
     let down = (pos.0 != max - 1).then(|| (pos.0 + 1, pos.1));
@@ -321,6 +344,8 @@ fn parse_file2(input: &str) -> IResult<&str, Vec<DugInstruction2>> {
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::differential::{self, without_one};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
     use rstest::rstest;
 
     use super::*;
@@ -345,4 +370,123 @@ mod tests {
         println!("{:?}", dug);
         assert!(dug.is_ok());
     }
+
+    // columns of (width, depth) hanging from a straight edge, mirrored and walked either way
+    #[derive(Debug, Clone)]
+    struct Lagoon {
+        columns: Vec<(u32, u32)>,
+        flip: (bool, bool),
+        reverse: bool,
+    }
+
+    impl Lagoon {
+        fn random(rng: &mut StdRng) -> Self {
+            Self {
+                columns: (0..rng.gen_range(1..=6))
+                    .map(|_| (rng.gen_range(1..=5), rng.gen_range(1..=6)))
+                    .collect(),
+                flip: (rng.gen_bool(0.5), rng.gen_bool(0.5)),
+                reverse: rng.gen_bool(0.5),
+            }
+        }
+
+        // right along the top, then back along the bottom of the columns
+        fn plan(&self) -> Vec<DugInstruction2> {
+            let width = self.columns.iter().map(|column| column.0).sum();
+            let mut plan = vec![(Direction::Right, width)];
+            let mut depth = 0;
+            for &(width, column_depth) in self.columns.iter().rev() {
+                match column_depth.cmp(&depth) {
+                    std::cmp::Ordering::Greater => {
+                        plan.push((Direction::Down, column_depth - depth))
+                    }
+                    std::cmp::Ordering::Less => plan.push((Direction::Up, depth - column_depth)),
+                    std::cmp::Ordering::Equal => {}
+                }
+                depth = column_depth;
+                // columns as deep as the last one make a longer step
+                match plan.last_mut() {
+                    Some((Direction::Left, len)) => *len += width,
+                    _ => plan.push((Direction::Left, width)),
+                }
+            }
+            plan.push((Direction::Up, depth));
+
+            let turn = |direction| match (direction, self.flip) {
+                (Direction::Up, (true, _)) => Direction::Down,
+                (Direction::Down, (true, _)) => Direction::Up,
+                (Direction::Left, (_, true)) => Direction::Right,
+                (Direction::Right, (_, true)) => Direction::Left,
+                _ => direction,
+            };
+            let back = |direction| match direction {
+                Direction::Up => Direction::Down,
+                Direction::Down => Direction::Up,
+                Direction::Left => Direction::Right,
+                Direction::Right => Direction::Left,
+            };
+            let plan = plan
+                .into_iter()
+                .map(|(direction, len)| (turn(direction), len));
+            if self.reverse {
+                plan.rev()
+                    .map(|(direction, len)| (back(direction), len))
+                    .collect()
+            } else {
+                plan.collect()
+            }
+        }
+
+        // a column less, or a column narrower or shallower
+        fn smaller(&self) -> Vec<Self> {
+            let mut smaller = vec![];
+            if self.columns.len() > 1 {
+                for columns in without_one(&self.columns) {
+                    smaller.push(Self {
+                        columns,
+                        ..self.clone()
+                    });
+                }
+            }
+            for idx in 0..self.columns.len() {
+                let (width, depth) = self.columns[idx];
+                for column in [(width - 1, depth), (width, depth - 1)] {
+                    if column.0 > 0 && column.1 > 0 {
+                        let mut columns = self.columns.clone();
+                        columns[idx] = column;
+                        smaller.push(Self {
+                            columns,
+                            ..self.clone()
+                        });
+                    }
+                }
+            }
+
+            smaller
+        }
+    }
+
+    #[rstest]
+    #[case(62, "R 6 (#70c710)\nD 5 (#0dc571)\nL 2 (#5713f0)\nD 2 (#d2c081)\nR 2 (#59c680)\nD 2 (#411b91)\nL 5 (#8ceee2)\nU 2 (#caa173)\nL 1 (#1b58a2)\nU 2 (#caa171)\nR 2 (#7807d2)\nU 3 (#a77fa3)\nL 2 (#015232)\nU 2 (#7a21e3)")]
+    fn test_flood_fill(#[case] expected: u64, #[case] input: &str) {
+        assert_eq!(expected, part1(input).unwrap());
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn test_differential_vertex_area(#[case] seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let lagoons = (0..500).map(|_| Lagoon::random(&mut rng)).collect_vec();
+
+        let result = differential::check(
+            lagoons,
+            |lagoon| flood_fill(&lagoon.plan()),
+            |lagoon| vertex_area(&lagoon.plan()).unwrap(),
+            Lagoon::smaller,
+        );
+        if let Err(counterexample) = result {
+            panic!("{counterexample}");
+        }
+    }
 }
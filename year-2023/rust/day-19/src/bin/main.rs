@@ -1,12 +1,13 @@
+use day_19::params;
 use day_19::part1;
 use day_19::part2;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt"));
+    let result = part1(&aoc_core::input!());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_str!("../../input.txt"));
+    let result = part2(&aoc_core::input!(), &params());
 
     println!("Part 2: {result:?}");
 }
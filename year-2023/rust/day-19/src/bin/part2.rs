@@ -1,7 +1,8 @@
+use day_19::params;
 use day_19::part2;
 
 fn main() {
-    let result = part2(include_str!("../../input.txt")).expect("Part 2 failed to run");
+    let result = part2(&aoc_core::input!(), &params()).expect("Part 2 failed to run");
 
     println!("{result}");
 }
@@ -10,6 +11,8 @@ fn main() {
 mod tests {
     use rstest::rstest;
 
+    use day_19::example_params;
+
     use super::*;
 
     #[test]
@@ -17,8 +20,9 @@ mod tests {
 
     #[rstest]
     #[case(167409079868000)]
-    fn test_using_test_input_1(#[case] expected: u64) {
-        let result = part2(include_str!("../../test-1.txt")).expect("Part 2 failed to run");
+    fn test_using_test_input_1(#[case] expected: u128) {
+        let result = part2(include_str!("../../test-1.txt"), &example_params())
+            .expect("Part 2 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -0,0 +1,119 @@
+// region:    --- Modules
+use std::collections::HashSet;
+
+use aoc_core::rng;
+use rand::{seq::SliceRandom, Rng};
+// endregion: --- Modules
+
+// there are 26^2 + 26^3 names of two or three letters
+const MAX_WORKFLOWS: usize = 10_000;
+
+/// How big a generated list of workflows and parts is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    pub workflows: usize,
+    pub parts: usize,
+    // ratings and thresholds are in 1..=rating_max, as in `params`
+    pub rating_max: u32,
+}
+
+impl Default for Size {
+    // close to the real input
+    fn default() -> Self {
+        Self {
+            workflows: 550,
+            parts: 200,
+            rating_max: 4000,
+        }
+    }
+}
+
+/// Workflows and parts, the same for the same seed.
+///
+/// The workflows form a tree from `in`, each sending parts only to the ones after it, so
+/// every workflow is used and none leads back to itself.
+pub fn generate(seed: u64, size: &Size) -> String {
+    let mut rng = rng::seeded(seed);
+    let rating_max = size.rating_max.max(2);
+
+    let mut used = HashSet::from(["in".to_string()]);
+    let mut names = vec!["in".to_string()];
+    while names.len() < size.workflows.clamp(1, MAX_WORKFLOWS) {
+        let name = (0..rng.gen_range(2..=3))
+            .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+            .collect::<String>();
+        if used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    // each workflow but `in` is sent to from one before it
+    let mut children = vec![vec![]; names.len()];
+    for idx in 1..names.len() {
+        children[rng.gen_range(0..idx)].push(names[idx].as_str());
+    }
+
+    let workflows = names
+        .iter()
+        .zip(children)
+        .map(|(name, mut targets)| {
+            // the other targets accept or reject
+            let outputs = rng.gen_range(2..=4).max(targets.len());
+            while targets.len() < outputs {
+                targets.push(if rng.gen_bool(0.5) { "A" } else { "R" });
+            }
+            targets.shuffle(&mut rng);
+
+            let catch_all = targets.pop().expect("at least two outputs");
+            let rules = targets
+                .into_iter()
+                .map(|target| {
+                    let category = ['x', 'm', 'a', 's'].choose(&mut rng).expect("categories");
+                    let comparison = if rng.gen_bool(0.5) { '<' } else { '>' };
+                    let threshold = rng.gen_range(1..rating_max);
+                    format!("{category}{comparison}{threshold}:{target},")
+                })
+                .collect::<String>();
+
+            format!("{name}{{{rules}{catch_all}}}")
+        })
+        .collect::<Vec<_>>();
+
+    let parts = (0..size.parts.max(1))
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=rating_max));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .collect::<Vec<_>>();
+
+    format!("{}\n\n{}", workflows.join("\n"), parts.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Params;
+
+    use crate::{part1, part2};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_generate() {
+        let size = Size {
+            workflows: 30,
+            parts: 20,
+            rating_max: 100,
+        };
+        let input = generate(7, &size);
+        let params = Params::new().with("rating_min", 1).with("rating_max", 100);
+
+        assert_eq!(input, generate(7, &size));
+        assert_ne!(input, generate(8, &size));
+        assert!(part1(&input).is_ok());
+        // the accepted combinations are some of the 100^4
+        assert!(part2(&input, &params).unwrap() <= 100u128.pow(4));
+    }
+}
@@ -1,117 +1,172 @@
 // --- Modules
 #![allow(unused)]
 #![allow(dead_code)]
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 
 use anyhow::{anyhow, Result};
-use parser::parse_file;
+use aoc_core::Params;
+use parser::{parse_file, single_part};
 use range::{PartRange, Range};
-use types::{Part, Workflow};
+pub use types::{Part, Workflow};
 
+pub mod generator;
 mod parser;
 mod range;
 mod types;
 // --- Modules
 
+// region:    --- Params
+
+// every rating of a part lies in rating_min..=rating_max
+pub fn params() -> Params {
+    Params::new().with("rating_min", 1).with("rating_max", 4000)
+}
+
+pub fn example_params() -> Params {
+    params()
+}
+
+// endregion: --- Params
+
+/// The workflows by name, and the parts waiting to be sorted by them.
+#[derive(Debug, Clone)]
+pub struct Workflows {
+    pub workflows: HashMap<String, Workflow>,
+    pub parts: Vec<Part>,
+}
+
+pub fn parse(input: &str) -> Result<Workflows> {
+    let (_, (workflows, parts)) = parse_file(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(Workflows { workflows, parts })
+}
+
+// a single part, e.g. `{x=787,m=2655,a=1222,s=2876}`
+pub fn parse_part(input: &str) -> Result<Part> {
+    let (_, part) = single_part(input.trim()).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(part)
+}
+
 pub fn part1(input: &str) -> Result<u64> {
-    let (_, (workflows, parts)) = parse_file(input).expect("parse ok");
+    parse(input)?.part1()
+}
 
-    let mut accepted = vec![];
+pub fn part2(input: &str, params: &Params) -> Result<u128> {
+    parse(input)?.part2(params)
+}
 
-    for part in parts {
-        let mut name = "in";
+impl Workflows {
+    pub fn part1(&self) -> Result<u64> {
+        let mut accepted = vec![];
 
-        'outer: loop {
-            match name {
+        for &part in &self.parts {
+            if self.route(part)?.last() == Some(&"A") {
+                accepted.push(part);
+            }
+        }
+
+        let total_rating = accepted.iter().map(Part::rating).sum::<u64>();
+
+        Ok(total_rating)
+    }
+
+    pub fn part2(&self, params: &Params) -> Result<u128> {
+        let workflows = &self.workflows;
+        let mut accepted = vec![];
+        let rating = Range {
+            start: params.get("rating_min")?,
+            end: params
+                .get::<u32>("rating_max")?
+                .checked_add(1)
+                .ok_or_else(|| anyhow!("rating_max should be below {}", u32::MAX))?,
+        };
+        let full_range = PartRange {
+            x: rating,
+            m: rating,
+            a: rating,
+            s: rating,
+        };
+        // with how many workflows a range went through, to tell a cycle
+        let mut ranges = VecDeque::from([(full_range, "in", 0)]);
+
+        while let Some((mut range, target, visited)) = ranges.pop_front() {
+            // dbg!(&range);
+            // dbg!(&target);
+            match target {
                 "A" => {
-                    accepted.push(part);
-                    break;
+                    accepted.push(range);
                 }
-                "R" => break,
+                "R" => {}
                 _ => {
-                    for rule in &workflows[name].rules {
-                        if let Some(new_name) = rule.satisfy(part) {
-                            name = new_name;
-                            continue 'outer;
+                    if visited >= workflows.len() {
+                        return Err(anyhow!("workflow {target} leads back to itself"));
+                    }
+                    let workflow = workflows
+                        .get(target)
+                        .ok_or_else(|| anyhow!("no workflow {target}"))?;
+                    let mut range = Some(range);
+                    // since each rule only compare one
+                    // each rule should bisect or not interact with the existing range
+                    for rule in &workflow.rules {
+                        // dbg!(&rule);
+                        let (satisfied, rest) = rule.satisfy_range(&range.expect("range exist"));
+                        if let Some(satisfied_range) = satisfied {
+                            ranges.push_back((satisfied_range, rule.target.as_str(), visited + 1));
+                            // dbg!(&ranges);
+                        }
+                        range = rest;
+                        if range.is_none() {
+                            break;
                         }
                     }
-                    name = workflows[name].catch_all;
+
+                    //push remaining range if any
+                    if let Some(range) = range {
+                        ranges.push_back((range, workflow.catch_all.as_str(), visited + 1));
+                    };
                 }
             }
         }
-    }
 
-    let total_rating = accepted.iter().map(Part::rating).sum::<u32>();
+        let combinations = accepted.iter().map(PartRange::combinations).sum::<u128>();
 
-    Ok(total_rating as u64)
-}
+        // intuition
+        // back track from workflow which accept to "in"
+        // find all acceptable ranges
+        // count the ranges combintaitons
+        // be careful of the overlaps.
 
-pub fn part2(input: &str) -> Result<u64> {
-    let (_, (workflows, _)) = parse_file(input).expect("parse ok");
-
-    let mut accepted = vec![];
-    let full_range = PartRange {
-        x: Range {
-            start: 1,
-            end: 4001,
-        },
-        m: Range {
-            start: 1,
-            end: 4001,
-        },
-        a: Range {
-            start: 1,
-            end: 4001,
-        },
-        s: Range {
-            start: 1,
-            end: 4001,
-        },
-    };
-    let mut ranges = VecDeque::from([(full_range, "in")]);
-
-    while let Some((mut range, target)) = ranges.pop_front() {
-        // dbg!(&range);
-        // dbg!(&target);
-        match target {
-            "A" => {
-                accepted.push(range);
+        Ok(combinations)
+    }
+
+    /// The workflows `part` goes through from `in`, ending with `A` or `R`.
+    pub fn route(&self, part: Part) -> Result<Vec<&str>> {
+        let workflows = &self.workflows;
+        let mut route = vec!["in"];
+        let mut name = "in";
+
+        'outer: while name != "A" && name != "R" {
+            // a part going through more workflows than there are is going round in circles
+            if route.len() > workflows.len() {
+                return Err(anyhow!("workflow {name} leads back to itself"));
             }
-            "R" => {}
-            _ => {
-                let mut range = Some(range);
-                // since each rule only compare one
-                // each rule should bisect or not interact with the existing range
-                for rule in &workflows[target].rules {
-                    // dbg!(&rule);
-                    let (satisfied, rest) = rule.satisfy_range(&range.expect("range exist"));
-                    if let Some(satisfied_range) = satisfied {
-                        ranges.push_back((satisfied_range, rule.target));
-                        // dbg!(&ranges);
-                    }
-                    range = rest;
-                    if range.is_none() {
-                        break;
-                    }
+            let workflow = workflows
+                .get(name)
+                .ok_or_else(|| anyhow!("no workflow {name}"))?;
+            for rule in &workflow.rules {
+                if let Some(new_name) = rule.satisfy(part) {
+                    name = new_name;
+                    route.push(name);
+                    continue 'outer;
                 }
-
-                //push remaining range if any
-                if let Some(range) = range {
-                    ranges.push_back((range, workflows[target].catch_all));
-                };
             }
+            name = &workflow.catch_all;
+            route.push(name);
         }
-    }
-
-    let combinations = accepted.iter().map(PartRange::combinations).sum::<u64>();
-
-    // intuition
-    // back track from workflow which accept to "in"
-    // find all acceptable ranges
-    // count the ranges combintaitons
-    // be careful of the overlaps.
 
-    Ok(combinations)
+        Ok(route)
+    }
 }
 
 #[cfg(test)]
@@ -122,4 +177,28 @@ mod tests {
 
     #[test]
     fn quick_test() {}
+
+    #[test]
+    fn test_part2_wide_ratings() {
+        let input = "in{x>2147483648:A,R}\n\n{x=1,m=1,a=1,s=1}";
+        let params = params()
+            .with("rating_min", 0)
+            .with("rating_max", u32::MAX - 1);
+
+        assert_eq!(
+            (u32::MAX as u128 - 2147483649) * (u32::MAX as u128).pow(3),
+            part2(input, &params).unwrap()
+        );
+        let params = params.with("rating_max", u32::MAX);
+        assert!(part2(input, &params).is_err());
+    }
+    #[rstest]
+    #[case("{x=787,m=2655,a=1222,s=2876}", vec!["in", "qqz", "qs", "lnx", "A"])]
+    #[case("{x=1679,m=44,a=2067,s=496}", vec!["in", "px", "rfg", "gd", "R"])]
+    fn test_route(#[case] part: &str, #[case] expected: Vec<&str>) {
+        let workflows = parse(include_str!("../test-1.txt")).unwrap();
+        let part = parse_part(part).unwrap();
+
+        assert_eq!(expected, workflows.route(part).unwrap());
+    }
 }
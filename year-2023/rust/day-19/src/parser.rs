@@ -1,51 +1,58 @@
 use std::collections::HashMap;
 
 use nom::{
+    branch::alt,
     bytes::complete::{is_not, tag},
     character::complete::{self, newline},
     character::complete::{alpha1, one_of},
-    combinator::all_consuming,
+    combinator::{all_consuming, map_opt, map_res, value},
     multi::separated_list1,
     sequence::{delimited, preceded, separated_pair},
     IResult,
 };
 
-use crate::types::{Comparison, Part, Rule, Workflow};
+use crate::types::{Category, Comparison, Part, Rule, Workflow};
 
 fn part(input: &str) -> IResult<&str, Part> {
-    let (rest, params) = delimited(
-        tag("{"),
-        separated_list1(
-            tag(","),
-            separated_pair(one_of("xmas"), tag("="), complete::u32),
+    // every category has to be rated
+    map_opt(
+        delimited(
+            tag("{"),
+            separated_list1(
+                tag(","),
+                separated_pair(one_of("xmas"), tag("="), complete::u32),
+            ),
+            tag("}"),
         ),
-        tag("}"),
-    )(input)?;
-
-    let pair = Part {
-        x: params.iter().find(|elem| elem.0 == 'x').expect("x").1,
-        m: params.iter().find(|elem| elem.0 == 'm').expect("m").1,
-        a: params.iter().find(|elem| elem.0 == 'a').expect("a").1,
-        s: params.iter().find(|elem| elem.0 == 's').expect("s").1,
-    };
+        |params| {
+            let rating = |cat| params.iter().find(|elem| elem.0 == cat).map(|elem| elem.1);
+            Some(Part {
+                x: rating('x')?,
+                m: rating('m')?,
+                a: rating('a')?,
+                s: rating('s')?,
+            })
+        },
+    )(input)
+}
 
-    Ok((rest, pair))
+pub fn single_part(input: &str) -> IResult<&str, Part> {
+    all_consuming(part)(input)
 }
 
 fn rule(input: &str) -> IResult<&str, Rule> {
-    let (input, param) = one_of("xmas")(input)?;
-    let (input, cmp) = one_of("<>")(input)?;
+    let (input, cat) = map_res(one_of("xmas"), Category::try_from)(input)?;
+    let (input, cmp) = alt((
+        value(Comparison::LessThan, tag("<")),
+        value(Comparison::GreaterThan, tag(">")),
+    ))(input)?;
     let (rest, (threshold, target)) = separated_pair(complete::u32, tag(":"), alpha1)(input)?;
 
     let rule = Rule {
-        cat: param.into(),
-        cmp: match cmp {
-            '<' => Comparison::LessThan,
-            '>' => Comparison::GreaterThan,
-            _ => panic!("invalid char"),
-        },
+        cat,
+        cmp,
         threshold,
-        target,
+        target: target.to_string(),
     };
 
     Ok((rest, rule))
@@ -58,22 +65,22 @@ fn workflow(input: &str) -> IResult<&str, Workflow> {
 
     let catch_all = rules_raw.pop().expect("rules is not empty");
     let workflow = Workflow {
-        name: name,
+        name: name.to_string(),
         rules: rules_raw
             .iter()
-            .map(|input| rule(input).expect("rule").1)
-            .collect(),
-        catch_all,
+            .map(|input| all_consuming(rule)(input).map(|(_, rule)| rule))
+            .collect::<Result<_, _>>()?,
+        catch_all: catch_all.to_string(),
     };
 
     Ok((rest, workflow))
 }
 
-pub fn parse_file(input: &str) -> IResult<&str, (HashMap<&str, Workflow>, Vec<Part>)> {
+pub fn parse_file(input: &str) -> IResult<&str, (HashMap<String, Workflow>, Vec<Part>)> {
     let (input, workflows) = separated_list1(newline, workflow)(input)?;
     let (rest, parts) = preceded(tag("\n\n"), separated_list1(newline, part))(input)?;
 
-    let workflows = HashMap::from_iter(workflows.into_iter().map(|w| (w.name, w)));
+    let workflows = HashMap::from_iter(workflows.into_iter().map(|w| (w.name.clone(), w)));
 
     Ok((rest, (workflows, parts)))
 }
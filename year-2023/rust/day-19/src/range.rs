@@ -34,18 +34,11 @@ impl Range {
     // a...b...A...B           b...a...B...A
     // a...b...B...A           b...a...A...B
     fn has_overlaps(&self, other: &Range) -> bool {
-        std::cmp::min(self.end, other.end) as i32 - std::cmp::max(self.start, other.start) as i32
-            > 0
+        std::cmp::min(self.end, other.end) > std::cmp::max(self.start, other.start)
     }
 
     fn overlap_len(&self, other: &Range) -> u32 {
-        let phi = std::cmp::min(self.end, other.end) as i32
-            - std::cmp::max(self.start, other.start) as i32;
-        if phi > 0 {
-            phi as u32
-        } else {
-            0
-        }
+        std::cmp::min(self.end, other.end).saturating_sub(std::cmp::max(self.start, other.start))
     }
 
     fn is_subrange_of(&self, range: &Range) -> bool {
@@ -162,8 +155,8 @@ impl Range {
         }
     }
 
-    fn len(&self) -> u64 {
-        (self.end - self.start) as u64
+    fn len(&self) -> u128 {
+        u128::from(self.end - self.start)
     }
 }
 
@@ -179,6 +172,7 @@ mod tests {
     #[case( Range{start: 1, end: 5}, Range{start: 4, end: 6}, true)]
     #[case( Range{start: 1, end: 3}, Range{start: 3, end: 5}, false)]
     #[case( Range{start: 4, end: 5}, Range{start: 1, end: 3}, false)]
+    #[case( Range{start: 0, end: u32::MAX}, Range{start: 3_000_000_000, end: u32::MAX}, true)]
     fn test_range_overlap(#[case] lhs: Range, #[case] rhs: Range, #[case] expected: bool) {
         assert_eq!(lhs.has_overlaps(&rhs), expected);
         assert_eq!(rhs.has_overlaps(&lhs), expected);
@@ -190,6 +184,7 @@ mod tests {
     #[case( Range{start: 1, end: 5}, Range{start: 4, end: 6}, 1)]
     #[case( Range{start: 1, end: 3}, Range{start: 3, end: 5}, 0)]
     #[case( Range{start: 4, end: 5}, Range{start: 1, end: 3}, 0)]
+    #[case( Range{start: 0, end: u32::MAX}, Range{start: 3_000_000_000, end: u32::MAX}, 1_294_967_295)]
     fn test_range_overlap_len(#[case] lhs: Range, #[case] rhs: Range, #[case] expected: u32) {
         assert_eq!(lhs.overlap_len(&rhs), expected);
         assert_eq!(rhs.overlap_len(&lhs), expected);
@@ -294,7 +289,8 @@ impl PartRange {
         new_disjoint_ranges
     }
 
-    pub fn combinations(&self) -> u64 {
+    // up to 2^128 for ratings spanning all of u32
+    pub fn combinations(&self) -> u128 {
         self.x.len() * self.m.len() * self.a.len() * self.s.len()
     }
 }
@@ -370,7 +366,7 @@ impl DisjointRanges {
         }
     }
 
-    fn combinations(&self) -> u64 {
+    fn combinations(&self) -> u128 {
         self.ranges.iter().map(PartRange::combinations).sum()
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use crate::range::{PartRange, Range};
 
 #[derive(Debug, Clone, Copy)]
@@ -14,40 +16,42 @@ pub enum Category {
     S,
 }
 
-impl From<char> for Category {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Category {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            'x' => Self::X,
-            'm' => Self::M,
-            'a' => Self::A,
-            's' => Self::S,
-            _ => panic!("invalid char"),
+            'x' => Ok(Self::X),
+            'm' => Ok(Self::M),
+            'a' => Ok(Self::A),
+            's' => Ok(Self::S),
+            _ => Err(anyhow::anyhow!("invalid category {value:?}")),
         }
     }
 }
 
-#[derive(Debug)]
-pub struct Rule<'a> {
+#[derive(Debug, Clone)]
+pub struct Rule {
     pub cat: Category,
     pub cmp: Comparison,
     pub threshold: u32,
-    pub target: &'a str,
+    pub target: String,
 }
 
-impl<'a> Rule<'a> {
-    pub fn satisfy(&self, part: Part) -> Option<&'a str> {
+impl Rule {
+    pub fn satisfy(&self, part: Part) -> Option<&str> {
         match self.cmp {
             Comparison::LessThan => match self.cat {
-                Category::X => (part.x < self.threshold).then(|| self.target),
-                Category::M => (part.m < self.threshold).then(|| self.target),
-                Category::A => (part.a < self.threshold).then(|| self.target),
-                Category::S => (part.s < self.threshold).then(|| self.target),
+                Category::X => (part.x < self.threshold).then_some(self.target.as_str()),
+                Category::M => (part.m < self.threshold).then_some(self.target.as_str()),
+                Category::A => (part.a < self.threshold).then_some(self.target.as_str()),
+                Category::S => (part.s < self.threshold).then_some(self.target.as_str()),
             },
             Comparison::GreaterThan => match self.cat {
-                Category::X => (part.x > self.threshold).then(|| self.target),
-                Category::M => (part.m > self.threshold).then(|| self.target),
-                Category::A => (part.a > self.threshold).then(|| self.target),
-                Category::S => (part.s > self.threshold).then(|| self.target),
+                Category::X => (part.x > self.threshold).then_some(self.target.as_str()),
+                Category::M => (part.m > self.threshold).then_some(self.target.as_str()),
+                Category::A => (part.a > self.threshold).then_some(self.target.as_str()),
+                Category::S => (part.s > self.threshold).then_some(self.target.as_str()),
             },
         }
     }
@@ -250,11 +254,11 @@ impl<'a> Rule<'a> {
     }
 }
 
-#[derive(Debug)]
-pub struct Workflow<'a> {
-    pub name: &'a str,
-    pub rules: Vec<Rule<'a>>,
-    pub catch_all: &'a str,
+#[derive(Debug, Clone)]
+pub struct Workflow {
+    pub name: String,
+    pub rules: Vec<Rule>,
+    pub catch_all: String,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -266,7 +270,13 @@ pub struct Part {
 }
 
 impl Part {
-    pub fn rating(&self) -> u32 {
-        self.x + self.m + self.a + self.s
+    pub fn rating(&self) -> u64 {
+        [self.x, self.m, self.a, self.s].map(u64::from).iter().sum()
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{x={},m={},a={},s={}}}", self.x, self.m, self.a, self.s)
     }
 }
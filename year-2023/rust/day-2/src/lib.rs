@@ -2,11 +2,11 @@
 
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{digit1, space0},
+    character::complete::{self, space0},
     combinator::value,
     multi::separated_list1,
     sequence::preceded,
@@ -15,29 +15,44 @@ use nom::{
 
 // endregion: --- Modules
 
-pub fn part1(input: &str) -> Result<u32> {
-    let games: Vec<Game> = input
-        .lines()
-        .map(|line| parse_game(line).unwrap().1)
-        .collect();
+/// Every game played, with the sets of cubes shown in each.
+#[derive(Debug)]
+pub struct Games(pub Vec<Game>);
 
-    let legal_games: Vec<Game> = games
-        .into_iter()
-        .filter(|game| game.is_possbile(12, 13, 14))
-        .collect();
+pub fn parse(input: &str) -> Result<Games> {
+    let games = input
+        .lines()
+        .map(|line| {
+            parse_game(line)
+                .map(|(_, game)| game)
+                .map_err(|err| anyhow!("parse error: {err}"))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Games(games))
+}
 
-    Ok(legal_games.iter().map(|game| game.id).sum())
+pub fn part1(input: &str) -> Result<u32> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u32> {
-    let games: Vec<Game> = input
-        .lines()
-        .map(|line| parse_game(line).unwrap().1)
-        .collect();
+    parse(input)?.part2()
+}
 
-    let games_power: Vec<_> = games.into_iter().map(|game| game.power()).collect();
+impl Games {
+    pub fn part1(&self) -> Result<u32> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|game| game.is_possbile(12, 13, 14))
+            .map(|game| game.id)
+            .sum())
+    }
 
-    Ok(games_power.iter().sum())
+    pub fn part2(&self) -> Result<u32> {
+        Ok(self.0.iter().map(|game| game.power()).sum())
+    }
 }
 
 // region:    --- Types
@@ -62,8 +77,7 @@ pub struct Game {
 // region:    --- Parser
 
 fn color(input: &str) -> IResult<&str, (Color, u32)> {
-    let (input, quantity) = preceded(space0, digit1)(input)?;
-    let quantity = quantity.parse().unwrap();
+    let (input, quantity) = preceded(space0, complete::u32)(input)?;
 
     let (rest, color) = alt((
         value(Color::Red, tag(" red")),
@@ -86,8 +100,7 @@ fn gameset(input: &str) -> IResult<&str, GameSet> {
 
 pub fn parse_game(input: &str) -> IResult<&str, Game> {
     let (input, _) = tag("Game ")(input)?;
-    let (input, id) = digit1(input)?;
-    let id = id.parse().unwrap();
+    let (input, id) = complete::u32(input)?;
     let (input, _) = tag(":")(input)?;
 
     let (_, sets) = separated_list1(tag("; "), gameset)(input)?;
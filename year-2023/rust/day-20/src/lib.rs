@@ -1,118 +1,123 @@
 use std::{
     collections::{HashMap, VecDeque},
-    iter,
+    fmt,
 };
 
 use anyhow::{anyhow, Result};
+use aoc_core::{debug, trace};
 use itertools::Itertools;
 use parser::parse_file;
-use simulation::{module::Module, Signal};
+pub use simulation::{module::Module, Signal};
 
 use crate::simulation::message::Message;
 
 mod parser;
 mod simulation;
 
-pub fn part1(input: &str) -> Result<u64> {
-    // parse file
-    let (_, modules) = parse_file(input).expect("parse ok");
-
-    let (boardcaster, mut modules) = {
-        let mut broadcaster = None;
-        let mut map = HashMap::new();
-
-        for module in modules {
-            match module {
-                b @ Module::Broadcaster { .. } => broadcaster = Some(b),
-                f @ Module::FlipFlop { .. } => {
-                    map.insert(f.name().to_string(), f);
-                }
-                c @ Module::Conjunction { .. } => {
-                    map.insert(c.name().to_string(), c);
-                }
+/// The broadcaster, and the flip-flops and conjunctions it sends pulses through by name.
+#[derive(Debug, Clone)]
+pub struct ModuleNetwork {
+    pub broadcaster: Module,
+    pub modules: HashMap<String, Module>,
+}
+
+pub fn parse(input: &str) -> Result<ModuleNetwork> {
+    let (_, modules) = parse_file(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    let mut broadcaster = None;
+    let mut map = HashMap::new();
+
+    for module in modules {
+        match module {
+            b @ Module::Broadcaster { .. } => broadcaster = Some(b),
+            f @ Module::FlipFlop { .. } => {
+                map.insert(f.name().to_string(), f);
             }
-        }
-        let broadcaster = broadcaster.expect("broadcaster");
-
-        // setup conjunction record
-        let sender_receiver = map
-            .iter()
-            .flat_map(|(name, module)| {
-                module
-                    .receivers()
-                    .iter()
-                    .map(|r| (name.clone(), r.to_string()))
-                    .collect_vec()
-            })
-            .chain(
-                broadcaster
-                    .receivers()
-                    .iter()
-                    .map(|r| ("broadcaster".to_string(), r.to_string()))
-                    .collect_vec(),
-            )
-            .collect_vec();
-
-        for (sender, receiver) in sender_receiver {
-            match map.get_mut(&receiver) {
-                Some(Module::Conjunction { record, .. }) => {
-                    record.entry(sender.to_string()).or_insert(Signal::LOW);
-                }
-                _ => {}
+            c @ Module::Conjunction { .. } => {
+                map.insert(c.name().to_string(), c);
             }
         }
+    }
+    let broadcaster = broadcaster.ok_or_else(|| anyhow!("no broadcaster"))?;
+
+    // setup conjunction record
+    let sender_receiver = map
+        .iter()
+        .flat_map(|(name, module)| {
+            module
+                .receivers()
+                .iter()
+                .map(|r| (name.clone(), r.to_string()))
+                .collect_vec()
+        })
+        .chain(
+            broadcaster
+                .receivers()
+                .iter()
+                .map(|r| ("broadcaster".to_string(), r.to_string()))
+                .collect_vec(),
+        )
+        .collect_vec();
+
+    for (sender, receiver) in sender_receiver {
+        if let Some(Module::Conjunction { record, .. }) = map.get_mut(&receiver) {
+            record.entry(sender.to_string()).or_insert(Signal::LOW);
+        }
+    }
 
-        (broadcaster, map)
-    };
+    Ok(ModuleNetwork {
+        broadcaster,
+        modules: map,
+    })
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
 
-    // dbg!(&boardcaster);
-    // dbg!(&modules);
+impl ModuleNetwork {
+    pub fn part1(&self) -> Result<u64> {
+        let mut network = self.clone();
+        let mut low_count = 0;
+        let mut high_count = 0;
 
-    // construct simulate
-    // record initial states of the setup
-    // OnOff State of FlipFlop and Conjunction record
-    // save it as a (Vec<(name, ONoFF)>, Vec<name, record>)
-    let mut low_count = 0;
-    let mut high_count = 0;
+        for _ in 0..1_000 {
+            let (low, high) = network.press();
+            low_count += low;
+            high_count += high;
 
-    // for _ in 0..3 {
-    for _ in 0..1_000 {
-        // button low
-        low_count += 1;
-        dbg!("button low");
-
-        let setup = {
-            let mut states = Vec::new();
-            let mut records = Vec::new();
-
-            for (name, module) in &modules {
-                match module {
-                    Module::Broadcaster { name, receivers } => unreachable!(),
-                    Module::FlipFlop {
-                        name,
-                        state,
-                        receivers,
-                    } => {
-                        states.push((name, state));
-                    }
-                    Module::Conjunction {
-                        name,
-                        record,
-                        receivers,
-                    } => {
-                        records.push((name, record));
-                    }
-                }
-            }
+            debug!("low pulses: {low_count}, high pulses: {high_count}");
+        }
 
-            (states, records)
-        };
+        // find cycle
+        // simulate until we find a repeated end setup
+        // CyclicSimulation
+        // Order, State, Fn Simulate, Out
+        // History: HashMap (State -> (Order, Out))
 
-        // if we found identical state in history, dont simulate
+        // calculate total
+
+        Ok(low_count * high_count)
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        Err(anyhow!("Not Implemented."))
+    }
+
+    /// Pushes the button once, returning the low and the high pulses sent until the network settles.
+    pub fn press(&mut self) -> (u64, u64) {
+        // button low
+        let mut low_count = 1;
+        let mut high_count = 0;
+        trace!("button -low-> broadcaster");
 
         // vecdeque init = Message { sender: "boardcast", signal: LOW, receiver: boardcast.receiver }
         let mut queue = VecDeque::new();
-        for receiver in boardcaster.receivers() {
+        for receiver in self.broadcaster.receivers() {
             queue.push_back(Message {
                 sender: "broadcaster".to_string(),
                 signal: Signal::LOW,
@@ -125,7 +130,7 @@ pub fn part1(input: &str) -> Result<u64> {
         //   trace High and LOW total
         // end while
         while let Some(msg) = queue.pop_front() {
-            dbg!(&msg);
+            trace!("{msg:?}");
 
             if msg.signal == Signal::HIGH {
                 high_count += 1;
@@ -133,37 +138,25 @@ pub fn part1(input: &str) -> Result<u64> {
                 low_count += 1;
             }
 
-            match modules.get_mut(&msg.receiver) {
-                Some(module) => {
-                    let new_messages = module.process(msg);
-
-                    for msg in new_messages {
-                        queue.push_back(msg);
-                    }
-
-                    // queue.extend(new_messages);
-                }
-                None => {}
+            if let Some(module) = self.modules.get_mut(&msg.receiver) {
+                queue.extend(module.process(msg));
             }
         }
 
-        dbg!(low_count);
-        dbg!(high_count);
+        (low_count, high_count)
     }
-
-    // find cycle
-    // simulate until we find a repeated end setup
-    // CyclicSimulation
-    // Order, State, Fn Simulate, Out
-    // History: HashMap (State -> (Order, Out))
-
-    // calculate total
-
-    Ok( low_count * high_count)
 }
 
-pub fn part2(input: &str) -> Result<u64> {
-    Err(anyhow!("Not Implemented."))
+// the broadcaster, then every other module by name
+impl fmt::Display for ModuleNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.broadcaster)?;
+        for name in self.modules.keys().sorted() {
+            writeln!(f, "{}", self.modules[name])?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -174,4 +167,12 @@ mod tests {
 
     #[test]
     fn quick_test() {}
+
+    #[test]
+    fn test_press() {
+        let mut network = parse(include_str!("../test-1.txt")).unwrap();
+
+        assert_eq!((8, 4), network.press());
+        assert!(network.to_string().contains("&inv [c=low] -> a\n"));
+    }
 }
@@ -11,26 +11,26 @@ use nom::{
 
 use crate::simulation::{module::Module, FlipFlopState};
 
-fn receivers(input: &str) -> IResult<&str, Vec<&str>> {
-    separated_list1(tag(", "), alpha1)(input)
+fn receivers(input: &str) -> IResult<&str, Vec<String>> {
+    separated_list1(tag(", "), alpha1.map(String::from))(input)
 }
 
 fn module(input: &str) -> IResult<&str, Module> {
     alt((
         preceded(tag("broadcaster -> "), receivers).map(|receivers| Module::Broadcaster {
-            name: "broadcaster",
+            name: "broadcaster".to_string(),
             receivers,
         }),
         preceded(tag("%"), separated_pair(alpha1, tag(" -> "), receivers)).map(
             |(name, receivers)| Module::FlipFlop {
-                name,
+                name: name.to_string(),
                 state: FlipFlopState::OFF,
                 receivers,
             },
         ),
         preceded(tag("&"), separated_pair(alpha1, tag(" -> "), receivers)).map(
             |(name, receivers)| Module::Conjunction {
-                name,
+                name: name.to_string(),
                 record: HashMap::new(),
                 receivers,
             },
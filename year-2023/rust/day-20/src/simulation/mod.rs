@@ -1,3 +1,5 @@
+use std::fmt;
+
 pub mod message;
 pub mod module;
 
@@ -13,6 +15,12 @@ impl Signal {
     }
 }
 
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0 { "high" } else { "low" })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct FlipFlopState(bool);
 
@@ -20,3 +28,9 @@ impl FlipFlopState {
     pub const ON: Self = Self(true);
     pub const OFF: Self = Self(false);
 }
+
+impl fmt::Display for FlipFlopState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0 { "on" } else { "off" })
+    }
+}
@@ -1,30 +1,30 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 use itertools::Itertools;
 
 use crate::simulation::{message::Message, FlipFlopState, Signal};
 
 #[derive(Debug, Clone)]
-pub enum Module<'a> {
+pub enum Module {
     Broadcaster {
-        name: &'a str,
-        receivers: Vec<&'a str>,
+        name: String,
+        receivers: Vec<String>,
     },
     FlipFlop {
-        name: &'a str,
+        name: String,
         state: FlipFlopState,
-        receivers: Vec<&'a str>,
+        receivers: Vec<String>,
     },
     Conjunction {
-        name: &'a str,
+        name: String,
         record: HashMap<String, Signal>,
-        receivers: Vec<&'a str>,
+        receivers: Vec<String>,
     },
     // Output
 }
 
-impl<'a> Module<'a> {
-    pub fn name(&self) -> &'a str {
+impl Module {
+    pub fn name(&self) -> &str {
         match self {
             Module::Broadcaster { name, receivers } => name,
             Module::FlipFlop {
@@ -40,7 +40,7 @@ impl<'a> Module<'a> {
         }
     }
 
-    pub fn receivers(&self) -> &Vec<&'a str> {
+    pub fn receivers(&self) -> &[String] {
         match self {
             Module::Broadcaster { name, receivers } => receivers,
             Module::FlipFlop {
@@ -109,3 +109,23 @@ impl<'a> Module<'a> {
         }
     }
 }
+
+// the module as the input writes it, with its state
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Module::Broadcaster { name, .. } => write!(f, "{name}")?,
+            Module::FlipFlop { name, state, .. } => write!(f, "%{name} {state}")?,
+            Module::Conjunction { name, record, .. } => {
+                let record = record
+                    .iter()
+                    .sorted_by_key(|(sender, _)| *sender)
+                    .map(|(sender, signal)| format!("{sender}={signal}"))
+                    .join(", ");
+                write!(f, "&{name} [{record}]")?
+            }
+        }
+
+        write!(f, " -> {}", self.receivers().join(", "))
+    }
+}
@@ -0,0 +1,17 @@
+use aoc_visual::Animation;
+use day_21::{frontier_frames, palette, params};
+
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or("day-21.gif".to_string());
+
+    let steps = params().get("part1_steps").expect("step count");
+
+    let mut animation = Animation::new(palette()).delay(10);
+    let frames = frontier_frames(&aoc_core::input!(), steps).expect("frames of the garden");
+    for frame in frames {
+        animation.push(frame.scaled(4));
+    }
+    animation.save(&path).expect("GIF written");
+
+    println!("{path}");
+}
@@ -1,12 +1,13 @@
+use day_21::params;
 use day_21::part1;
 use day_21::part2;
 
 fn main() {
-    let result = part1::<131>(include_str!("../../input.txt"), 64);
+    let result = part1(&aoc_core::input!(), &params());
 
     println!("Part 1: {result:?}");
 
-    let result = part2(include_str!("../../input.txt"), 26501365);
+    let result = part2(&aoc_core::input!(), &params());
 
     println!("Part 2: {result:?}");
-}
\ No newline at end of file
+}
@@ -1,7 +1,8 @@
+use day_21::params;
 use day_21::part1;
 
 fn main() {
-    let result = part1::<131>(include_str!("../../input.txt"), 64).expect("Part 1 failed to run");
+    let result = part1(&aoc_core::input!(), &params()).expect("Part 1 failed to run");
 
     println!("{result}");
-}
\ No newline at end of file
+}
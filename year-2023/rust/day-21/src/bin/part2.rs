@@ -1,7 +1,8 @@
+use day_21::params;
 use day_21::part2;
 
 fn main() {
-    let result = part2(include_str!("../../input.txt"), 26501365).expect("Part 2 failed to run");
+    let result = part2(&aoc_core::input!(), &params()).expect("Part 2 failed to run");
 
     println!("{result}");
-}
\ No newline at end of file
+}
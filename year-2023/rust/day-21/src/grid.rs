@@ -52,7 +52,7 @@ where
         for row in 0..N {
             for col in 0..N {
                 if self.cells[row][col] == *value {
-                    return Some(Coord { y: row, x: col })
+                    return Some(Coord { y: row, x: col });
                 }
             }
         }
@@ -1,6 +1,8 @@
-use std::{collections::HashSet, cmp::Reverse};
+use std::{cmp::Reverse, collections::HashSet};
 
 use anyhow::{anyhow, Result};
+use aoc_core::{debug, log::Level, log_enabled, trace, Params};
+use aoc_visual::{Frame, Palette};
 use coordinates::Coord;
 use grid::Grid;
 use parser::parse_map;
@@ -10,35 +12,142 @@ mod coordinates;
 mod grid;
 mod parser;
 
-pub fn part1<const N: usize>(input: &str, step_limit: usize) -> Result<u64> {
-    let grid = grid::<N>(input);
-    // println!("{grid}");
+// region:    --- Params
 
-    // find start
-    let start = grid.find(&'S').expect("S");
-    println!("{start:?}");
+// how many steps the elf takes in each part
+pub fn params() -> Params {
+    Params::new()
+        .with("part1_steps", 64)
+        .with("part2_steps", 26501365)
+}
+
+pub fn example_params() -> Params {
+    Params::new()
+        .with("part1_steps", 6)
+        .with("part2_steps", 5000)
+}
+
+// endregion: --- Params
+
+/// The garden plots and rocks around the starting position, a square 11 or 131 plots wide.
+#[derive(Debug, Clone)]
+pub struct Garden {
+    pub rows: Vec<Vec<char>>,
+}
+
+pub fn parse(input: &str) -> Result<Garden> {
+    let (_, rows) = parse_map(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(Garden { rows })
+}
+
+pub fn part1(input: &str, params: &Params) -> Result<u64> {
+    parse(input)?.part1(params)
+}
+
+pub fn part2(input: &str, params: &Params) -> Result<u64> {
+    parse(input)?.part2(params)
+}
+
+impl Garden {
+    // the grid is sized at compile time, for the example and for the real input
+    pub fn part1(&self, params: &Params) -> Result<u64> {
+        match self.rows.len() {
+            11 => self.part1_of::<11>(params),
+            131 => self.part1_of::<131>(params),
+            width => Err(unsupported(width)),
+        }
+    }
+
+    fn part1_of<const N: usize>(&self, params: &Params) -> Result<u64> {
+        let step_limit = params.get("part1_steps")?;
+        let grid = grid::<N>(&self.rows)?;
+        // println!("{grid}");
+
+        // find start
+        let start = grid.find(&'S').ok_or_else(|| anyhow!("no start"))?;
+        debug!("start at {start:?}");
+
+        // dijkstra
+        // mark all pos reachable from steps % 2 == 0
+        let reachables = dijkstra::<N>(&grid, start, step_limit);
+        debug!("{} plots reachable", reachables.len());
+
+        if log_enabled!(Level::Trace) {
+            let mut marked = grid;
+            for pos in &reachables {
+                marked[*pos] = 'O';
+            }
+            trace!("reachable plots:\n{marked}");
+        }
 
-    // dijkstra
-    // mark all pos reachable from steps % 2 == 0
-    let reachables = dijkstra::<N>(&grid, start, step_limit);
-    dbg!(&reachables);
-    dbg!(&reachables.len());
+        Ok(reachables.len() as u64)
+    }
 
-    let mut marked = grid.clone();
-    for pos in &reachables {
-        marked[*pos] = 'O';
+    pub fn part2(&self, _params: &Params) -> Result<u64> {
+        Err(anyhow!("Not Implemented."))
     }
-    println!("{marked}");
+}
 
-    Ok(reachables.len() as u64)
+// region:    --- Visualisation
+
+// garden, rock, visited on the other parity, reachable, frontier
+pub fn palette() -> Palette {
+    Palette::new(&[
+        [30, 60, 30],
+        [90, 90, 90],
+        [60, 110, 60],
+        [150, 220, 120],
+        [255, 255, 200],
+    ])
+    .expect("valid palette")
 }
 
-pub fn part2(input: &str, step_limit: usize) -> Result<u64> {
-    Err(anyhow!("Not Implemented."))
+// one frame per step, from the start up to `step_limit`
+pub fn frontier_frames(input: &str, step_limit: usize) -> Result<Vec<Frame>> {
+    let garden = parse(input)?;
+    match garden.rows.len() {
+        11 => frontier_frames_of::<11>(&garden, step_limit),
+        131 => frontier_frames_of::<131>(&garden, step_limit),
+        width => Err(unsupported(width)),
+    }
 }
 
-fn grid<const N: usize>(input: &str) -> Grid<char, N> {
-    let (_, map) = parse_map(input).expect("parse ok");
+fn frontier_frames_of<const N: usize>(garden: &Garden, step_limit: usize) -> Result<Vec<Frame>> {
+    let grid = grid::<N>(&garden.rows)?;
+    let start = grid.find(&'S').ok_or_else(|| anyhow!("no start"))?;
+
+    let mut reached = Grid::<Option<usize>, N>::new();
+    dijkstra_observed::<N>(&grid, start, step_limit, |pos, steps| {
+        if steps <= step_limit {
+            reached[pos] = Some(steps);
+        }
+    });
+
+    Ok((0..=step_limit)
+        .map(|step| {
+            Frame::from_fn(N, N, |row, col| match reached.cells[row][col] {
+                Some(steps) if steps == step => 4,
+                Some(steps) if steps < step && steps % 2 == step % 2 => 3,
+                Some(steps) if steps < step => 2,
+                _ if grid.cells[row][col] == '#' => 1,
+                _ => 0,
+            })
+        })
+        .collect())
+}
+
+// endregion: --- Visualisation
+
+fn unsupported(width: usize) -> anyhow::Error {
+    anyhow!("a garden {width} plots wide is not supported, only 11 or 131")
+}
+
+// the garden has to be the square the grid is made for
+fn grid<const N: usize>(map: &[Vec<char>]) -> Result<Grid<char, N>> {
+    if map.len() != N || map.iter().any(|row| row.len() != N) {
+        return Err(anyhow!("the garden is not {N} plots square"));
+    }
 
     let mut grid = Grid::<_, N>::new();
     for row in 0..N {
@@ -47,10 +156,24 @@ fn grid<const N: usize>(input: &str) -> Grid<char, N> {
         }
     }
 
-    grid
+    Ok(grid)
+}
+
+fn dijkstra<const N: usize>(
+    grid: &Grid<char, N>,
+    start: Coord,
+    step_limit: usize,
+) -> HashSet<Coord> {
+    dijkstra_observed::<N>(grid, start, step_limit, |_, _| {})
 }
 
-fn dijkstra<const N: usize>(grid: &Grid<char, N>, start: Coord, step_limit: usize) -> HashSet<Coord> {
+// `observe` is called with every position as it leaves the frontier, in order of steps
+fn dijkstra_observed<const N: usize>(
+    grid: &Grid<char, N>,
+    start: Coord,
+    step_limit: usize,
+    mut observe: impl FnMut(Coord, usize),
+) -> HashSet<Coord> {
     let mut frontier = PriorityQueue::new();
     frontier.push(start, Reverse(0));
 
@@ -61,8 +184,8 @@ fn dijkstra<const N: usize>(grid: &Grid<char, N>, start: Coord, step_limit: usiz
 
     while !frontier.is_empty() {
         let (current, steps) = frontier.pop().expect("current");
-        dbg!(current);
-        dbg!(steps);
+        trace!("{current:?} reached in {} steps", steps.0);
+        observe(current, steps.0);
 
         if steps.0 % 2 == 0 {
             reachable.insert(current);
@@ -100,7 +223,8 @@ mod tests {
     #[rstest]
     #[case(16)]
     fn part1_using_test_input_1(#[case] expected: u64) {
-        let result = part1::<11>(include_str!("../test-1.txt"), 6).expect("Part 1 failed to run");
+        let result =
+            part1(include_str!("../test-1.txt"), &example_params()).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -114,11 +238,28 @@ mod tests {
     #[case(668697, 1000)]
     #[case(16733044, 5000)]
     fn part2_using_test_input_2(#[case] expected: u64, #[case] step_limit: usize) {
-        let result = part2(include_str!("../test-2.txt"), step_limit).expect("Part 2 failed to run");
+        let params = example_params().with("part2_steps", step_limit);
+        let result = part2(include_str!("../test-2.txt"), &params).expect("Part 2 failed to run");
 
         assert_eq!(expected, result);
     }
 
     #[test]
     fn quick_test() {}
+
+    #[test]
+    fn test_last_frame_matches_part1() {
+        let frames = frontier_frames(include_str!("../test-1.txt"), 6).unwrap();
+
+        let reachable = frames
+            .last()
+            .expect("at least one frame")
+            .cells()
+            .iter()
+            .filter(|&&cell| cell >= 3)
+            .count();
+
+        assert_eq!(7, frames.len());
+        assert_eq!(16, reachable);
+    }
 }
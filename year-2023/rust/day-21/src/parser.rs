@@ -10,4 +10,4 @@ fn parse_line(input: &str) -> IResult<&str, Vec<char>> {
 
 pub fn parse_map(input: &str) -> IResult<&str, Vec<Vec<char>>> {
     separated_list1(newline, parse_line)(input)
-}
\ No newline at end of file
+}
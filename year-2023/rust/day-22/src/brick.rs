@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, path::Iter};
+use std::{cmp::Ordering, fmt};
 
 use crate::coordinates::Coord3D;
 
@@ -89,3 +89,15 @@ mod tests {
         assert_eq!(expected, z_order(&lhs, &rhs));
     }
 }
+
+// the brick as the input writes it, where it started before settling
+impl fmt::Display for Brick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start, end) = (self.start, self.end);
+        write!(
+            f,
+            "{},{},{}~{},{},{}",
+            start.x, start.y, start.z, end.x, end.y, end.z
+        )
+    }
+}
@@ -0,0 +1,108 @@
+// region:    --- Modules
+use std::collections::HashSet;
+
+use aoc_core::rng;
+use rand::Rng;
+// endregion: --- Modules
+
+/// How big a generated snapshot of bricks is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    // fewer if they don't fit in the space
+    pub bricks: usize,
+    // x and y are in 0..width
+    pub width: u32,
+    // z is in 1..=height
+    pub height: u32,
+    // cubes of the longest brick
+    pub longest: u32,
+}
+
+impl Default for Size {
+    // close to the real input
+    fn default() -> Self {
+        Self {
+            bricks: 1500,
+            width: 10,
+            height: 330,
+            longest: 5,
+        }
+    }
+}
+
+/// Falling bricks, the same for the same seed.
+///
+/// The bricks are straight and never share a cube, in no particular order like the
+/// snapshot of the puzzle.
+pub fn generate(seed: u64, size: &Size) -> String {
+    let mut rng = rng::seeded(seed);
+    let width = size.width.max(1);
+    let height = size.height.max(1);
+    let longest = size.longest.clamp(1, width.max(height));
+
+    let mut occupied = HashSet::new();
+    let mut bricks = vec![];
+    // a thousand misses in a row and the space is about full
+    let mut misses = 0;
+    while bricks.len() < size.bricks.max(1) && misses < 1000 {
+        let length = rng.gen_range(1..=longest);
+        let start = [
+            rng.gen_range(0..width),
+            rng.gen_range(0..width),
+            rng.gen_range(1..=height),
+        ];
+        let axis = rng.gen_range(0..3);
+        let mut end = start;
+        end[axis] += length - 1;
+
+        let cubes = (0..length)
+            .map(|step| {
+                let mut cube = start;
+                cube[axis] += step;
+                cube
+            })
+            .collect::<Vec<_>>();
+        let fits = end[0] < width && end[1] < width && end[2] <= height;
+        if !fits || cubes.iter().any(|cube| occupied.contains(cube)) {
+            misses += 1;
+            continue;
+        }
+
+        misses = 0;
+        occupied.extend(cubes);
+        bricks.push(format!(
+            "{},{},{}~{},{},{}",
+            start[0], start[1], start[2], end[0], end[1], end[2]
+        ));
+    }
+
+    bricks.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{part1, part2};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_generate() {
+        let size = Size {
+            bricks: 50,
+            width: 4,
+            height: 40,
+            longest: 3,
+        };
+        let input = generate(7, &size);
+
+        assert_eq!(input, generate(7, &size));
+        assert_ne!(input, generate(8, &size));
+        assert_eq!(50, input.lines().count());
+        // the topmost brick can always be taken away
+        assert!(part1(&input).unwrap() >= 1);
+        assert!(part2(&input).is_ok());
+    }
+}
@@ -1,46 +1,94 @@
 // --- region: Modules
-use crate::brick::{z_order, Brick, Orientation};
-use anyhow::Result;
+pub use crate::brick::Brick;
+use crate::brick::{z_order, Orientation};
+use anyhow::{anyhow, Result};
 use parser::parse_file;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 
 mod brick;
 mod coordinates;
+pub mod generator;
 mod parser;
 type BrickIndex = usize;
 type Relations = HashMap<BrickIndex, HashSet<BrickIndex>>;
 // --- endregion: Modules
 
-pub fn part1(input: &str) -> Result<u64> {
-    let (_, bricks) = parse_file(input).expect("parse ok");
+/// The bricks once they have settled, with which bricks each one holds up and rests on.
+#[derive(Debug, Clone)]
+pub struct BrickStack {
+    pub bricks: Vec<Brick>,
+    supporting: Relations,
+    supported_by: Relations,
+    lowest_z: HashMap<BrickIndex, u32>,
+}
 
-    let (supporting, supported_by, _) = build_data_structures(bricks);
+pub fn parse(input: &str) -> Result<BrickStack> {
+    let (_, mut bricks) = parse_file(input).map_err(|err| anyhow!("parse error: {err}"))?;
+    bricks.sort_by(z_order);
 
-    let mut count = 0;
-    for (_, bricks_above) in supporting {
-        if bricks_above
-            .iter()
-            .all(|brick| supported_by[brick].len() > 1)
-        {
-            count += 1;
-        }
-    }
+    let (supporting, supported_by, lowest_z) = build_data_structures(&bricks);
+
+    Ok(BrickStack {
+        bricks,
+        supporting,
+        supported_by,
+        lowest_z,
+    })
+}
 
-    Ok(count)
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u64> {
-    let (_, bricks) = parse_file(input).expect("parse ok");
-    let total = bricks.len();
+    parse(input)?.part2()
+}
 
-    let (supporting, supported_by, lowest_z) = build_data_structures(bricks);
+impl BrickStack {
+    pub fn part1(&self) -> Result<u64> {
+        let mut count = 0;
+        for bricks_above in self.supporting.values() {
+            if bricks_above
+                .iter()
+                .all(|brick| self.supported_by[brick].len() > 1)
+            {
+                count += 1;
+            }
+        }
 
-    let mut count = 0;
-    for idx in 0..total {
-        count += count_would_fall(&supporting, &supported_by, &lowest_z, idx);
+        Ok(count)
     }
 
-    Ok(count)
+    pub fn part2(&self) -> Result<u64> {
+        let total = self.bricks.len();
+
+        // removals are independent of each other, so chain reactions can be counted apart
+        #[cfg(feature = "parallel")]
+        let removed = (0..total).into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let removed = 0..total;
+
+        Ok(removed
+            .map(|idx| count_would_fall(&self.supporting, &self.supported_by, &self.lowest_z, idx))
+            .sum())
+    }
+
+    /// The bricks that fall once `brick` is disintegrated, counting in z order from 0.
+    pub fn fall(&self, brick: BrickIndex) -> Result<Vec<&Brick>> {
+        if brick >= self.bricks.len() {
+            return Err(anyhow!("there are only {} bricks", self.bricks.len()));
+        }
+        let mut fallen =
+            would_fall_with(&self.supporting, &self.supported_by, &self.lowest_z, brick)
+                .into_iter()
+                .filter(|&idx| idx != brick)
+                .collect::<Vec<_>>();
+        fallen.sort();
+
+        Ok(fallen.into_iter().map(|idx| &self.bricks[idx]).collect())
+    }
 }
 
 fn land_on(
@@ -76,11 +124,8 @@ fn land_on(
     (land_on + 1, supporters)
 }
 
-fn build_data_structures(
-    mut bricks: Vec<Brick>,
-) -> (Relations, Relations, HashMap<BrickIndex, u32>) {
-    bricks.sort_by(z_order);
-
+// `bricks` in z order
+fn build_data_structures(bricks: &[Brick]) -> (Relations, Relations, HashMap<BrickIndex, u32>) {
     // record the current top most block foreach xy.
     let mut top_layers: HashMap<(u32, u32), (BrickIndex, u32)> = HashMap::new();
     let mut supporting: Relations = HashMap::new();
@@ -144,8 +189,16 @@ fn count_would_fall(
     lowest_z: &HashMap<BrickIndex, u32>,
     target: BrickIndex,
 ) -> u64 {
-    let mut count = 0;
+    would_fall_with(supporting, supported_by, lowest_z, target).len() as u64 - 1
+}
 
+// the target and every brick that falls once it is gone
+fn would_fall_with(
+    supporting: &Relations,
+    supported_by: &Relations,
+    lowest_z: &HashMap<BrickIndex, u32>,
+    target: BrickIndex,
+) -> HashSet<BrickIndex> {
     let mut descendants = Vec::from_iter(supporting[&target].iter().cloned());
     let mut next_generation = HashSet::new();
     let mut fall = HashSet::from([target]);
@@ -153,7 +206,6 @@ fn count_would_fall(
         for de in descendants {
             if would_fall(supported_by, &fall, de) {
                 fall.insert(de);
-                count += 1;
                 next_generation.extend(supporting[&de].iter());
             }
         }
@@ -176,7 +228,7 @@ fn count_would_fall(
         }
     }
 
-    count
+    fall
 }
 
 #[cfg(test)]
@@ -200,4 +252,28 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn test_fall() {
+        let stack = parse(include_str!("../test-1.txt")).unwrap();
+
+        assert_eq!(
+            vec![
+                "0,0,2~2,0,2",
+                "0,2,3~2,2,3",
+                "0,0,4~0,2,4",
+                "2,0,5~2,2,5",
+                "0,1,6~2,1,6",
+                "1,1,8~1,1,9"
+            ],
+            stack
+                .fall(0)
+                .unwrap()
+                .iter()
+                .map(|brick| brick.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert!(stack.fall(1).unwrap().is_empty());
+        assert!(stack.fall(7).is_err());
+    }
 }
@@ -1,26 +1,40 @@
 use nom::{
     bytes::complete::tag,
     character::complete::{self, newline},
+    combinator::verify,
     multi::separated_list1,
-    sequence::separated_pair,
+    sequence::{preceded, separated_pair, tuple},
     IResult, Parser,
 };
 
 use crate::{brick::Brick, coordinates::Coord3D};
 
 pub fn coord3d(input: &str) -> IResult<&str, Coord3D> {
-    separated_list1(tag(","), complete::u32)
-        .map(|vec| Coord3D {
-            x: vec[0],
-            y: vec[1],
-            z: vec[2],
-        })
-        .parse(input)
+    tuple((
+        complete::u32,
+        preceded(tag(","), complete::u32),
+        preceded(tag(","), complete::u32),
+    ))
+    .map(|(x, y, z)| Coord3D { x, y, z })
+    .parse(input)
 }
+
+// the longest brick, the real input has none longer than 5 cubes
+const MAX_LENGTH: u32 = 1_000;
+
+// a brick is a straight line of cubes, its ends differ along one axis at most
 pub fn block(input: &str) -> IResult<&str, Brick> {
-    separated_pair(coord3d, tag("~"), coord3d)
-        .map(|(p1, p2)| Brick::new(p1, p2))
-        .parse(input)
+    verify(separated_pair(coord3d, tag("~"), coord3d), |(p1, p2)| {
+        let spans = [
+            p1.x.abs_diff(p2.x),
+            p1.y.abs_diff(p2.y),
+            p1.z.abs_diff(p2.z),
+        ];
+        spans.iter().filter(|&&span| span > 0).count() <= 1
+            && spans.iter().all(|&span| span < MAX_LENGTH)
+    })
+    .map(|(p1, p2)| Brick::new(p1, p2))
+    .parse(input)
 }
 
 pub fn parse_file(input: &str) -> IResult<&str, Vec<Brick>> {
@@ -2,11 +2,11 @@ use day_23::part1;
 use day_23::part2;
 
 fn main() {
-    let result = part1::<141>(include_str!("../../input.txt"));
+    let result = part1(&aoc_core::input!());
 
     println!("Part 1: {result:?}");
 
-    let result = part2::<141>(include_str!("../../input.txt"));
+    let result = part2(&aoc_core::input!());
 
     println!("Part 2: {result:?}");
-}
\ No newline at end of file
+}
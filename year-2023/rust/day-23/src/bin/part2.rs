@@ -1,7 +1,7 @@
 use day_23::part2;
 
 fn main() {
-    let result = part2::<141>(include_str!("../../input.txt")).expect("Part 2 failed to run");
+    let result = part2(&aoc_core::input!()).expect("Part 2 failed to run");
 
     println!("{result}");
-}
\ No newline at end of file
+}
@@ -1,12 +1,10 @@
-#![feature(pattern)]
-
 use std::{
     collections::{HashSet, VecDeque},
     ops::{Range, RangeInclusive},
-    str::pattern::Pattern,
 };
 
 use anyhow::{anyhow, Result};
+use aoc_core::trace;
 use coordinates::Coord;
 use grid::Grid;
 use itertools::Itertools;
@@ -23,124 +21,162 @@ mod coordinates;
 mod grid;
 mod trail;
 
-pub fn part1<const N: usize>(input: &str) -> Result<u64> {
-    // FIXME: This possibly uses 2x to 3x more memory allocations...
-    let mut map = Grid::<_, N>::new();
-    input.split("\n").enumerate().for_each(|(row, line)| {
-        let chars: Vec<_> = line.chars().collect();
-        map[row].copy_from_slice(&chars);
-    });
-
-    let start = Coord {
-        row: 0,
-        col: map[0]
-            .iter()
-            .position(|&elem| elem == '.')
-            .expect("starting"),
-    };
+/// The hiking trails, paths, forest and slopes, a square 10, 23 or 141 tiles wide.
+#[derive(Debug, Clone)]
+pub struct Trails {
+    pub rows: Vec<Vec<char>>,
+}
 
-    let end = Coord {
-        row: N - 1,
-        col: map[N - 1]
-            .iter()
-            .position(|&elem| elem == '.')
-            .expect("ending"),
-    };
+pub fn parse(input: &str) -> Result<Trails> {
+    let rows = input.lines().map(|line| line.chars().collect()).collect();
+
+    Ok(Trails { rows })
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
+}
 
-    let graph = graph_generation(&map, start, end);
-    let from = graph
-        .node_references()
-        .find(|(_, co)| **co == start)
-        .expect("start")
-        .0;
-    let to = graph
-        .node_references()
-        .find(|(_, co)| **co == end)
-        .expect("end")
-        .0;
-
-    let paths = all_simple_paths::<Vec<_>, _>(&graph, from, to, 0, None);
-
-    println!("{:?}", Dot::with_config(&graph, &[]));
-
-    let mut max_length = 0;
-    for path in paths {
-        let mut steps_total = 0;
-        for (from, to) in path.iter().tuple_windows() {
-            let edge = graph.find_edge(*from, *to).expect("edge");
-            steps_total += graph.edge_weight(edge).expect("edge weight");
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
+
+impl Trails {
+    // the grid is sized at compile time, for the examples and for the real input
+    pub fn part1(&self) -> Result<u64> {
+        match self.rows.len() {
+            10 => self.part1_of::<10>(),
+            23 => self.part1_of::<23>(),
+            141 => self.part1_of::<141>(),
+            width => Err(unsupported(width)),
         }
+    }
 
-        if steps_total > max_length {
-            max_length = steps_total;
+    pub fn part2(&self) -> Result<u64> {
+        match self.rows.len() {
+            10 => self.part2_of::<10>(),
+            23 => self.part2_of::<23>(),
+            141 => self.part2_of::<141>(),
+            width => Err(unsupported(width)),
         }
     }
 
-    Ok(max_length as u64)
-}
+    // the trails have to be the square the grid is made for
+    fn grid<const N: usize>(&self) -> Result<Grid<char, N>> {
+        if self.rows.len() != N || self.rows.iter().any(|row| row.len() != N) {
+            return Err(anyhow!("the trails are not {N} tiles square"));
+        }
+
+        // FIXME: This possibly uses 2x to 3x more memory allocations...
+        let mut map = Grid::<_, N>::new();
+        self.rows.iter().enumerate().for_each(|(row, chars)| {
+            map[row].copy_from_slice(chars);
+        });
 
-pub fn part2<const N: usize>(input: &str) -> Result<u64> {
-    let mut map = Grid::<_, N>::new();
-    input.split("\n").enumerate().for_each(|(row, line)| {
-        let chars: Vec<_> = line.chars().collect();
-        map[row].copy_from_slice(&chars);
-    });
-
-    for row in map.row_bound() {
-        for col in map.col_bound() {
-            if map[Coord { row, col }].is_contained_in("<>v") {
-                map[Coord { row, col }] = '.';
+        Ok(map)
+    }
+
+    fn part1_of<const N: usize>(&self) -> Result<u64> {
+        let map = self.grid::<N>()?;
+
+        let (start, end) = ends(&map)?;
+
+        let graph = graph_generation(&map, start, end)?;
+        let from = graph
+            .node_references()
+            .find(|(_, co)| **co == start)
+            .ok_or_else(|| anyhow!("no trail leaves the start"))?
+            .0;
+        let to = graph
+            .node_references()
+            .find(|(_, co)| **co == end)
+            .ok_or_else(|| anyhow!("no trail reaches the end"))?
+            .0;
+
+        let paths = all_simple_paths::<Vec<_>, _>(&graph, from, to, 0, None);
+
+        trace!("{:?}", Dot::with_config(&graph, &[]));
+
+        let mut max_length = 0;
+        for path in paths {
+            let mut steps_total = 0;
+            for (from, to) in path.iter().tuple_windows() {
+                let edge = graph.find_edge(*from, *to).expect("edge");
+                steps_total += graph.edge_weight(edge).expect("edge weight");
+            }
+
+            if steps_total > max_length {
+                max_length = steps_total;
             }
         }
-    }
 
-    let start = Coord {
-        row: 0,
-        col: map[0]
-            .iter()
-            .position(|&elem| elem == '.')
-            .expect("starting"),
-    };
+        Ok(max_length as u64)
+    }
 
-    let end = Coord {
-        row: N - 1,
-        col: map[N - 1]
-            .iter()
-            .position(|&elem| elem == '.')
-            .expect("ending"),
-    };
+    fn part2_of<const N: usize>(&self) -> Result<u64> {
+        let mut map = self.grid::<N>()?;
 
-    let graph = graph_generation2(&map, start, end);
-    let from = graph
-        .node_references()
-        .find(|(_, co)| **co == start)
-        .expect("start")
-        .0;
-    let to = graph
-        .node_references()
-        .find(|(_, co)| **co == end)
-        .expect("end")
-        .0;
-
-    let paths = all_simple_paths::<Vec<_>, _>(&graph, from, to, 0, None);
-
-    println!("{:?}", Dot::with_config(&graph, &[]));
-
-    let mut max_length = 0;
-
-    for path in paths {
-        let mut steps_total = 0;
-        for (from, to) in path.iter().tuple_windows() {
-            let edge = graph.find_edge(*from, *to).expect("edge");
-            steps_total += graph.edge_weight(edge).expect("edge weight");
+        for row in map.row_bound() {
+            for col in map.col_bound() {
+                if "<>v".contains(map[Coord { row, col }]) {
+                    map[Coord { row, col }] = '.';
+                }
+            }
         }
 
-        if steps_total > max_length {
-            max_length = steps_total;
+        let (start, end) = ends(&map)?;
+
+        let graph = graph_generation2(&map, start, end);
+        let from = graph
+            .node_references()
+            .find(|(_, co)| **co == start)
+            .ok_or_else(|| anyhow!("no trail leaves the start"))?
+            .0;
+        let to = graph
+            .node_references()
+            .find(|(_, co)| **co == end)
+            .ok_or_else(|| anyhow!("no trail reaches the end"))?
+            .0;
+
+        let paths = all_simple_paths::<Vec<_>, _>(&graph, from, to, 0, None);
+
+        trace!("{:?}", Dot::with_config(&graph, &[]));
+
+        let mut max_length = 0;
+
+        for path in paths {
+            let mut steps_total = 0;
+            for (from, to) in path.iter().tuple_windows() {
+                let edge = graph.find_edge(*from, *to).expect("edge");
+                steps_total += graph.edge_weight(edge).expect("edge weight");
+            }
+
+            if steps_total > max_length {
+                max_length = steps_total;
+            }
         }
+
+        Ok(max_length as u64)
     }
+}
+
+fn unsupported(width: usize) -> anyhow::Error {
+    anyhow!("trails {width} tiles wide are not supported, only 10, 23 or 141")
+}
+
+// the path tiles in the top and in the bottom row
+fn ends<const N: usize>(map: &Grid<char, N>) -> Result<(Coord, Coord)> {
+    let path = |row: usize| {
+        map[row]
+            .iter()
+            .position(|&elem| elem == '.')
+            .map(|col| Coord { row, col })
+    };
 
-    Ok(max_length as u64)
+    Ok((
+        path(0).ok_or_else(|| anyhow!("no path in the top row"))?,
+        path(N - 1).ok_or_else(|| anyhow!("no path in the bottom row"))?,
+    ))
 }
 
 #[cfg(test)]
@@ -155,7 +191,7 @@ mod tests {
     #[rstest]
     #[case(94)]
     fn part1_using_test_input_1(#[case] expected: u64) {
-        let result = part1::<23>(include_str!("../test-1.txt")).expect("Part 1 failed to run");
+        let result = part1(include_str!("../test-1.txt")).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -163,7 +199,7 @@ mod tests {
     #[rstest]
     #[case(14)]
     fn part1_using_test_input_2(#[case] expected: u64) {
-        let result = part1::<10>(include_str!("../test-2.txt")).expect("Part 1 failed to run");
+        let result = part1(include_str!("../test-2.txt")).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -171,7 +207,7 @@ mod tests {
     #[rstest]
     #[case(154)]
     fn part2_using_test_input_1(#[case] expected: u64) {
-        let result = part2::<23>(include_str!("../test-1.txt")).expect("Part 2 failed to run");
+        let result = part2(include_str!("../test-1.txt")).expect("Part 2 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -179,7 +215,7 @@ mod tests {
     #[rstest]
     #[case(14)]
     fn part2_using_test_input_2(#[case] expected: u64) {
-        let result = part2::<10>(include_str!("../test-2.txt")).expect("Part 2 failed to run");
+        let result = part2(include_str!("../test-2.txt")).expect("Part 2 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -187,7 +223,7 @@ mod tests {
     #[rstest]
     #[case(21)]
     fn part2_using_test_input_3(#[case] expected: u64) {
-        let result = part2::<10>(include_str!("../test-3.txt")).expect("Part 2 failed to run");
+        let result = part2(include_str!("../test-3.txt")).expect("Part 2 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -1,8 +1,6 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    str::pattern::Pattern,
-};
+use std::collections::{HashSet, VecDeque};
 
+use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use petgraph::{
     graph::UnGraph, stable_graph::NodeIndex, visit::IntoNodeReferences, Graph, Undirected,
@@ -57,9 +55,15 @@ fn add_directed_edge2(graph: &mut Trails2, from: Coord, to: Coord, weight: u32)
 // endregion: Helpers
 
 // dfs directed graph generation
-pub fn graph_generation<const N: usize>(map: &Grid<char, N>, start: Coord, end: Coord) -> Trails {
+pub fn graph_generation<const N: usize>(
+    map: &Grid<char, N>,
+    start: Coord,
+    end: Coord,
+) -> Result<Trails> {
     let mut graph = Graph::new();
     graph.add_node(start);
+    // the trails from a slope are the same every time it is reached
+    let mut explored = HashSet::new();
 
     let record = Record {
         cur: start,
@@ -70,8 +74,18 @@ pub fn graph_generation<const N: usize>(map: &Grid<char, N>, start: Coord, end:
     let mut queue = VecDeque::from([record]);
 
     while let Some(mut record) = queue.pop_front() {
+        if !explored.insert(record.begin) {
+            continue;
+        }
 
         loop {
+            if record.steps as usize > N * N {
+                return Err(anyhow!(
+                    "the trail at {:?} goes round in a loop",
+                    record.cur
+                ));
+            }
+
             let nbrs = record
                 .cur
                 .neighbours(map.row_bound(), map.col_bound())
@@ -82,7 +96,7 @@ pub fn graph_generation<const N: usize>(map: &Grid<char, N>, start: Coord, end:
 
             if nbrs.len() == 1 {
                 // dot to arrow
-                if map[nbrs[0]].is_contained_in("<v>") {
+                if "<v>".contains(map[nbrs[0]]) {
                     match map[nbrs[0]] {
                         '<' if nbrs[0].col > record.cur.col => break,
                         '>' if nbrs[0].col < record.cur.col => break,
@@ -108,16 +122,29 @@ pub fn graph_generation<const N: usize>(map: &Grid<char, N>, start: Coord, end:
                     record.cur = nbrs[0];
                     record.steps += 1;
                 }
+            } else if nbrs.is_empty() {
+                // a dead end
+                break;
             } else {
                 // arrow to conjunction
-                assert!(map[record.cur] == '.');
+                if map[record.cur] != '.' {
+                    return Err(anyhow!(
+                        "the trails branch on the slope at {:?}",
+                        record.cur
+                    ));
+                }
                 for nbr in nbrs {
                     match map[nbr] {
                         '<' if nbr.col > record.cur.col => continue,
                         '>' if nbr.col < record.cur.col => continue,
                         'v' if nbr.row < record.cur.row => continue,
                         '<' | '>' | 'v' => {}
-                        c => unreachable!("invalid character {}", c),
+                        _ => {
+                            return Err(anyhow!(
+                                "the trails branch at {:?} without a slope",
+                                record.cur
+                            ))
+                        }
                     }
 
                     add_directed_edge(&mut graph, record.begin, nbr, record.steps + 1);
@@ -132,7 +159,8 @@ pub fn graph_generation<const N: usize>(map: &Grid<char, N>, start: Coord, end:
             }
         }
     }
-    graph
+
+    Ok(graph)
 }
 
 // dfs directed graph generation
@@ -0,0 +1,97 @@
+// region:    --- Modules
+use aoc_core::rng;
+use itertools::Itertools;
+use rand::{seq::index, Rng};
+// endregion: --- Modules
+
+/// How big a generated list of hailstones is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    // fewer if the space is too small to give each its own time
+    pub hailstones: usize,
+    // positions are in 0..=scale on every axis, like the window of `params`
+    pub scale: i64,
+    // velocities are in -speed..=speed
+    pub speed: i64,
+}
+
+impl Default for Size {
+    // close to the real input
+    fn default() -> Self {
+        Self {
+            hailstones: 300,
+            scale: 400_000_000_000_000,
+            speed: 500,
+        }
+    }
+}
+
+/// Hailstones, the same for the same seed.
+///
+/// A rock is thrown first and each hailstone is placed so that the rock hits it at a
+/// time of its own, so the input always has the answer part 2 asks for.
+pub fn generate(seed: u64, size: &Size) -> String {
+    let mut rng = rng::seeded(seed);
+    let speed = size.speed.max(1);
+    let scale = size.scale.max(8 * speed);
+    // the rock and a hailstone drift apart by 2 * speed at most, a quarter of the scale in time
+    let last_time = scale / (8 * speed);
+    let hailstones = size.hailstones.clamp(1, last_time as usize);
+
+    let rock_position = [(); 3].map(|_| rng.gen_range(scale / 4..=3 * scale / 4));
+    let rock_velocity = [(); 3].map(|_| rng.gen_range(-speed..=speed));
+
+    index::sample(&mut rng, last_time as usize, hailstones)
+        .into_iter()
+        .map(|time| {
+            let time = time as i64 + 1;
+            // one that stays with the rock would be hit at any time
+            let velocity = loop {
+                let velocity = [(); 3].map(|_| rng.gen_range(-speed..=speed));
+                if velocity != rock_velocity {
+                    break velocity;
+                }
+            };
+            let position = [0, 1, 2]
+                .map(|axis| rock_position[axis] + (rock_velocity[axis] - velocity[axis]) * time);
+
+            format!(
+                "{} @ {}",
+                position.iter().join(", "),
+                velocity.iter().join(", ")
+            )
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::Params;
+
+    use crate::part1;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_generate() {
+        let size = Size {
+            hailstones: 40,
+            scale: 1_000_000,
+            speed: 50,
+        };
+        let input = generate(7, &size);
+        let params = Params::new()
+            .with("window_start", 0)
+            .with("window_end", 1_000_000)
+            .with("offset", 0);
+
+        assert_eq!(input, generate(7, &size));
+        assert_ne!(input, generate(8, &size));
+        assert_eq!(40, input.lines().count());
+        // any two paths cross at most once
+        assert!(part1(&input, &params).unwrap() <= 40 * 39 / 2);
+    }
+}
@@ -1,48 +1,88 @@
 #![allow(unused)]
 use anyhow::{anyhow, Result};
+use aoc_core::{trace, Params};
 use itertools::Itertools;
-use parser::{parse_file, HailStone, Vector2D};
+use parser::parse_file;
+pub use parser::{HailStone, Vector2D};
 
+pub mod generator;
 mod parser;
 
-// const WINDOW_START: f64 = 7.0;
-// const WINDOW_END: f64 = 27.0;
-
-// const WINDOW_START: f64 = 0.0;
-// const WINDOW_END: f64 = 20.0;
-
-// const WINDOW_START: f64 = 0.0;
-// const WINDOW_END: f64 = 200000000000000.0;
-const WINDOW_START: f64 = 200000000000000.0;
-const WINDOW_END: f64 = 400000000000000.0;
-
-pub fn part1(input: &str) -> Result<u64> {
-    let (_, stones) = parse_file(input).expect("parse ok");
-
-    let mut count = 0;
-    for left in 0..stones.len() {
-        for right in left + 1..stones.len() {
-            let a = &stones[left];
-            let b = &stones[right];
-
-            let determinant = collide(a, b);
-            // dbg!(a, b, &determinant);
-            dbg!(&determinant);
-            let observed = if let CollideScenario::IntersectAt { position } = determinant {
-                position.x <= WINDOW_END
-                    && position.x >= WINDOW_START
-                    && position.y <= WINDOW_END
-                    && position.y >= WINDOW_START
-            } else {
-                false
-            };
-            if dbg!(observed) {
-                count += 1;
+// region:    --- Params
+
+// the test area spans window_start..=window_end on both axes. Positions and the window are
+// shifted by offset before intersecting, to keep the floats small.
+pub fn params() -> Params {
+    Params::new()
+        .with("window_start", 200000000000000i64)
+        .with("window_end", 400000000000000i64)
+        .with("offset", 0)
+}
+
+pub fn example_params() -> Params {
+    params().with("window_start", 7).with("window_end", 27)
+}
+
+// endregion: --- Params
+
+/// The hailstones, each with its position and velocity on the x and y axes.
+#[derive(Debug, Clone)]
+pub struct Hailstorm(pub Vec<HailStone>);
+
+pub fn parse(input: &str) -> Result<Hailstorm> {
+    let (_, stones) = parse_file(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(Hailstorm(stones))
+}
+
+pub fn part1(input: &str, params: &Params) -> Result<u64> {
+    parse(input)?.part1(params)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
+
+impl Hailstorm {
+    pub fn part1(&self, params: &Params) -> Result<u64> {
+        let mut stones = self.0.clone();
+
+        let offset = params.get::<i64>("offset")?;
+        let window_start = (params.get::<i64>("window_start")? - offset) as f64;
+        let window_end = (params.get::<i64>("window_end")? - offset) as f64;
+        for stone in &mut stones {
+            stone.pos.x -= offset as f64;
+            stone.pos.y -= offset as f64;
+        }
+
+        let mut count = 0;
+        for left in 0..stones.len() {
+            for right in left + 1..stones.len() {
+                let a = &stones[left];
+                let b = &stones[right];
+
+                let determinant = collide(a, b);
+                let observed = if let CollideScenario::IntersectAt { position } = determinant {
+                    position.x <= window_end
+                        && position.x >= window_start
+                        && position.y <= window_end
+                        && position.y >= window_start
+                } else {
+                    false
+                };
+                trace!("{determinant:?}, inside window: {observed}");
+                if observed {
+                    count += 1;
+                }
             }
         }
+
+        Ok(count as u64)
     }
 
-    Ok(count as u64)
+    pub fn part2(&self) -> Result<u64> {
+        Err(anyhow!("Not Implemented."))
+    }
 }
 
 #[derive(Debug)]
@@ -81,10 +121,6 @@ fn collide(a: &HailStone, b: &HailStone) -> CollideScenario {
     }
 }
 
-pub fn part2(input: &str) -> Result<u64> {
-    Err(anyhow!("Not Implemented."))
-}
-
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -97,7 +133,8 @@ mod tests {
     #[rstest]
     #[case(2)]
     fn part1_using_test_input_1(#[case] expected: u64) {
-        let result = part1(include_str!("../test-1.txt")).expect("Part 1 failed to run");
+        let result =
+            part1(include_str!("../test-1.txt"), &example_params()).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
     }
@@ -2,7 +2,7 @@ use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending, space0},
     multi::separated_list1,
-    sequence::{preceded, separated_pair},
+    sequence::{pair, preceded, separated_pair, tuple},
     IResult, Parser,
 };
 
@@ -24,16 +24,17 @@ pub struct HailStone {
     pub spd: Vector2D,
 }
 
-// const OFFSET: i64 = 200000000000000;
-const OFFSET: i64 = 0;
-// const OFFSET: i64 = 7;
-
+// x, y and z, where z is left out
 fn coord_2d(input: &str) -> IResult<&str, Vector2D> {
-    let (rest, nums) = separated_list1(tag(","), preceded(space0, complete::i64))(input)?;
+    let (rest, (x, y, _)) = tuple((
+        preceded(space0, complete::i64),
+        preceded(pair(tag(","), space0), complete::i64),
+        preceded(pair(tag(","), space0), complete::i64),
+    ))(input)?;
 
     let coord = Vector2D {
-        x: (nums[0] - OFFSET) as f64, // lossy, should be ok for our input range
-        y: (nums[1] - OFFSET) as f64,
+        x: x as f64, // lossy, should be ok for our input range
+        y: y as f64,
     };
 
     Ok((rest, coord))
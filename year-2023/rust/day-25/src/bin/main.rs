@@ -1,7 +1,7 @@
-use day_25::part1;
+use day_25::{params, part1};
 
 fn main() {
-    let result = part1(include_str!("../../input.txt"));
+    let result = part1(&aoc_core::input!(), &params());
 
     println!("Part 1: {result:?}");
-}
\ No newline at end of file
+}
@@ -7,15 +7,15 @@ use petgraph::{
     Graph, Undirected,
 };
 
-pub type Components = Graph<&'static str, (), Undirected>;
+pub type Components = Graph<String, (), Undirected>;
 
-pub fn get_node_index(graph: &mut Components, name: &'static str) -> NodeIndex {
+pub fn get_node_index(graph: &mut Components, name: &str) -> NodeIndex {
     match graph
         .node_references()
         .find(|(_idx, named)| **named == name)
     {
         Some((idx, _)) => idx,
-        None => graph.add_node(name),
+        None => graph.add_node(name.to_string()),
     }
 }
 
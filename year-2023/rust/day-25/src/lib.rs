@@ -1,142 +1,202 @@
 use std::collections::HashSet;
 
 use anyhow::{anyhow, Result};
+use aoc_core::{
+    cancel, debug,
+    rng::{self, DEFAULT_SEED, SEED},
+    Params,
+};
 use itertools::Itertools;
 use parser::parse_file;
 use petgraph::stable_graph::NodeIndex;
+use rand::Rng;
 
 use crate::graph::is_connected_to_all;
+pub use crate::graph::Components;
 
 mod graph;
 mod parser;
 
-pub fn part1(input: &'static str) -> Result<u64> {
-    let (_, graph) = parse_file(input).expect("parse ok");
-    let number_of_nodes = graph.node_count();
-
-    // get a random node and find a subgraph
-    // where all members are interconnected by at least 2 edges.
-    // but for the initial seeding, takes a leap of faith
-    // grab around 70~110 nodes (by collecting all 2 degrees neighbours around initial)
-    // and call them a subgroup. This approach works for the input, not test case however.
-    let result = 'random_group: loop {
-        let mut subgroup = 'seeding: {
-            let seed = rand::random::<usize>() % number_of_nodes;
-            let initial = graph.node_indices().skip(seed).next().expect("node index");
-
-            let mut subgroup: HashSet<NodeIndex> = HashSet::new();
-            subgroup.insert(initial);
-            for nbr in graph.neighbors_undirected(initial) {
-                subgroup.insert(nbr);
+// region:    --- Params
+
+// where the random starting nodes come from, the same seed takes the same path
+pub fn params() -> Params {
+    Params::new().with(SEED, DEFAULT_SEED)
+}
+
+pub fn example_params() -> Params {
+    params()
+}
+
+// endregion: --- Params
+
+// random groups grown before giving up on finding the cut
+const ATTEMPTS: usize = 1_000;
+
+/// The components, joined by the wires between them.
+#[derive(Debug, Clone)]
+pub struct Wiring {
+    pub graph: Components,
+}
+
+pub fn parse(input: &str) -> Result<Wiring> {
+    let (_, graph) = parse_file(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(Wiring { graph })
+}
+
+pub fn part1(input: &str, params: &Params) -> Result<u64> {
+    parse(input)?.part1(params)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
+
+impl Wiring {
+    pub fn part1(&self, params: &Params) -> Result<u64> {
+        let graph = &self.graph;
+        let number_of_nodes = graph.node_count();
+        let mut rng = rng::from_params(params)?;
+
+        // get a random node and find a subgraph
+        // where all members are interconnected by at least 2 edges.
+        // but for the initial seeding, takes a leap of faith
+        // grab around 70~110 nodes (by collecting all 2 degrees neighbours around initial)
+        // and call them a subgroup. This approach works for the input, not test case however.
+        // seeds are random and some never lead to a cut, so the retries are bounded
+        let mut attempts = 0;
+        let result = 'random_group: loop {
+            cancel::check()?;
+            if attempts == ATTEMPTS {
+                return Err(anyhow!("no cut of three wires in {ATTEMPTS} random groups"));
             }
-            for _ in 0..2 {
-                for &node in &subgroup.iter().cloned().collect_vec() {
-                    for nbr in graph.neighbors_undirected(node) {
-                        subgroup.insert(nbr);
-                    }
+            attempts += 1;
+
+            let mut subgroup = 'seeding: {
+                let seed = rng.gen_range(0..number_of_nodes);
+                let initial = graph.node_indices().nth(seed).expect("node index");
+
+                let mut subgroup: HashSet<NodeIndex> = HashSet::new();
+                subgroup.insert(initial);
+                for nbr in graph.neighbors_undirected(initial) {
+                    subgroup.insert(nbr);
                 }
-            }
-            break 'seeding subgroup;
-        };
-        dbg!(&subgroup.len());
-
-        // repeatedly eval subgraph edges, connected node is merge into the subgraph
-        // if it is connected to 3 or more of subgraph members
-        // end the process when subgraph has only three outgoing connections left
-        let mut connections = HashSet::new(); // outgoing edges from subgraph
-        let mut contending = HashSet::new(); // immediate neighbours of subgraph
-
-        for node in &subgroup {
-            for nbr in graph.neighbors_undirected(*node) {
-                // add outgoing edges
-                let edge = graph.find_edge(*node, nbr).expect("edge");
-                connections.insert(edge);
-
-                // add contending node
-                if !subgroup.contains(&nbr) {
-                    contending.insert(nbr);
+                for _ in 0..2 {
+                    for &node in &subgroup.iter().cloned().collect_vec() {
+                        for nbr in graph.neighbors_undirected(node) {
+                            subgroup.insert(nbr);
+                        }
+                    }
                 }
-            }
-        }
-        for node in &subgroup {
-            for nbr in graph.neighbors_undirected(*node) {
-                if subgroup.contains(&nbr) {
+                break 'seeding subgroup;
+            };
+            debug!("seeded subgroup of {} nodes", subgroup.len());
+
+            // repeatedly eval subgraph edges, connected node is merge into the subgraph
+            // if it is connected to 3 or more of subgraph members
+            // end the process when subgraph has only three outgoing connections left
+            let mut connections = HashSet::new(); // outgoing edges from subgraph
+            let mut contending = HashSet::new(); // immediate neighbours of subgraph
+
+            for node in &subgroup {
+                for nbr in graph.neighbors_undirected(*node) {
+                    // add outgoing edges
                     let edge = graph.find_edge(*node, nbr).expect("edge");
-                    connections.remove(&edge);
+                    connections.insert(edge);
+
+                    // add contending node
+                    if !subgroup.contains(&nbr) {
+                        contending.insert(nbr);
+                    }
                 }
             }
-        }
-
-        while connections.len() != 3 {
-            if contending.len() > 400 {
-                contending.drain();
-                continue 'random_group;
+            for node in &subgroup {
+                for nbr in graph.neighbors_undirected(*node) {
+                    if subgroup.contains(&nbr) {
+                        let edge = graph.find_edge(*node, nbr).expect("edge");
+                        connections.remove(&edge);
+                    }
+                }
             }
 
-            // enumerate contending and add valid members to a list
-            let mut valid_nodes = HashSet::new();
-            for node in &contending {
-                // if connected to at least 2 members of the subgroup
-                // add to valid list
-                if is_connected_to_all(&graph, *node, subgroup.iter()) {
-                    valid_nodes.insert(*node);
+            while connections.len() != 3 {
+                cancel::check()?;
+                if contending.len() > 400 {
+                    contending.drain();
+                    continue 'random_group;
                 }
-            }
 
-            // expand contending search scope.
-            if valid_nodes.is_empty() {
-                for node in contending.iter().cloned().collect_vec() {
-                    for nbr in graph.neighbors_undirected(node) {
-                        if !subgroup.contains(&nbr) {
-                            contending.insert(nbr);
-                        }
+                // enumerate contending and add valid members to a list
+                let mut valid_nodes = HashSet::new();
+                for node in &contending {
+                    // if connected to at least 2 members of the subgroup
+                    // add to valid list
+                    if is_connected_to_all(graph, *node, subgroup.iter()) {
+                        valid_nodes.insert(*node);
                     }
                 }
-            } else {
-                // Process valid nodes
-                // 1) remove valid node from contending
-                // 2a) update connections: add new node edges, cleanup intra subgraph edge in 3.
-                // 2b) Add valid nodes to subgraph
-                // 2c) Add non-subgraph nbrs of valid nodes to contending
-                // 3) remove subgraph-node edges
-                for node in &valid_nodes {
-                    contending.remove(node);
-                }
-                for node in &valid_nodes {
-                    for nbr in graph.neighbors_undirected(*node) {
-                        // add outgoing edges
-                        let edge = graph.find_edge(*node, nbr).expect("edge");
-                        connections.insert(edge);
 
-                        // add contending node
-                        if !subgroup.contains(&nbr) && !valid_nodes.contains(&nbr) {
-                            contending.insert(nbr);
+                // expand contending search scope.
+                if valid_nodes.is_empty() {
+                    let scope = contending.len();
+                    for node in contending.iter().cloned().collect_vec() {
+                        for nbr in graph.neighbors_undirected(node) {
+                            if !subgroup.contains(&nbr) {
+                                contending.insert(nbr);
+                            }
                         }
                     }
-                    subgroup.insert(*node);
-                }
-                for node in &valid_nodes {
-                    for nbr in graph.neighbors_undirected(*node) {
-                        if subgroup.contains(&nbr) {
+                    // nothing left to take in, the group never gets down to three wires
+                    if contending.len() == scope {
+                        continue 'random_group;
+                    }
+                } else {
+                    // Process valid nodes
+                    // 1) remove valid node from contending
+                    // 2a) update connections: add new node edges, cleanup intra subgraph edge in 3.
+                    // 2b) Add valid nodes to subgraph
+                    // 2c) Add non-subgraph nbrs of valid nodes to contending
+                    // 3) remove subgraph-node edges
+                    for node in &valid_nodes {
+                        contending.remove(node);
+                    }
+                    for node in &valid_nodes {
+                        for nbr in graph.neighbors_undirected(*node) {
+                            // add outgoing edges
                             let edge = graph.find_edge(*node, nbr).expect("edge");
-                            connections.remove(&edge);
+                            connections.insert(edge);
+
+                            // add contending node
+                            if !subgroup.contains(&nbr) && !valid_nodes.contains(&nbr) {
+                                contending.insert(nbr);
+                            }
+                        }
+                        subgroup.insert(*node);
+                    }
+                    for node in &valid_nodes {
+                        for nbr in graph.neighbors_undirected(*node) {
+                            if subgroup.contains(&nbr) {
+                                let edge = graph.find_edge(*node, nbr).expect("edge");
+                                connections.remove(&edge);
+                            }
                         }
                     }
+                    valid_nodes.drain();
                 }
-                valid_nodes.drain();
             }
-        }
 
-        let subgraph_size = subgroup.len();
-        let another_size = number_of_nodes - subgraph_size;
-        break (subgraph_size * another_size);
-    };
+            let subgraph_size = subgroup.len();
+            let another_size = number_of_nodes - subgraph_size;
+            break subgraph_size * another_size;
+        };
 
-    Ok(result as u64)
-}
+        Ok(result as u64)
+    }
 
-pub fn part2(input: &str) -> Result<u64> {
-    Err(anyhow!("Not Implemented."))
+    pub fn part2(&self) -> Result<u64> {
+        Err(anyhow!("Not Implemented."))
+    }
 }
 
 #[cfg(test)]
@@ -148,7 +208,8 @@ mod tests {
     #[rstest]
     #[case(54)]
     fn part_1_using_test_input_1(#[case] expected: u64) {
-        let result = part1(include_str!("../test-1.txt")).expect("Part 1 failed to run");
+        let result =
+            part1(include_str!("../test-1.txt"), &example_params()).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
     }
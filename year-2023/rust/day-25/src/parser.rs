@@ -13,7 +13,7 @@ fn parse_line(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
     separated_pair(alpha1, tag(": "), separated_list1(tag(" "), alpha1)).parse(input)
 }
 
-pub fn parse_file(input: &'static str) -> IResult<&'static str, Components> {
+pub fn parse_file(input: &str) -> IResult<&str, Components> {
     let (rest, connections) = separated_list1(line_ending, parse_line)(input)?;
 
     let mut graph = Graph::new_undirected();
@@ -9,56 +9,72 @@ pub mod parsing;
 
 // endregion: --- Modules
 
-pub fn part1(input: &str) -> Result<u32> {
+/// The numbers of the engine schematic by where they start and end, and its symbols.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub numbers: HashMap<(Coordinates, Coordinates), u32>,
+    pub symbols: HashMap<Coordinates, char>,
+}
+
+pub fn parse(input: &str) -> Result<Schematic> {
     let mut numbers: HashMap<(Coordinates, Coordinates), u32> = HashMap::new();
     let mut symbols: HashMap<Coordinates, char> = HashMap::new();
 
-    input.lines().enumerate().for_each(|(line_number, line)| {
-        parse_line(&mut numbers, &mut symbols, line, line_number);
-    });
+    for (line_number, line) in input.lines().enumerate() {
+        parse_line(&mut numbers, &mut symbols, line, line_number)?;
+    }
 
-    Ok(numbers
-        .iter()
-        .filter(|((start, end), _)| has_adjacent_symbols(&mut symbols, start, end))
-        .map(|(_, val)| *val)
-        .sum())
+    Ok(Schematic { numbers, symbols })
+}
+
+pub fn part1(input: &str) -> Result<u32> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u32> {
-    let mut numbers: HashMap<(Coordinates, Coordinates), u32> = HashMap::new();
-    let mut symbols: HashMap<Coordinates, char> = HashMap::new();
+    parse(input)?.part2()
+}
 
-    input.lines().enumerate().for_each(|(line_number, line)| {
-        parse_line(&mut numbers, &mut symbols, line, line_number);
-    });
-
-    let gears: Vec<_> = symbols
-        .iter()
-        .filter(|(_, c)| **c == '*')
-        .filter(|(pos, _)| {
-            numbers
-                .iter()
-                .filter(|((start, end), _)| is_adjacent_to(start, end, **pos))
-                .count()
-                == 2
-        })
-        .collect();
-
-    Ok(gears
-        .iter()
-        .map(|(pos, _)| {
-            numbers
-                .iter()
-                .filter_map(|((start, end), value)| {
-                    if is_adjacent_to(start, end, **pos) {
-                        Some(value)
-                    } else {
-                        None
-                    }
-                })
-                .product::<u32>()
-        })
-        .sum())
+impl Schematic {
+    pub fn part1(&self) -> Result<u32> {
+        Ok(self
+            .numbers
+            .iter()
+            .filter(|((start, end), _)| has_adjacent_symbols(&self.symbols, start, end))
+            .map(|(_, val)| *val)
+            .sum())
+    }
+
+    pub fn part2(&self) -> Result<u32> {
+        let gears: Vec<_> = self
+            .symbols
+            .iter()
+            .filter(|(_, c)| **c == '*')
+            .filter(|(pos, _)| {
+                self.numbers
+                    .iter()
+                    .filter(|((start, end), _)| is_adjacent_to(start, end, **pos))
+                    .count()
+                    == 2
+            })
+            .collect();
+
+        Ok(gears
+            .iter()
+            .map(|(pos, _)| {
+                self.numbers
+                    .iter()
+                    .filter_map(|((start, end), value)| {
+                        if is_adjacent_to(start, end, **pos) {
+                            Some(value)
+                        } else {
+                            None
+                        }
+                    })
+                    .product::<u32>()
+            })
+            .sum())
+    }
 }
 
 #[cfg(test)]
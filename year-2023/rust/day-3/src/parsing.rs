@@ -1,16 +1,21 @@
 use std::collections::HashMap;
 
+use anyhow::{anyhow, Result};
+
 fn insert_number(
     to: &mut HashMap<(Coordinates, Coordinates), u32>,
     start: usize,
     end: usize,
     line_number: usize,
     digits: &[char],
-) {
-    to.insert(
-        ((start, line_number), (end, line_number)),
-        digits.iter().collect::<String>().parse().unwrap(),
-    );
+) -> Result<()> {
+    let digits = digits.iter().collect::<String>();
+    let number = digits
+        .parse()
+        .map_err(|err| anyhow!("parse error: {digits}: {err}"))?;
+    to.insert(((start, line_number), (end, line_number)), number);
+
+    Ok(())
 }
 
 pub fn parse_line(
@@ -18,7 +23,7 @@ pub fn parse_line(
     symbols: &mut HashMap<Coordinates, char>,
     input: &str,
     line_number: usize,
-) {
+) -> Result<()> {
     let iter = input.chars().enumerate();
     let mut storing_number = false;
     let mut digits_collected: Vec<char> = vec![];
@@ -34,7 +39,7 @@ pub fn parse_line(
                         pos - 1,
                         line_number,
                         &digits_collected,
-                    );
+                    )?;
                     storing_number = false;
                     digits_collected.drain(..);
                 }
@@ -47,7 +52,7 @@ pub fn parse_line(
                         pos - 1,
                         line_number,
                         &digits_collected,
-                    );
+                    )?;
                     storing_number = false;
                     digits_collected.drain(..);
                 }
@@ -63,7 +68,7 @@ pub fn parse_line(
                 digits_collected.push(c);
             }
             c => {
-                panic!("Unexpected characters.: {}", c);
+                return Err(anyhow!("parse error: unexpected character {c:?}"));
             }
         }
     }
@@ -76,14 +81,16 @@ pub fn parse_line(
             input.len() - 1,
             line_number,
             &digits_collected,
-        );
+        )?;
     }
+
+    Ok(())
 }
 
 pub type Coordinates = (usize, usize);
 
 pub fn has_adjacent_symbols(
-    symbols: &mut HashMap<Coordinates, char>,
+    symbols: &HashMap<Coordinates, char>,
     start: &Coordinates,
     end: &Coordinates,
 ) -> bool {
@@ -148,7 +155,7 @@ mod tests {
         let mut numbers: HashMap<(Coordinates, Coordinates), u32> = HashMap::new();
         let mut symbols: HashMap<Coordinates, char> = HashMap::new();
 
-        parse_line(&mut numbers, &mut symbols, &fixture, 0);
+        parse_line(&mut numbers, &mut symbols, fixture, 0).unwrap();
 
         assert!(symbols.is_empty());
         assert!(numbers.contains_key(&((0, 0), (2, 0))));
@@ -161,7 +168,7 @@ mod tests {
         let mut numbers: HashMap<(Coordinates, Coordinates), u32> = HashMap::new();
         let mut symbols: HashMap<Coordinates, char> = HashMap::new();
 
-        parse_line(&mut numbers, &mut symbols, &fixture, 0);
+        parse_line(&mut numbers, &mut symbols, fixture, 0).unwrap();
 
         assert!(numbers.contains_key(&((7, 0), (9, 0))));
     }
@@ -172,7 +179,7 @@ mod tests {
         let mut numbers: HashMap<(Coordinates, Coordinates), u32> = HashMap::new();
         let mut symbols: HashMap<Coordinates, char> = HashMap::new();
 
-        parse_line(&mut numbers, &mut symbols, &fixture, 0);
+        parse_line(&mut numbers, &mut symbols, fixture, 0).unwrap();
 
         assert!(numbers.contains_key(&((0, 0), (2, 0))));
         assert!(symbols.contains_key(&(3, 0)));
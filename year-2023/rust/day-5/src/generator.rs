@@ -0,0 +1,151 @@
+// region:    --- Modules
+use std::fmt;
+
+use aoc_core::rng;
+use rand::{seq::SliceRandom, Rng};
+// endregion: --- Modules
+
+// the maps of an almanac, in the order they are chained
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// How big a generated almanac is.
+#[derive(Debug, Clone)]
+pub struct Size {
+    // pairs of seed start and length, fewer if some overlap
+    pub seed_ranges: usize,
+    // lines of each map
+    pub mappings: usize,
+    // the ids of a map are in 0..span
+    pub span: u64,
+}
+
+impl Default for Size {
+    // close to the real input
+    fn default() -> Self {
+        Self {
+            seed_ranges: 10,
+            mappings: 30,
+            span: 1 << 32,
+        }
+    }
+}
+
+/// The numbers of an almanac, before they are written out.
+#[derive(Debug, Clone)]
+pub struct Numbers {
+    // start and length
+    pub seed_ranges: Vec<(u64, u64)>,
+    // destination, source and length, for each map in order
+    pub maps: Vec<Vec<(u64, u64, u64)>>,
+}
+
+impl fmt::Display for Numbers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seeds = self
+            .seed_ranges
+            .iter()
+            .map(|(start, length)| format!("{start} {length}"))
+            .collect::<Vec<_>>();
+        write!(f, "seeds: {}", seeds.join(" "))?;
+
+        for (pair, map) in CATEGORIES.windows(2).zip(&self.maps) {
+            write!(f, "\n\n{}-to-{} map:", pair[0], pair[1])?;
+            for (destination, source, length) in map {
+                write!(f, "\n{destination} {source} {length}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An almanac, the same for the same seed.
+pub fn generate(seed: u64, size: &Size) -> String {
+    numbers(seed, size).to_string()
+}
+
+/// The numbers of an almanac, the same for the same seed.
+///
+/// Each map cuts `0..span` into ranges and lays them out again in a shuffled order, so
+/// neither the sources nor the destinations of a map overlap.
+pub fn numbers(seed: u64, size: &Size) -> Numbers {
+    let mut rng = rng::seeded(seed);
+    let span = size.span.max(1);
+    // at least one id per range
+    let mappings = size.mappings.clamp(1, span as usize);
+
+    // the seed ranges don't overlap either, each is between two cuts
+    let mut bounds = (0..2 * size.seed_ranges.max(1))
+        .map(|_| rng.gen_range(0..=span))
+        .collect::<Vec<_>>();
+    bounds.sort_unstable();
+    bounds.dedup();
+    let mut seed_ranges = bounds
+        .chunks_exact(2)
+        .map(|bound| (bound[0], bound[1] - bound[0]))
+        .collect::<Vec<_>>();
+    seed_ranges.shuffle(&mut rng);
+
+    let maps = (1..CATEGORIES.len())
+        .map(|_| {
+            let mut cuts = (1..mappings)
+                .map(|_| rng.gen_range(1..span))
+                .collect::<Vec<_>>();
+            cuts.extend([0, span]);
+            cuts.sort_unstable();
+            cuts.dedup();
+
+            let mut sources = cuts
+                .windows(2)
+                .map(|cut| (cut[0], cut[1] - cut[0]))
+                .collect::<Vec<_>>();
+            sources.shuffle(&mut rng);
+
+            let mut destination = 0;
+            sources
+                .into_iter()
+                .map(|(source, length)| {
+                    destination += length;
+                    (destination - length, source, length)
+                })
+                .collect()
+        })
+        .collect();
+
+    Numbers { seed_ranges, maps }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{part1, part2};
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[test]
+    fn test_generate() {
+        let size = Size {
+            seed_ranges: 3,
+            mappings: 5,
+            span: 1000,
+        };
+        let input = generate(7, &size);
+
+        assert_eq!(input, generate(7, &size));
+        assert_ne!(input, generate(8, &size));
+        assert!(part1(&input).is_ok());
+        // the maps only shuffle the ids below the span
+        assert!(part2(&input).unwrap() < size.span);
+    }
+}
@@ -1,58 +1,111 @@
 use anyhow::{anyhow, Result};
 use disjoint_range::DisjointRange;
-use parser::full;
+use parser::{full, IDMap};
+use range_map::RangeMap;
 
 pub mod disjoint_range;
+pub mod generator;
 pub mod parser;
 pub mod range_map;
 
-pub fn part1(input: &str) -> Result<u64> {
-    let (_, (seeds, listings)) = full(input).expect("Parse successful.");
+/// The seeds to plant and the maps an id goes through on its way from seed to location.
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<IDMap>,
+    // every map chained into one, from seed straight to location
+    pub condensed: RangeMap,
+}
+
+pub fn parse(input: &str) -> Result<Almanac> {
+    let (_, (seeds, maps)) = full(input).map_err(|err| anyhow!("parse error: {err}"))?;
 
-    let condensed_map = listings
-        .into_iter()
-        .map(|idmap| idmap.mappings)
+    let condensed = maps
+        .iter()
+        .map(|idmap| idmap.mappings.clone())
         .reduce(|lhs, rhs| lhs.concatenate(&rhs))
-        .expect("Condense map should not fail.");
+        .ok_or_else(|| anyhow!("no maps"))?;
 
-    Ok(seeds
-        .into_iter()
-        .map(|seed| condensed_map.map(seed))
-        .min()
-        .unwrap())
+    Ok(Almanac {
+        seeds,
+        maps,
+        condensed,
+    })
 }
 
-pub fn part2(input: &str) -> Result<u64> {
-    let (_, (seeds, listings)) = full(input).expect("Parse successful.");
-
-    let condensed_map = listings
-        .into_iter()
-        .map(|idmap| idmap.mappings)
-        .reduce(|lhs, rhs| lhs.concatenate(&rhs))
-        .expect("Condense map should not fail.");
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
+}
 
-    // expand seeds into ranges
-    let mut seed_ranges: Vec<DisjointRange> = seeds
-        .chunks(2)
-        .map(|pair| (pair[0]..=(pair[0] + pair[1] - 1)).into())
-        .collect();
-    seed_ranges.sort();
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
 
-    // find the ranges that will transform into lowest location in the condensed map.
-    let mut seed_domains: Vec<DisjointRange> = seed_ranges
-        .iter()
-        .flat_map(|range| condensed_map.map_range(*range))
-        .collect();
+impl Almanac {
+    pub fn part1(&self) -> Result<u64> {
+        self.seeds
+            .iter()
+            .map(|&seed| self.condensed.map(seed))
+            .min()
+            .ok_or_else(|| anyhow!("no seeds"))
+    }
 
-    seed_domains.sort();
+    pub fn part2(&self) -> Result<u64> {
+        let pairs = self.seeds.chunks_exact(2);
+        if !pairs.remainder().is_empty() {
+            return Err(anyhow!("the seeds are not pairs of start and length"));
+        }
+
+        // expand seeds into ranges, one of no seeds is left out
+        let mut seed_ranges = vec![];
+        for pair in pairs {
+            let (start, length) = (pair[0], pair[1]);
+            let Some(last) = length.checked_sub(1) else {
+                continue;
+            };
+            let end = start
+                .checked_add(last)
+                .ok_or_else(|| anyhow!("the seeds {start} to {start} + {length} overflow"))?;
+            seed_ranges.push(DisjointRange::from(start..=end));
+        }
+        seed_ranges.sort_by_key(|range| range.start);
+
+        // find the ranges that will transform into lowest location in the condensed map.
+        let seed_domains: Vec<DisjointRange> = seed_ranges
+            .iter()
+            .flat_map(|range| self.condensed.map_range(*range))
+            .collect();
+
+        seed_domains
+            .iter()
+            .map(|domain| domain.start)
+            .min()
+            .ok_or_else(|| anyhow!("no seeds"))
+    }
 
-    Ok(seed_domains[0].start)
+    /// The id of `seed` in each category, from the seed itself to its location.
+    pub fn follow(&self, seed: u64) -> Vec<(&str, u64)> {
+        let first = self
+            .maps
+            .first()
+            .map_or("seed", |idmap| idmap.source.as_str());
+        let mut ids = vec![(first, seed)];
+        for idmap in &self.maps {
+            let (_, id) = ids[ids.len() - 1];
+            ids.push((idmap.target.as_str(), idmap.transfer(id)));
+        }
+
+        ids
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_core::differential::{self, without_one};
     use rstest::rstest;
 
+    use crate::generator::{self, Numbers, Size};
+
     use super::*;
 
     #[test]
@@ -64,16 +117,74 @@ mod tests {
     #[case(55, 86)]
     #[case(13, 35)]
     fn test_mapping(#[case] seed: u64, #[case] location: u64) {
-        let fixture = include_str!("../test-1.txt");
+        let almanac = parse(include_str!("../test-1.txt")).expect("Parse successful.");
 
-        let (_, (_, listings)) = full(fixture).expect("Parse successful.");
+        assert_eq!(almanac.condensed.map(seed), location);
+        assert_eq!(Some(&("location", location)), almanac.follow(seed).last());
+    }
 
-        let condensed_map = listings
-            .into_iter()
-            .map(|idmap| idmap.mappings)
-            .reduce(|lhs, rhs| lhs.concatenate(&rhs))
-            .expect("Condense map should not fail.");
+    // every seed through every map, one at a time
+    fn lowest_location(numbers: &Numbers) -> u64 {
+        let almanac = parse(&numbers.to_string()).expect("Parse successful.");
+
+        numbers
+            .seed_ranges
+            .iter()
+            .flat_map(|&(start, length)| start..start + length)
+            .map(|seed| {
+                almanac
+                    .maps
+                    .iter()
+                    .fold(seed, |id, listing| listing.transfer(id))
+            })
+            .min()
+            .expect("at least a seed")
+    }
 
-        assert_eq!(condensed_map.map(seed), location);
+    // a seed range less or a shorter one. the maps stay whole, `concatenate` needs each
+    // of them to lay the ids out again without overlaps, like the real input does
+    fn smaller(numbers: &Numbers) -> Vec<Numbers> {
+        let mut smaller = vec![];
+        if numbers.seed_ranges.len() > 1 {
+            for seed_ranges in without_one(&numbers.seed_ranges) {
+                smaller.push(Numbers {
+                    seed_ranges,
+                    ..numbers.clone()
+                });
+            }
+        }
+        for (idx, &(start, length)) in numbers.seed_ranges.iter().enumerate() {
+            if length > 1 {
+                let mut seed_ranges = numbers.seed_ranges.clone();
+                seed_ranges[idx] = (start, length / 2);
+                smaller.push(Numbers {
+                    seed_ranges,
+                    ..numbers.clone()
+                });
+            }
+        }
+        smaller
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    fn test_differential_map_range(#[case] seed: u64) {
+        let size = Size {
+            seed_ranges: 4,
+            mappings: 6,
+            span: 200,
+        };
+        let numbers = (0..100).map(|idx| generator::numbers(seed * 1000 + idx, &size));
+
+        let result = differential::check(
+            numbers,
+            lowest_location,
+            |numbers| part2(&numbers.to_string()).expect("Part 2 failed to run"),
+            smaller,
+        );
+        if let Err(counterexample) = result {
+            panic!("{counterexample}");
+        }
     }
 }
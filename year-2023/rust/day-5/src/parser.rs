@@ -3,16 +3,19 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{self, alpha1, line_ending},
-    combinator::{all_consuming, eof},
+    combinator::{all_consuming, eof, map_opt},
     multi::separated_list1,
-    sequence::{delimited, separated_pair, terminated},
+    sequence::{delimited, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
-use crate::range_map::RangeMap;
+use crate::{
+    disjoint_range::{have_overlap, is_adjacent, DisjointRange},
+    range_map::RangeMap,
+};
 // endregion: --- Modules
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IDMap {
     pub source: String,
     pub target: String,
@@ -30,35 +33,79 @@ impl IDMap {
 fn id_map(input: &str) -> IResult<&str, IDMap> {
     let (input, (source, target)) =
         terminated(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map:\n"))(input)?;
-    let (rest, mut mappings_raw) = terminated(
-        separated_list1(line_ending, separated_list1(tag(" "), complete::u64)),
+    let (rest, mappings) = terminated(
+        map_opt(separated_list1(line_ending, mapping), range_map),
         alt((line_ending, eof)),
     )(input)?;
 
-    let source = source.to_owned();
-    let target = target.to_owned();
-    mappings_raw.sort_by(|lhs, rhs| lhs[1].cmp(&rhs[1]));
-
-    // build RangeMap here
-    let mut mappings = RangeMap::build();
-    for mapping in mappings_raw {
-        mappings = mappings.insert(
-            (mapping[1]..=mapping[1] + mapping[2] - 1).into(),
-            (mapping[0]..=mapping[0] + mapping[2] - 1).into(),
-        );
-    }
-    let mappings = mappings.fill_gaps();
-
     Ok((
         rest,
         IDMap {
-            source,
-            target,
+            source: source.to_owned(),
+            target: target.to_owned(),
             mappings,
         },
     ))
 }
 
+// destination, source and length, a range of no ids or past u64 is no mapping
+fn mapping(input: &str) -> IResult<&str, (DisjointRange, DisjointRange)> {
+    map_opt(
+        tuple((
+            complete::u64,
+            preceded(tag(" "), complete::u64),
+            preceded(tag(" "), complete::u64),
+        )),
+        |(target, source, length)| {
+            let last = length.checked_sub(1)?;
+            Some((
+                (source..=source.checked_add(last)?).into(),
+                (target..=target.checked_add(last)?).into(),
+            ))
+        },
+    )(input)
+}
+
+// `concatenate` needs every map to lay the ids out again: the sources do not overlap,
+// nor do the targets, and together they cover the same ids
+fn range_map(mappings: Vec<(DisjointRange, DisjointRange)>) -> Option<RangeMap> {
+    let mut sources = mappings
+        .iter()
+        .map(|(source, _)| *source)
+        .collect::<Vec<_>>();
+    let mut targets = mappings
+        .iter()
+        .map(|(_, target)| *target)
+        .collect::<Vec<_>>();
+    if covered(&mut sources)? != covered(&mut targets)? {
+        return None;
+    }
+
+    let builder = mappings
+        .into_iter()
+        .fold(RangeMap::build(), |builder, (source, target)| {
+            builder.insert(source, target)
+        });
+
+    Some(builder.fill_gaps())
+}
+
+// the ids the ranges cover, each stretch of adjacent ranges joined, or none if two overlap
+fn covered(ranges: &mut [DisjointRange]) -> Option<Vec<DisjointRange>> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut stretches: Vec<DisjointRange> = vec![];
+    for range in ranges.iter() {
+        match stretches.last_mut() {
+            Some(last) if have_overlap(last, range) => return None,
+            Some(last) if is_adjacent(last, range) => last.end = range.end,
+            _ => stretches.push(*range),
+        }
+    }
+
+    Some(stretches)
+}
+
 fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
     delimited(
         tag("seeds: "),
@@ -75,8 +122,6 @@ pub fn full(input: &str) -> IResult<&str, (Vec<u64>, Vec<IDMap>)> {
     let (input, seeds) = seeds(input)?;
     let (_, listings) = all_consuming(listings)(input)?;
 
-    assert!(listings.len() == 7);
-
     Ok(("", (seeds, listings)))
 }
 
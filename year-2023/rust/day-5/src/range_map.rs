@@ -243,8 +243,8 @@ impl RangeMapBuilder {
         let mut untracked_start = 0;
         let keys: Vec<DisjointRange> = self.map.keys().cloned().collect();
         for key in &keys {
-            // if there are gaps
-            if key.start - untracked_start > 1 {
+            // if there are gaps, a single id is one too
+            if key.start > untracked_start {
                 let identical_mapping: DisjointRange = (untracked_start..=key.start - 1).into();
                 self.map.insert(identical_mapping, identical_mapping);
             }
@@ -1,7 +1,7 @@
 use day_6::part2;
 
 fn main() {
-    let result = part2(include_str!("../../input.txt")).expect("Part 2 failed to run");
+    let result = part2(&aoc_core::input!()).expect("Part 2 failed to run");
 
     println!("{result}");
 }
@@ -17,7 +17,7 @@ mod tests {
 
     #[rstest]
     #[case(71503)]
-    fn test_using_test_input_1(#[case] expected: u64) {
+    fn test_using_test_input_1(#[case] expected: u128) {
         let result = part2(include_str!("../../test-1.txt")).expect("Part 2 failed to run");
 
         assert_eq!(expected, result);
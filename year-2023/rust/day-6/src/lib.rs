@@ -1,4 +1,5 @@
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use aoc_core::debug;
 use nom::{
     bytes::complete::tag,
     character::complete::{self, line_ending, space0, space1},
@@ -8,38 +9,47 @@ use nom::{
     IResult,
 };
 
-pub fn part1(input: &str) -> Result<u64> {
-    let (_, (time, dist)) = parse_time_distance(input).unwrap();
+/// The time of each race and the distance of its record, in the order of the sheet.
+#[derive(Debug, Clone)]
+pub struct Races {
+    pub times: Vec<u64>,
+    pub records: Vec<u64>,
+}
+
+pub fn parse(input: &str) -> Result<Races> {
+    let (_, (times, records)) =
+        parse_time_distance(input).map_err(|err| anyhow!("parse error: {err}"))?;
+
+    Ok(Races { times, records })
+}
+
+pub fn part1(input: &str) -> Result<u128> {
+    parse(input)?.part1()
+}
 
-    Ok(time
-        .iter()
-        .zip(dist.iter())
-        .map(|(time, record)| numbers_of_ways_to_win(*time, *record))
-        .product::<u64>())
+pub fn part2(input: &str) -> Result<u128> {
+    parse(input)?.part2()
 }
 
-pub fn part2(input: &str) -> Result<u64> {
-    let (_, (times, dists)) = parse_time_distance(input).unwrap();
-
-    let time = times
-        .into_iter()
-        .map(|t| t.to_string())
-        .collect::<Vec<String>>()
-        .join("")
-        .parse::<u64>()
-        .unwrap();
-    let record = dists
-        .into_iter()
-        .map(|t| t.to_string())
-        .collect::<Vec<String>>()
-        .join("")
-        .parse::<u64>()
-        .unwrap();
-
-    dbg!(&time);
-    dbg!(&record);
-
-    Ok(numbers_of_ways_to_win(time, record))
+impl Races {
+    pub fn part1(&self) -> Result<u128> {
+        self.times
+            .iter()
+            .zip(self.records.iter())
+            .map(|(time, record)| numbers_of_ways_to_win((*time).into(), (*record).into()))
+            .try_fold(1u128, |product, ways| product.checked_mul(ways))
+            .ok_or_else(|| anyhow!("the product of the ways to win overflows"))
+    }
+
+    // the races read as one, which takes a long enough line past u64
+    pub fn part2(&self) -> Result<u128> {
+        let time = concatenate(&self.times).context("the time of the race")?;
+        let record = concatenate(&self.records).context("the record of the race")?;
+
+        debug!("time: {time}, record: {record}");
+
+        Ok(numbers_of_ways_to_win(time, record))
+    }
 }
 
 // region:    --- Parsing
@@ -63,20 +73,43 @@ fn parse_time_distance(input: &str) -> IResult<&str, (Vec<u64>, Vec<u64>)> {
 
 // region:    --- Part 1
 
-fn distance_travelled(acceleration: u64, hold: u64, time: u64) -> u64 {
-    (time - hold) * (acceleration * hold)
+// None past u128, further than any record
+fn distance_travelled(acceleration: u128, hold: u128, time: u128) -> Option<u128> {
+    (time - hold).checked_mul(acceleration.checked_mul(hold)?)
 }
 
-fn numbers_of_ways_to_win(time: u64, record: u64) -> u64 {
-    (1..time)
-        .filter(|hold| distance_travelled(1, *hold, time) > record)
-        .count() as u64
+// the distance grows up to half the time and shrinks back the same way, so the holds that win
+// lie between the first one that does and its mirror
+fn numbers_of_ways_to_win(time: u128, record: u128) -> u128 {
+    let wins = |hold| distance_travelled(1, hold, time).is_none_or(|distance| distance > record);
+    if !wins(time / 2) {
+        return 0;
+    }
+
+    let (mut low, mut high) = (0, time / 2);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if wins(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    time - 2 * low + 1
 }
 
 // endregion: --- Part 1
 
 // region:    --- Part 2
 
+fn concatenate(numbers: &[u64]) -> Result<u128> {
+    let digits = numbers.iter().map(u64::to_string).collect::<String>();
+    digits
+        .parse()
+        .with_context(|| format!("{digits} does not fit in u128"))
+}
+
 // endregion: --- Part 2
 
 #[cfg(test)]
@@ -104,19 +137,36 @@ mod tests {
     #[case(1, 6, 7, 6)]
     #[case(1, 7, 7, 0)]
     fn test_distance_travelled(
-        #[case] acceleration: u64,
-        #[case] hold: u64,
-        #[case] time: u64,
-        #[case] expected: u64,
+        #[case] acceleration: u128,
+        #[case] hold: u128,
+        #[case] time: u128,
+        #[case] expected: u128,
     ) {
-        assert_eq!(expected, distance_travelled(acceleration, hold, time))
+        assert_eq!(Some(expected), distance_travelled(acceleration, hold, time))
     }
 
     #[rstest]
     #[case(7, 9, 4)]
     #[case(15, 40, 8)]
     #[case(30, 200, 9)]
-    fn test_numbers_of_ways_to_win(#[case] record: u64, #[case] time: u64, #[case] expected: u64) {
+    #[case(71530, 940200, 71503)]
+    #[case(2, 1, 0)]
+    fn test_numbers_of_ways_to_win(
+        #[case] record: u128,
+        #[case] time: u128,
+        #[case] expected: u128,
+    ) {
         assert_eq!(expected, numbers_of_ways_to_win(record, time))
     }
+
+    #[test]
+    fn test_part2_past_u64() {
+        // just under 2 * 10^19 ms, past u64, and only the two holds next to the middle win
+        let input =
+            "Time: 1999999999 9999999999\nDistance: 9999999999999999998 9999999999999999999";
+        assert_eq!(2, part2(input).unwrap());
+
+        let input = "Time: 9999999999999999999 9999999999999999999 99\nDistance: 1";
+        assert!(part2(input).is_err());
+    }
 }
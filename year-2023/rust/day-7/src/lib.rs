@@ -1,6 +1,6 @@
 // region:    --- Modules
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use nom::{
     bytes::complete::take_while_m_n,
     character::complete::{self, space1},
@@ -11,34 +11,63 @@ use nom::{
 
 // endregion: --- Modules
 
-pub fn part1(input: &str) -> Result<u64> {
-    let mut hands_and_bids: Vec<(Hand<Rank>, u64)> = input
+/// The cards of each hand and its bid, ranked by the parts with `J` for a jack or a joker.
+#[derive(Debug, Clone)]
+pub struct CamelCards(pub Vec<([char; 5], u64)>);
+
+pub fn parse(input: &str) -> Result<CamelCards> {
+    let hands = input
         .lines()
-        .map(|line| parse_line(line).unwrap().1)
-        .collect();
+        .map(|line| {
+            parse_line::<char>(line)
+                .map(|(_, (hand, bid))| (hand.0, bid))
+                .map_err(|err| anyhow!("parse error: {err}"))
+        })
+        .collect::<Result<_>>()?;
 
-    hands_and_bids.sort_by(|(lhs_hand, _), (rhs_hand, _)| lhs_hand.cmp(rhs_hand));
+    Ok(CamelCards(hands))
+}
 
-    Ok(hands_and_bids
-        .iter()
-        .enumerate()
-        .map(|(rank, (_, bid))| (rank + 1) as u64 * *bid)
-        .sum())
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
 }
 
 pub fn part2(input: &str) -> Result<u64> {
-    let mut hands_and_bids: Vec<(Hand<Rank2>, u64)> = input
-        .lines()
-        .map(|line| parse_line(line).unwrap().1)
-        .collect();
+    parse(input)?.part2()
+}
+
+impl CamelCards {
+    pub fn part1(&self) -> Result<u64> {
+        let mut hands_and_bids: Vec<(Hand<Rank>, u64)> = self
+            .0
+            .iter()
+            .map(|(cards, bid)| (Hand(cards.map(Rank::from)), *bid))
+            .collect();
 
-    hands_and_bids.sort_by(|(lhs_hand, _), (rhs_hand, _)| lhs_hand.cmp(rhs_hand));
+        hands_and_bids.sort_by(|(lhs_hand, _), (rhs_hand, _)| lhs_hand.cmp(rhs_hand));
 
-    Ok(hands_and_bids
-        .iter()
-        .enumerate()
-        .map(|(rank, (_, bid))| (rank + 1) as u64 * *bid)
-        .sum())
+        Ok(hands_and_bids
+            .iter()
+            .enumerate()
+            .map(|(rank, (_, bid))| (rank + 1) as u64 * *bid)
+            .sum())
+    }
+
+    pub fn part2(&self) -> Result<u64> {
+        let mut hands_and_bids: Vec<(Hand<Rank2>, u64)> = self
+            .0
+            .iter()
+            .map(|(cards, bid)| (Hand(cards.map(Rank2::from)), *bid))
+            .collect();
+
+        hands_and_bids.sort_by(|(lhs_hand, _), (rhs_hand, _)| lhs_hand.cmp(rhs_hand));
+
+        Ok(hands_and_bids
+            .iter()
+            .enumerate()
+            .map(|(rank, (_, bid))| (rank + 1) as u64 * *bid)
+            .sum())
+    }
 }
 
 // region:    --- Types
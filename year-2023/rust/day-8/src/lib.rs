@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use aoc_core::{cancel, debug};
 
 use itertools::Itertools;
 
@@ -9,120 +10,218 @@ use crate::{math::factorize, parser::parse_document};
 mod math;
 mod parser;
 
-pub fn part1(input: &str) -> Result<u64> {
+/// The left and right instructions, and where each node leads to either way.
+#[derive(Debug, Clone)]
+pub struct Network {
+    pub instructions: String,
+    pub left_map: HashMap<String, String>,
+    pub right_map: HashMap<String, String>,
+}
+
+pub fn parse(input: &str) -> Result<Network> {
     let (_, (instructions, left_map, right_map)) =
-        parse_document(input).expect("instructions and directions");
+        parse_document(input).map_err(|err| anyhow!("parse error: {err}"))?;
+    let owned = |map: HashMap<&str, &str>| {
+        map.into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect()
+    };
+
+    Ok(Network {
+        instructions: instructions.to_string(),
+        left_map: owned(left_map),
+        right_map: owned(right_map),
+    })
+}
 
-    let mut instructions = instructions.chars().cycle();
+pub fn part1(input: &str) -> Result<u64> {
+    parse(input)?.part1()
+}
 
-    let mut pos = "AAA";
-    let mut steps = 0;
-    while pos != "ZZZ" {
-        let Some(dir) = instructions.next() else {
-            panic!("no instructions")
-        };
-
-        if dir == 'L' {
-            pos = left_map.get(pos).expect("entry in left map");
-        } else {
-            pos = right_map.get(pos).expect("entry in right map");
+pub fn part2(input: &str) -> Result<u64> {
+    parse(input)?.part2()
+}
+
+impl Network {
+    pub fn part1(&self) -> Result<u64> {
+        let mut instructions = self.instructions.chars().enumerate().cycle();
+
+        let mut pos = "AAA";
+        let mut steps = 0;
+        // a node reached twice at the same instruction is a loop without ZZZ
+        let mut seen = HashSet::new();
+        while pos != "ZZZ" {
+            cancel::check()?;
+
+            let Some((index, dir)) = instructions.next() else {
+                return Err(anyhow!("no instructions"));
+            };
+            if !seen.insert((pos, index)) {
+                return Err(anyhow!("no way from AAA to ZZZ"));
+            }
+            pos = step(pos, dir, &self.left_map, &self.right_map)?;
+
+            steps += 1;
         }
 
-        steps += 1;
+        Ok(steps)
     }
 
-    Ok(steps)
-}
-
-pub fn part2(input: &str) -> Result<u64> {
-    let (_, (instructions, left_map, right_map)) =
-        parse_document(input).expect("instructions and directions");
-
-    let instructions = instructions.chars().cycle();
-
-    dbg!(left_map.keys().filter(|key| key.ends_with('A')).count());
-
-    let starts = left_map
-        .keys()
-        .filter(|key| key.ends_with('A'))
-        .collect::<Vec<_>>();
-
-    let z_pos_appearances: Vec<_> = starts
-        .iter()
-        .map(|pos| {
-            let z_steps = steps_end_on_z(pos, instructions.clone(), &left_map, &right_map, 1);
-            z_steps[0]
-        })
-        .collect();
-    // dbg!(&z_pos_appearances);
-
-    let all_factors: Vec<_> = z_pos_appearances
-        .iter()
-        .map(|(steps, _)| factorize(*steps))
-        .collect();
-    // dbg!(&all_factors);
-
-    let mut factors_counted = all_factors
-        .into_iter()
-        .flat_map(|factors| {
-            factors
-                .into_iter()
-                .dedup_with_count()
-                .collect::<Vec<(usize, u64)>>()
-        })
-        .collect::<Vec<(usize, u64)>>();
-
-    factors_counted.sort_by(|lhs, rhs| {
-        if lhs.1 == rhs.1 {
-            lhs.0.cmp(&rhs.0)
-        } else {
-            lhs.1.cmp(&rhs.1)
+    pub fn part2(&self) -> Result<u64> {
+        let instructions = self.instructions.chars().enumerate().cycle();
+
+        debug!(
+            "{} starting nodes",
+            self.left_map
+                .keys()
+                .filter(|key| key.ends_with('A'))
+                .count()
+        );
+
+        let starts = self
+            .left_map
+            .keys()
+            .filter(|key| key.ends_with('A'))
+            .collect::<Vec<_>>();
+        if starts.is_empty() {
+            return Err(anyhow!("no node ending on A"));
         }
-    });
-
-    let mut factors = HashMap::new();
-    for (new_count, factor) in factors_counted {
-        factors
-            .entry(factor)
-            .and_modify(|count| {
-                if new_count > *count {
-                    *count = new_count
+
+        // the least common multiple only answers if every start loops back to its Z in as many
+        // steps as it took to get there, so the second Z is checked to be twice as far
+        let z_pos_appearances: Vec<_> = starts
+            .iter()
+            .map(|pos| {
+                let z_steps = steps_end_on_z(
+                    pos,
+                    instructions.clone(),
+                    &self.left_map,
+                    &self.right_map,
+                    2,
+                )?;
+                let [first, second] = z_steps[..] else {
+                    return Err(anyhow!("no instructions"));
+                };
+                if second.0 != 2 * first.0 {
+                    return Err(anyhow!(
+                        "{pos} reaches a node ending on Z after {} and {} steps, not a loop",
+                        first.0,
+                        second.0
+                    ));
                 }
+
+                Ok(first)
             })
-            .or_insert(new_count);
-    }
+            .collect::<Result<_>>()?;
+        // dbg!(&z_pos_appearances);
+
+        let all_factors: Vec<_> = z_pos_appearances
+            .iter()
+            .map(|(steps, _)| factorize(*steps))
+            .collect();
+        // dbg!(&all_factors);
+
+        let mut factors_counted = all_factors
+            .into_iter()
+            .flat_map(|factors| {
+                factors
+                    .into_iter()
+                    .dedup_with_count()
+                    .collect::<Vec<(usize, u64)>>()
+            })
+            .collect::<Vec<(usize, u64)>>();
 
-    Ok(factors
-        .into_iter()
-        .map(|(factor, count)| factor.pow(count as u32))
-        .product::<u64>())
+        factors_counted.sort_by(|lhs, rhs| {
+            if lhs.1 == rhs.1 {
+                lhs.0.cmp(&rhs.0)
+            } else {
+                lhs.1.cmp(&rhs.1)
+            }
+        });
+
+        let mut factors = HashMap::new();
+        for (new_count, factor) in factors_counted {
+            factors
+                .entry(factor)
+                .and_modify(|count| {
+                    if new_count > *count {
+                        *count = new_count
+                    }
+                })
+                .or_insert(new_count);
+        }
+
+        Ok(factors
+            .into_iter()
+            .map(|(factor, count)| factor.pow(count as u32))
+            .product::<u64>())
+    }
 }
 
 fn steps_end_on_z<'a>(
     starting_pos: &'a str,
-    instructions: impl Iterator<Item = char>,
-    left_map: &HashMap<&'a str, &'a str>,
-    right_map: &HashMap<&'a str, &'a str>,
+    instructions: impl Iterator<Item = (usize, char)>,
+    left_map: &'a HashMap<String, String>,
+    right_map: &'a HashMap<String, String>,
     take: usize,
-) -> Vec<(u64, &'a str)> {
+) -> Result<Vec<(u64, &'a str)>> {
     let mut steps = 0;
     let mut pos = starting_pos;
 
-    instructions
-        .filter_map(|dir| {
-            if dir == 'L' {
-                pos = left_map.get(pos).expect("entry in left map");
-            } else {
-                pos = right_map.get(pos).expect("entry in right map");
-            }
+    let mut z_steps = vec![];
+    // between two Z, a node reached twice at the same instruction is a loop without one
+    let mut seen = HashSet::new();
+    for (index, dir) in instructions {
+        if z_steps.len() == take {
+            break;
+        }
+        cancel::check()?;
+        if !seen.insert((pos, index)) {
+            return Err(anyhow!("no way from {starting_pos} to a node ending on Z"));
+        }
 
-            steps += 1;
-            if pos.ends_with('Z') {
-                Some((steps, pos))
-            } else {
-                None
-            }
-        })
-        .take(take)
-        .collect()
+        pos = step(pos, dir, left_map, right_map)?;
+        steps += 1;
+        if pos.ends_with('Z') {
+            z_steps.push((steps, pos));
+            seen.clear();
+        }
+    }
+
+    Ok(z_steps)
+}
+
+// a node the map does not list is a dead end
+fn step<'a>(
+    pos: &str,
+    dir: char,
+    left_map: &'a HashMap<String, String>,
+    right_map: &'a HashMap<String, String>,
+) -> Result<&'a str> {
+    let map = if dir == 'L' { left_map } else { right_map };
+
+    map.get(pos)
+        .map(String::as_str)
+        .ok_or_else(|| anyhow!("no node {pos}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn quick_test() {}
+
+    #[rstest]
+    // no start at all
+    #[case("LR\n\n11B = (11Z, 11Z)\n11Z = (11B, 11B)")]
+    // the second Z is 3 steps after the first one, which is 1 step away
+    #[case("L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11C, 11C)\n11C = (11Z, 11Z)")]
+    // never back on a Z
+    #[case("L\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11B, 11B)")]
+    fn test_part2_rejects(#[case] input: &str) {
+        assert!(part2(input).is_err());
+    }
 }
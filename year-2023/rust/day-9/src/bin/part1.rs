@@ -1,7 +1,7 @@
 use day_9::part1;
 
 fn main() {
-    let result = part1(include_str!("../../input.txt")).expect("Part 1 failed to run");
+    let result = part1(&aoc_core::input!()).expect("Part 1 failed to run");
 
     println!("{result}");
 }
@@ -17,7 +17,7 @@ mod tests {
 
     #[rstest]
     #[case(114)]
-    fn test_using_test_input_1(#[case] expected: i64) {
+    fn test_using_test_input_1(#[case] expected: i128) {
         let result = part1(include_str!("../../test-1.txt")).expect("Part 1 failed to run");
 
         assert_eq!(expected, result);
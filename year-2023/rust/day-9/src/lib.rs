@@ -1,5 +1,5 @@
 // region:    --- Modules
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use math::ncr;
 use nom::{
     character::complete::{self, line_ending, space1},
@@ -10,22 +10,34 @@ use nom::{
 mod math;
 // endregion: --- Modules
 
-pub fn part1(input: &str) -> Result<i64> {
-    let list_of_numbers = parse_file(input);
+/// The history of each value of the report, oldest first.
+#[derive(Debug, Clone)]
+pub struct Report(pub Vec<Vec<i64>>);
 
-    Ok(list_of_numbers
-        .into_iter()
-        .map(|numbers| extrapolate(&numbers))
-        .sum())
+pub fn parse(input: &str) -> Result<Report> {
+    Ok(Report(parse_file(input)?))
 }
 
-pub fn part2(input: &str) -> Result<i64> {
-    let list_of_numbers = parse_file(input);
+pub fn part1(input: &str) -> Result<i128> {
+    parse(input)?.part1()
+}
 
-    Ok(list_of_numbers
-        .into_iter()
-        .map(|numbers| extrapolate_backward(&numbers))
-        .sum())
+pub fn part2(input: &str) -> Result<i128> {
+    parse(input)?.part2()
+}
+
+impl Report {
+    // the values past the end of a line grow fast, the sum is kept in i128
+    pub fn part1(&self) -> Result<i128> {
+        self.0.iter().map(|numbers| extrapolate(numbers)).sum()
+    }
+
+    pub fn part2(&self) -> Result<i128> {
+        self.0
+            .iter()
+            .map(|numbers| extrapolate_backward(numbers))
+            .sum()
+    }
 }
 
 // region:    --- Parsing
@@ -33,10 +45,10 @@ fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
     separated_list1(space1, complete::i64)(input)
 }
 
-fn parse_file(input: &str) -> Vec<Vec<i64>> {
-    let (_, vec_of_numbers) =
-        all_consuming(separated_list1(line_ending, parse_line))(input).expect("parse complete");
-    vec_of_numbers
+fn parse_file(input: &str) -> Result<Vec<Vec<i64>>> {
+    let (_, vec_of_numbers) = all_consuming(separated_list1(line_ending, parse_line))(input)
+        .map_err(|err| anyhow!("parse error: {err}"))?;
+    Ok(vec_of_numbers)
 }
 // endregion: --- Parsing
 
@@ -54,53 +66,68 @@ where
     iter.all(|elem| elem == first_elem)
 }
 
-fn find_seeds(numbers: &[i64]) -> Vec<i64> {
-    assert!(numbers.len() > 1);
-
+// a single number is a constant history
+fn find_seeds(numbers: &[i64]) -> Result<Vec<i64>> {
     match numbers.len() {
+        0 | 1 => Ok(numbers.to_vec()),
         2 => {
             if numbers[0] == numbers[1] {
-                vec![numbers[0]]
+                Ok(vec![numbers[0]])
             } else {
-                vec![numbers[0], numbers[1] - numbers[0]]
+                Ok(vec![numbers[0], difference(numbers[0], numbers[1])?])
             }
         }
         _ => {
             let diffs: Vec<_> = numbers
                 .windows(2)
-                .map(|pairs| pairs[1] - pairs[0])
-                .collect();
+                .map(|pairs| difference(pairs[0], pairs[1]))
+                .collect::<Result<_>>()?;
 
             if all_equal(diffs.iter()) {
-                vec![numbers[0], diffs[0]]
+                Ok(vec![numbers[0], diffs[0]])
             } else {
                 let mut result = vec![numbers[0]];
-                result.append(&mut find_seeds(&diffs));
-                result
+                result.append(&mut find_seeds(&diffs)?);
+                Ok(result)
             }
         }
     }
 }
 
-fn extrapolate(numbers: &[i64]) -> i64 {
-    let seeds = find_seeds(numbers);
+fn difference(lhs: i64, rhs: i64) -> Result<i64> {
+    rhs.checked_sub(lhs)
+        .ok_or_else(|| anyhow!("the difference of {lhs} and {rhs} overflows"))
+}
+
+fn extrapolate(numbers: &[i64]) -> Result<i128> {
+    let seeds = find_seeds(numbers)?;
+    let n = numbers.len();
 
     seeds
         .into_iter()
         .enumerate()
-        .map(|(r, seed)| ncr(numbers.len() as u64, r as u64) as i64 * seed)
-        .sum()
+        .try_fold(0i128, |sum, (r, seed)| {
+            let ways = ncr(n as u64, r as u64)
+                .and_then(|ways| i128::try_from(ways).ok())
+                .ok_or_else(|| anyhow!("{n} choose {r} overflows"))?;
+            ways.checked_mul(seed.into())
+                .and_then(|term| sum.checked_add(term))
+                .ok_or_else(|| anyhow!("the value after {numbers:?} overflows"))
+        })
 }
 
 // endregion: --- Part 1
 
 // region:    --- Part 2
 
-fn extrapolate_backward(numbers: &[i64]) -> i64 {
-    let mut seeds = find_seeds(numbers);
-    seeds.reverse();
+fn extrapolate_backward(numbers: &[i64]) -> Result<i128> {
+    let seeds = find_seeds(numbers)?;
 
-    seeds.into_iter().reduce(|acc, rhs| rhs - acc).unwrap()
+    Ok(seeds
+        .into_iter()
+        .rev()
+        .map(i128::from)
+        .fold(0, |acc, rhs| rhs - acc))
 }
 
 // endregion: --- Part 2
@@ -114,13 +141,13 @@ mod tests {
     #[test]
     fn quick_test() {
         let (_, numbers) = parse_line("10 13 16 21 30 45").expect("parse successful");
-        println!("{:?}", find_seeds(&numbers));
+        println!("{:?}", find_seeds(&numbers).unwrap());
         let (_, numbers) = parse_line("22 31 52 90 144 206 260 281 234 73 -260 -836 -1740 -3072 -4948 -7501 -10882 -15261 -20828 -27794 -36392").expect("parse successful");
-        println!("{:?}", find_seeds(&numbers));
+        println!("{:?}", find_seeds(&numbers).unwrap());
         let (_, numbers) = parse_line("12 18 39 90 199 424 889 1853 3829 7788 15539 30516 59516 116587 231569 468274 961829 1992697 4128498 8487217 17211396").expect("parse successful");
         let n_len = numbers.len();
         println!("{n_len}");
-        let seeds = find_seeds(&numbers);
+        let seeds = find_seeds(&numbers).unwrap();
         println!("{:?}", seeds.len());
     }
 
@@ -152,7 +179,7 @@ mod tests {
     #[case(vec![12, 6, 15, 15, 13, 17, 19, 14, 3, 15, 23, 23, 20, 17, 4, 2, 3, -4], "12 18 39 90 199 424 889 1853 3829 7788 15539 30516 59516 116587 231569 468274 961829 1992697 4128498 8487217 17211396")]
     fn test_find_seed_and_diff(#[case] expected: Vec<i64>, #[case] input: &str) {
         let (_, numbers) = parse_line(input).expect("parse successful");
-        assert_eq!(expected, find_seeds(&numbers));
+        assert_eq!(expected, find_seeds(&numbers).unwrap());
     }
 
     #[rstest]
@@ -172,8 +199,8 @@ mod tests {
     #[case(18, "0 3 6 9 12 15")]
     #[case(28, "1 3 6 10 15 21")]
     #[case(68, "10 13 16 21 30 45")]
-    fn test_extrapolation(#[case] expected: i64, #[case] input: &str) {
+    fn test_extrapolation(#[case] expected: i128, #[case] input: &str) {
         let (_, numbers) = parse_line(input).expect("parse successful");
-        assert_eq!(expected, extrapolate(&numbers));
+        assert_eq!(expected, extrapolate(&numbers).unwrap());
     }
 }
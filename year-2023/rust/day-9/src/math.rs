@@ -1,14 +1,14 @@
-pub fn factorial(n: u64) -> u64 {
-    if n == 0 {
-        return 1;
-    }
-    (1..=n).product()
+// None when it does not fit
+pub fn factorial(n: u64) -> Option<u128> {
+    (1..=n).try_fold(1u128, |acc, k| acc.checked_mul(k.into()))
 }
 
-pub fn ncr(n: u64, r: u64) -> u64 {
+// the product is the biggest number on the way, up to 34! for 34 numbers on a line
+pub fn ncr(n: u64, r: u64) -> Option<u128> {
     assert!(n > 0);
 
-    (n - r + 1..=n).product::<u64>() / factorial(r)
+    let product = (n - r + 1..=n).try_fold(1u128, |acc, k| acc.checked_mul(k.into()))?;
+    Some(product / factorial(r)?)
 }
 
 #[cfg(test)]
@@ -23,8 +23,8 @@ mod tests {
     #[case(2, 2)]
     #[case(6, 3)]
     #[case(24, 4)]
-    fn test_factorial(#[case] expected: u64, #[case] input: u64) {
-        assert_eq!(expected, factorial(input));
+    fn test_factorial(#[case] expected: u128, #[case] input: u64) {
+        assert_eq!(Some(expected), factorial(input));
     }
 
     #[rstest]
@@ -34,7 +34,15 @@ mod tests {
     #[case(1, 2, 2)]
     #[case(10, 5, 2)]
     #[case(15, 6, 4)]
-    fn test_ncr(#[case] expected: u64, #[case] n: u64, #[case] r: u64) {
-        assert_eq!(expected, ncr(n, r));
+    // 21! is past u64
+    #[case(21, 21, 20)]
+    fn test_ncr(#[case] expected: u128, #[case] n: u64, #[case] r: u64) {
+        assert_eq!(Some(expected), ncr(n, r));
+    }
+
+    #[test]
+    fn test_ncr_overflow() {
+        assert_eq!(None, factorial(35));
+        assert_eq!(None, ncr(40, 39));
     }
 }